@@ -4,17 +4,20 @@
 Client should be reused for multiple requests.
 """
 
+from collections.abc import Callable, Sequence
 from datetime import timedelta
-from typing import Any, Self
+from os import PathLike
+from typing import Any, Literal, Self
 
-from pyreqwest.cookie import CookieStore
+from pyreqwest.cookie import Cookie, CookieStore
 from pyreqwest.http import Url
+from pyreqwest.middleware.retry import RetryPolicy
 from pyreqwest.middleware.types import Middleware, SyncMiddleware
 from pyreqwest.proxy import ProxyBuilder
-from pyreqwest.request import RequestBuilder, SyncRequestBuilder
-from pyreqwest.types import HeadersType
+from pyreqwest.request import RequestBuilder, StreamRequest, SyncRequestBuilder, SyncStreamRequest
+from pyreqwest.types import HeadersType, QueryParams
 
-from .types import JsonDumps, JsonLoads, SyncJsonLoads, TlsVersion
+from .types import JsonDumps, JsonLoads, RecordSink, SyncJsonLoads, TlsVersion
 
 class BaseClient:
     """Common base for async and sync clients."""
@@ -38,8 +41,16 @@ class Client(BaseClient):
         Returns a request builder, which will allow setting headers and the request body before sending.
         """
 
-    def get(self, url: Url | str) -> RequestBuilder:
-        """Same as `request("GET", url)`."""
+    def get(
+        self,
+        url: Url | str,
+        *,
+        params: QueryParams | None = None,
+        headers: HeadersType | None = None,
+    ) -> RequestBuilder:
+        """Same as `request("GET", url)`. Optional params and headers are applied like `query(params)` and
+        `headers(headers)` on the returned builder.
+        """
 
     def post(self, url: Url | str) -> RequestBuilder:
         """Same as `request("POST", url)`."""
@@ -56,8 +67,11 @@ class Client(BaseClient):
     def head(self, url: Url | str) -> RequestBuilder:
         """Same as `request("HEAD", url)`."""
 
+    def stream(self, method: str, url: Url | str) -> StreamRequest:
+        """Same as `request(method, url).build_streamed()`. Use as an async context manager."""
+
     async def close(self) -> None:
-        """Close the client."""
+        """Close the client. Closing again is a no-op. Creating new requests afterwards raises ClientClosedError."""
 
 class SyncClient(BaseClient):
     """Synchronous HTTP client. Inspired by reqwest's Client.
@@ -78,8 +92,16 @@ class SyncClient(BaseClient):
         Returns a request builder, which will allow setting headers and the request body before sending.
         """
 
-    def get(self, url: Url | str) -> SyncRequestBuilder:
-        """Same as `request("GET", url)`."""
+    def get(
+        self,
+        url: Url | str,
+        *,
+        params: QueryParams | None = None,
+        headers: HeadersType | None = None,
+    ) -> SyncRequestBuilder:
+        """Same as `request("GET", url)`. Optional params and headers are applied like `query(params)` and
+        `headers(headers)` on the returned builder.
+        """
 
     def post(self, url: Url | str) -> SyncRequestBuilder:
         """Same as `request("POST", url)`."""
@@ -96,8 +118,11 @@ class SyncClient(BaseClient):
     def head(self, url: Url | str) -> SyncRequestBuilder:
         """Same as `request("HEAD", url)`."""
 
+    def stream(self, method: str, url: Url | str) -> SyncStreamRequest:
+        """Same as `request(method, url).build_streamed()`. Use as a context manager."""
+
     def close(self) -> None:
-        """Close the client."""
+        """Close the client. Closing again is a no-op. Creating new requests afterwards raises ClientClosedError."""
 
 class BaseClientBuilder:
     def base_url(self, url: Url | str) -> Self:
@@ -107,45 +132,118 @@ class BaseClientBuilder:
         """Use a custom runtime (advanced scenarios). Usually not needed. By default, a global runtime is used."""
 
     def max_connections(self, max_connections: int | None) -> Self:
-        """Maximum number of inflight requests. None means no limit. Default is None."""
+        """Maximum number of inflight requests. None means no limit. Default is None.
+        The limit is shared by all requests of the client, regardless of the used proxy.
+        """
 
-    def error_for_status(self, enable: bool) -> Self:
+    def error_for_status(self, enable: bool = True) -> Self:
         """Enable automatic HTTP error raising (4xx/5xx)."""
 
+    def request_jitter(self, max_delay: timedelta | None) -> Self:
+        """Delay each request by a random duration between zero and max_delay before sending it.
+        Useful to desynchronize periodic callers. Default is None (no delay).
+        """
+
+    def retry_on_incomplete_message(self, enable: bool = True) -> Self:
+        """Retry idempotent requests once when the connection closes before the full response was received.
+        Fully consumed response bodies are then buffered before returning. Default is disabled.
+        """
+
+    def collect_timings(self, enable: bool = True) -> Self:
+        """Record a timing breakdown of each request, available via `Response.timings()`. Default is disabled."""
+
+    def max_response_header_size(self, size: int | None) -> Self:
+        """Maximum total size in bytes of the response header names and values. Larger responses raise ReadError
+        before the body is read. Default is no limit (hyper still limits the HTTP/1 header count).
+        """
+
+    def compress_requests(self, algorithm: Literal["gzip", "deflate"], min_size: int = 1024) -> Self:
+        """Compress in-memory request bodies of at least `min_size` bytes and set Content-Encoding.
+        Streamed bodies and requests with Content-Encoding already set are sent as is.
+        """
+
     def user_agent(self, value: str) -> Self:
         """Sets the User-Agent header to be used by this client (unless overridden).
         Default is `python-pyreqwest/1.0.0`.
         """
 
+    def user_agent_product(self, name: str, version: str, comment: str | None = None) -> Self:
+        """Sets the User-Agent header formatted as `name/version (comment)`.
+        Raises ValueError when name or version is not a valid HTTP token or comment contains invalid characters.
+        """
+
     def default_headers(self, headers: HeadersType) -> Self:
         """Sets the default headers for every request (unless overridden)."""
 
-    def default_cookie_store(self, enable: bool) -> Self:
+    def headers_from_env(self, prefix: str = "PYREQWEST_HEADER_") -> Self:
+        """Add default headers from environment variables starting with the prefix. The rest of the variable name is
+        the header name with underscores replaced by hyphens (e.g. `PYREQWEST_HEADER_X_API_KEY` sets `x-api-key`).
+        Values are read when called. Merged into `default_headers` (call this afterwards), replacing same named headers.
+        """
+
+    def default_cookie_store(self, enable: bool = True) -> Self:
         """Enables default in-memory cookie store. Same as `cookie_store` in reqwest. Default is false."""
 
+    def record(self, sink: RecordSink) -> Self:
+        """Call sink with request and response metadata after each request. Runs as the last middleware.
+        The sink is also called when the request fails, with `error` set.
+        """
+
+    def with_retries_middleware(self, policy: RetryPolicy) -> Self:
+        """Retry all requests of the client according to the policy. See `RetryPolicy` for details.
+
+        Retry middleware runs after the middlewares registered via `with_middleware`.
+        Each attempt is recorded separately by `record`.
+        """
+
     def cookie_provider(self, provider: CookieStore) -> Self:
-        """Set the cookie store for the client. Overrides `default_cookie_store`."""
+        """Set the cookie store for the client. The store can be shared between clients and inspected between requests.
+        Can not be combined with `default_cookie_store(True)`.
+        """
 
-    def gzip(self, enable: bool) -> Self:
+    def cookies(self, initial: Sequence[Cookie | str], url: Url | str) -> Self:
+        """Enable a new cookie store pre-populated with cookies as if set by a response for url.
+        Overrides `cookie_provider`. Can not be combined with `default_cookie_store(True)`.
+        """
+
+    def gzip(self, enable: bool = True) -> Self:
         """Enable auto gzip decompression. Default is true."""
 
-    def brotli(self, enable: bool) -> Self:
+    def brotli(self, enable: bool = True) -> Self:
         """Enable auto brotli decompression. Default is true."""
 
-    def zstd(self, enable: bool) -> Self:
+    def zstd(self, enable: bool = True) -> Self:
         """Enable auto zstd decompression. Default is true."""
 
-    def deflate(self, enable: bool) -> Self:
+    def deflate(self, enable: bool = True) -> Self:
         """Enable auto deflate decompression. Default is true."""
 
     def max_redirects(self, max_redirects: int) -> Self:
-        """Set maximum number of followed redirects. Default will follow redirects up to a maximum of 10."""
+        """Set maximum number of followed redirects. Default will follow redirects up to a maximum of 10.
+        Exceeding the maximum raises RedirectError, use `follow_redirects(False)` to get the redirect response instead.
+        """
+
+    def follow_redirects(self, enable: bool = True) -> Self:
+        """Enable following redirects. When disabled, a redirect is returned as a normal response with the Location
+        header. Enabling sets the default policy of `max_redirects`. Default is true.
+        """
+
+    def redirect_policy_fn(self, callback: Callable[[Url, Url, int], Literal["follow", "stop", "error"]]) -> Self:
+        """Decide per redirect whether to follow it. The callback receives the next URL, the previous URL and the
+        redirect status code. "stop" returns the redirect response and "error" raises RedirectError.
+        An exception raised in the callback (or an invalid return value) also turns into RedirectError.
+        Replaces `max_redirects` and `follow_redirects` so the callback is responsible for stopping redirect loops.
+        """
 
-    def referer(self, enable: bool) -> Self:
+    def referer(self, enable: bool = True) -> Self:
         """Enable or disable automatic setting of the Referer header. Default is true."""
 
     def proxy(self, proxy: ProxyBuilder) -> Self:
-        """Add a proxy based on ProxyBuilder to the list of proxies the Client will use."""
+        """Add a proxy based on ProxyBuilder to the list of proxies the Client will use.
+
+        The connection pool is shared by all proxies. Pooled connections are keyed by the target scheme and host,
+        and the proxy is selected by the target URL, so connections are not reused across different proxies.
+        """
 
     def no_proxy(self) -> Self:
         """Clear all Proxies, so Client will use no proxy anymore."""
@@ -182,16 +280,16 @@ class BaseClientBuilder:
         This differs from reqwest which uses lowercase by default.
         """
 
-    def http1_allow_obsolete_multiline_headers_in_responses(self, value: bool) -> Self:
+    def http1_allow_obsolete_multiline_headers_in_responses(self, enable: bool = True) -> Self:
         """Set whether HTTP/1 connections will accept obsolete line folding for header values.
 
         Newline codepoints will be transformed to spaces when parsing.
         """
 
-    def http1_ignore_invalid_headers_in_responses(self, value: bool) -> Self:
+    def http1_ignore_invalid_headers_in_responses(self, enable: bool = True) -> Self:
         """Sets whether invalid header lines should be silently ignored in HTTP/1 responses."""
 
-    def http1_allow_spaces_after_header_name_in_responses(self, value: bool) -> Self:
+    def http1_allow_spaces_after_header_name_in_responses(self, enable: bool = True) -> Self:
         """Set whether HTTP/1 accepts spaces between header names and the colon that follow them in responses.
 
         Newline codepoints will be transformed to spaces when parsing.
@@ -206,14 +304,40 @@ class BaseClientBuilder:
     def http2_prior_knowledge(self) -> Self:
         """Only use HTTP/2."""
 
+    def http3_prior_knowledge(self) -> Self:
+        """Only use HTTP/3. HTTP/3 options require pyreqwest built with the `http3` feature (and
+        `RUSTFLAGS='--cfg reqwest_unstable'`), otherwise `build()` raises ValueError.
+        """
+
+    def http3_max_idle_timeout(self, timeout: timedelta | None) -> Self:
+        """Maximum duration of inactivity to accept before timing out the QUIC connection. Requires HTTP/3 support."""
+
+    def http3_congestion_bbr(self, enable: bool = True) -> Self:
+        """Use the BBR congestion control algorithm for QUIC connections. Requires HTTP/3 support."""
+
+    def prefer_versions(self, versions: Sequence[str]) -> Self:
+        """Set HTTP version preference order. `["HTTP/2", "HTTP/1.1"]` (the default) negotiates HTTP/2 via ALPN on
+        TLS connections and falls back to HTTP/1.1. `["HTTP/1.1"]` is the same as `http1_only` and `["HTTP/2"]` as
+        `http2_prior_knowledge`. Versions are parsed like in `RequestBuilder.version`. Preferring HTTP/1.1 over HTTP/2
+        is not supported, and the default order can not be restored after `http1_only` or `http2_prior_knowledge`.
+        """
+
     def http2_initial_stream_window_size(self, value: int | None) -> Self:
         """Sets the SETTINGS_INITIAL_WINDOW_SIZE option for HTTP2 stream-level flow control. Default is 65K."""
 
     def http2_initial_connection_window_size(self, value: int | None) -> Self:
         """Sets the max connection-level flow control for HTTP2. Default is currently 65K."""
 
-    def http2_adaptive_window(self, enabled: bool) -> Self:
-        """Sets whether to use an adaptive flow control."""
+    def http2_windows(self, *, stream: int | None = None, connection: int | None = None) -> Self:
+        """Sets both `http2_initial_stream_window_size` and `http2_initial_connection_window_size`.
+        None uses the default size.
+        """
+
+    def http2_adaptive_window(self, enable: bool = True) -> Self:
+        """Sets whether to use an adaptive flow control. When enabled, it takes precedence over the fixed window sizes
+        regardless of the call order: windows start from the HTTP/2 spec default of 65535 and grow based on the
+        measured bandwidth.
+        """
 
     def http2_max_frame_size(self, value: int | None) -> Self:
         """Sets the maximum frame size to use for HTTP2. Default is currently 16K."""
@@ -227,26 +351,49 @@ class BaseClientBuilder:
     def http2_keep_alive_timeout(self, timeout: timedelta) -> Self:
         """Sets a timeout for receiving an acknowledgement of the keep-alive ping. Default is disabled."""
 
-    def http2_keep_alive_while_idle(self, enabled: bool) -> Self:
+    def http2_keep_alive_while_idle(self, enable: bool = True) -> Self:
         """Sets whether HTTP2 keep-alive should apply while the connection is idle. Default is false."""
 
-    def tcp_nodelay(self, enabled: bool) -> Self:
+    def http2_keep_alive(
+        self, interval: timedelta | None, timeout: timedelta | None = None, while_idle: bool = False
+    ) -> Self:
+        """Combines `http2_keep_alive_interval`, `http2_keep_alive_timeout` and `http2_keep_alive_while_idle`.
+        Timeout is left unchanged when None.
+        """
+
+    def tcp_nodelay(self, enable: bool = True) -> Self:
         """Set TCP_NODELAY (disable Nagle). Default is true."""
 
     def local_address(self, addr: str | None) -> Self:
-        """Bind to a local IP Address."""
+        """Bind to a local IP Address. IPv6 addresses may include a zone as an interface name or index (e.g.
+        `fe80::1%eth0` or `fe80::1%2`), connections are then bound to that interface. Zones are supported on Linux and
+        macOS.
+        """
 
     def interface(self, value: str) -> Self:
-        """Bind connections only on the specified network interface."""
+        """Bind connections only on the specified network interface. Supported on Linux and macOS, on other platforms
+        use `local_address` with the interface IP address instead.
+        """
 
-    def tcp_keepalive(self, duration: timedelta | None) -> Self:
-        """Set SO_KEEPALIVE duration (overall TCP keepalive time)."""
+    def unix_socket(self, path: str | PathLike[str]) -> Self:
+        """Connect to the Unix domain socket at path for all requests (e.g. `http://localhost/v1.43/info` to a
+        local daemon). URL authority is not used for connecting but is kept in the Host header. Proxies are not used.
+        Supported on Unix platforms (Linux, macOS).
+        """
+
+    def tcp_keepalive(
+        self, duration: timedelta | None, interval: timedelta | None = None, retries: int | None = None
+    ) -> Self:
+        """Set SO_KEEPALIVE duration (overall TCP keepalive time). Optionally also sets the interval and retries
+        (see `tcp_keepalive_interval` and `tcp_keepalive_retries`), which are left unchanged when None.
+        Defaults are 15 seconds duration, 15 seconds interval and 3 retries. None duration disables keepalive.
+        """
 
     def tcp_keepalive_interval(self, interval: timedelta | None) -> Self:
-        """Set SO_KEEPALIVE interval (TCP keepalive probe interval)."""
+        """Set SO_KEEPALIVE interval (TCP keepalive probe interval). Default is 15 seconds."""
 
     def tcp_keepalive_retries(self, count: int | None) -> Self:
-        """Set SO_KEEPALIVE retry count (number of failed keepalive probes before drop)."""
+        """Set SO_KEEPALIVE retry count (number of failed keepalive probes before drop). Default is 3."""
 
     def tcp_user_timeout(self, timeout: timedelta | None) -> Self:
         """Set TCP_USER_TIMEOUT (how long data may remain unacknowledged before the connection is force-closed)."""
@@ -260,33 +407,65 @@ class BaseClientBuilder:
     def add_crl_pem(self, cert: bytes) -> Self:
         """Add a certificate revocation list from PEM data."""
 
-    def tls_built_in_root_certs(self, enable: bool) -> Self:
+    def tls_built_in_root_certs(self, enable: bool = True) -> Self:
         """Toggle built-in root cert usage. Defaults to true - built-in system certs will be used."""
 
     def identity_pem(self, buf: bytes) -> Self:
         """Sets the identity to be used for client certificate authentication."""
 
-    def danger_accept_invalid_hostnames(self, enable: bool) -> Self:
+    def tls_client_auth_from_files(self, cert_path: str | PathLike[str], key_path: str | PathLike[str]) -> Self:
+        """Sets the identity to be used for client certificate authentication from separate PEM files.
+        Raises FileNotFoundError if a file does not exist.
+        """
+
+    def danger_accept_invalid_hostnames(self, enable: bool = True) -> Self:
         """Disable hostname verification (INSECURE). Defaults to false."""
 
-    def danger_accept_invalid_certs(self, enable: bool) -> Self:
+    def danger_accept_invalid_certs(self, enable: bool = True) -> Self:
         """Disable certificate validation (INSECURE). Defaults to false."""
 
-    def tls_sni(self, enable: bool) -> Self:
+    def danger_accept_invalid_certs_for(self, hosts: Sequence[str]) -> Self:
+        """Disable certificate validation for the given hostnames or IP addresses (DANGEROUS).
+        Any certificate presented by the listed hosts is trusted, including expired, self-signed and forged ones,
+        so the connections are open to man-in-the-middle attacks. Use only for testing. Other hosts are verified
+        normally. Can not be combined with `danger_accept_invalid_hostnames`.
+        """
+
+    def tls_sni(self, enable: bool = True) -> Self:
         """Enable / disable TLS server name indication. Defaults to true."""
 
+    def tls_info(self, enable: bool = True) -> Self:
+        """Keep TLS information of the connection in responses, available via `Response.peer_certificate()`.
+        Defaults to false.
+        """
+
+    def ocsp_stapling(self, require: bool = True) -> Self:
+        """Require a valid stapled OCSP response in the TLS handshake. Not supported by the rustls TLS backend,
+        `require=True` raises ValueError. Use `add_crl_pem` for certificate revocation checks instead.
+        """
+
     def min_tls_version(self, value: TlsVersion) -> Self:
         """Set minimum accepted TLS version."""
 
     def max_tls_version(self, value: TlsVersion) -> Self:
         """Set maximum accepted TLS version."""
 
-    def https_only(self, enable: bool) -> Self:
+    def https_only(self, enable: bool = True) -> Self:
         """Refuse plain HTTP (HTTPS required). Defaults to false."""
 
     def resolve(self, domain: str, ip: str, port: int) -> Self:
         """Add static DNS resolution mapping (domain -> ip:port)."""
 
+    def dns_cache_ttl(self, ttl: timedelta) -> Self:
+        """Cache resolved DNS addresses for the given duration. Zero disables caching (default)."""
+
+    def resolver_from_system(self, enable: bool = True) -> Self:
+        """Resolve DNS with nameservers, search domains and options read from the system resolv.conf.
+
+        Useful in containers where the default resolver does not honor the custom configuration.
+        Building the client fails if the system configuration cannot be read.
+        """
+
 class ClientBuilder(BaseClientBuilder):
     """Fluent builder for configuring an async `Client`.
 
@@ -332,7 +511,10 @@ class SyncClientBuilder(BaseClientBuilder):
         """Override JSON loads / dumps callables for this sync client."""
 
 class Runtime:
-    """Tokio runtime instance. Usually not needed, as library global runtime is used by default."""
+    """Tokio runtime instance. Usually not needed, as library global runtime is used by default.
+
+    Async clients are driven by the asyncio event loop, use `asyncio.run` to call them from sync code.
+    """
 
     def __init__(self) -> None:
         """Create a tokio runtime instance. This is an advanced feature."""
@@ -1,9 +1,10 @@
 """Types for pyreqwest client module."""
 
 from collections.abc import Awaitable, Callable
-from typing import Any, Literal, Protocol
+from datetime import timedelta
+from typing import Any, Literal, Protocol, TypedDict
 
-from pyreqwest.http import HeaderMap
+from pyreqwest.http import HeaderMap, Url
 from pyreqwest.response import ResponseBodyReader, SyncResponseBodyReader
 
 
@@ -47,8 +48,23 @@ class SyncJsonLoadsContext(Protocol):
         """The extensions associated with the request."""
 
 
+class RecordEntry(TypedDict):
+    """Request and response metadata passed to the `record` sink. Sensitive header values are hidden."""
+
+    method: str
+    url: Url
+    request_headers: dict[str, str | list[str]]
+    tag: str | None
+    elapsed: timedelta
+    status: int | None
+    response_headers: dict[str, str | list[str]] | None
+    error: Exception | None
+
+
 JsonDumps = Callable[[JsonDumpsContext], bytes | bytearray | memoryview]
 JsonLoads = Callable[[JsonLoadsContext], Awaitable[Any]]
 SyncJsonLoads = Callable[[SyncJsonLoadsContext], Any]
 
+RecordSink = Callable[[RecordEntry], None]
+
 TlsVersion = Literal["TLSv1.0", "TLSv1.1", "TLSv1.2", "TLSv1.3"]
@@ -161,12 +161,25 @@ class CookieStore:
         having HttpOnly and Secure attributes compatible with the request_url.
         """
 
+    def get_request_cookies(self, url: Url | str) -> list[Cookie]:
+        """Returns the cookies the store would send for the URL, in the `cookie_header` order. Cookies only have the
+        name and value as attributes are not sent in requests.
+        """
+
+    def cookie_header(self, url: Url | str) -> str | None:
+        """Returns the Cookie header value the store would send for the URL. None if no cookies match."""
+
     def insert(self, cookie: Cookie | str, request_url: Url | str) -> None:
         """Insert a cookie as if set by a response for request_url."""
 
     def clear(self) -> None:
         """Remove all cookies from the store."""
 
+    def merge(self, other: "CookieStore") -> None:
+        """Insert all unexpired cookies of the other store into this store. Cookies of the other store replace the
+        ones with the same domain, path and name.
+        """
+
     def get_all_unexpired(self) -> list[Cookie]:
         """Return all unexpired cookies currently stored."""
 
@@ -1,7 +1,10 @@
 """Exception classes."""
 
 from json import JSONDecodeError as JSONDecodeError_
-from typing import Any, Generic, TypedDict, TypeVar
+from typing import TYPE_CHECKING, Any, ClassVar, Generic, NotRequired, TypedDict, TypeVar
+
+if TYPE_CHECKING:
+    from pyreqwest.bytes import Bytes
 
 
 class Cause(TypedDict):
@@ -11,9 +14,16 @@ class Cause(TypedDict):
 
 
 class CauseErrorDetails(TypedDict):
-    """Details for errors that may have causes."""
+    """Details for errors that may have causes.
+
+    Errors raised while sending the request or reading the response also include the request `method` and `url`.
+    HTTP/2 errors caused by a reset stream (RST_STREAM) or a closed connection (GOAWAY) include the `h2_error_code`.
+    """
 
     causes: list[Cause] | None
+    method: NotRequired[str]
+    url: NotRequired[str]
+    h2_error_code: NotRequired[int]
 
 
 class StatusErrorDetails(TypedDict):
@@ -22,18 +32,38 @@ class StatusErrorDetails(TypedDict):
     status: int
 
 
+class ApiErrorDetails(StatusErrorDetails):
+    """Details for errors found in the response JSON body."""
+
+    error: Any
+
+
 T = TypeVar("T", bound=CauseErrorDetails | StatusErrorDetails)
 
 
 class PyreqwestError(Exception):
     """Base class for all pyreqwest errors."""
 
+    _retryable: ClassVar[bool] = False
+
     def __init__(self, message: str, *args: Any) -> None:
         """Internally initialized."""
         assert isinstance(message, str)
         Exception.__init__(self, message, *args)
         self.message = message
 
+    def __reduce__(self) -> tuple[Any, ...]:
+        """Support pickling. Attributes like `details` and `partial_body` are restored from the instance dict."""
+        return type(self), self.args, self.__dict__
+
+    @property
+    def is_retryable(self) -> bool:
+        """Whether retrying the request may succeed. True for transport (connection and timeout) errors.
+
+        False for status, decode and builder errors, which would fail the same way again.
+        """
+        return self._retryable
+
 
 class DetailedPyreqwestError(PyreqwestError, Generic[T]):
     """Base class for all pyreqwest errors with details.
@@ -52,8 +82,11 @@ class RequestError(DetailedPyreqwestError[T], Generic[T]):
     """Error while processing a request.
 
     Details may be available in `details`.
+    When reading the response body fails, the bytes received before the failure are available in `partial_body`.
     """
 
+    partial_body: "Bytes | None" = None
+
 
 class StatusError(RequestError[StatusErrorDetails]):
     """Error due to HTTP 4xx or 5xx status code. Raised when `error_for_status` is enabled.
@@ -62,6 +95,13 @@ class StatusError(RequestError[StatusErrorDetails]):
     """
 
 
+class ApiError(RequestError[ApiErrorDetails]):
+    """Error found in the response JSON body. Raised when `error_for_json` predicate returns a truthy value.
+
+    The status code is available in `details["status"]` and the predicate result in `details["error"]`.
+    """
+
+
 class RedirectError(RequestError[CauseErrorDetails]):
     """Error due to too many redirects. Raised when `max_redirects` is exceeded.
 
@@ -97,6 +137,11 @@ class JSONDecodeError(BodyDecodeError, JSONDecodeError_):
         JSONDecodeError_.__init__(self, message, details["doc"], details["pos"])
         BodyDecodeError.__init__(self, message, {"causes": details["causes"]})
 
+    def __reduce__(self) -> tuple[Any, ...]:
+        """Support pickling."""
+        details = {"doc": self.doc, "pos": self.pos, "causes": self.details["causes"]}
+        return type(self), (self.message, details), self.__dict__
+
 
 class TransportError(RequestError[CauseErrorDetails]):
     """Error while processing the transport layer.
@@ -104,6 +149,8 @@ class TransportError(RequestError[CauseErrorDetails]):
     Cause details may be available in `details["causes"]`.
     """
 
+    _retryable = True
+
 
 class RequestTimeoutError(TransportError, TimeoutError):
     """Error due to a timeout.
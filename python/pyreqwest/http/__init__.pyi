@@ -1,6 +1,7 @@
-from collections.abc import ItemsView, Iterator, KeysView, MutableMapping, Sequence, ValuesView
+from collections.abc import Callable, ItemsView, Iterator, KeysView, MutableMapping, Sequence, ValuesView
 from typing import Any, Self, TypeVar, overload
 
+from pyreqwest.bytes import Bytes
 from pyreqwest.types import HeadersType, QueryParams
 
 _T = TypeVar("_T")
@@ -152,12 +153,31 @@ class Url:
     def fragment(self) -> str | None:
         """Return this URL's fragment identifier, if any. A fragment is the part of the URL after the # symbol."""
 
+    @property
+    def is_data_uri(self) -> bool:
+        """Return True if this is a `data:` URL."""
+
+    def data_uri_decode(self) -> tuple["Mime", Bytes]:
+        """Decode a `data:` URL into its media type and payload. Supports base64 and percent-encoded payloads.
+        Media type defaults to text/plain. Raises ValueError if this is not a valid data URL.
+        """
+
     def with_query(self, query: QueryParams | None) -> Self:
         """Replace the entire query with provided params (None removes query)."""
 
     def extend_query(self, query: QueryParams) -> Self:
         """Append additional key/value pairs to existing query keeping original order."""
 
+    def with_query_merged(self, query: QueryParams) -> Self:
+        """Merge pairs over existing query by key. All existing values of a given key are replaced by all the given
+        values of that key, placed at the key's first existing position. New keys are appended."""
+
+    def with_query_removed(self, key: str) -> Self:
+        """Remove all pairs of the key from the query, keeping the order of other pairs."""
+
+    def with_query_set(self, key: str, value: str) -> Self:
+        """Remove all pairs of the key from the query and append the key with the value."""
+
     def with_query_string(self, query: str | None) -> Self:
         """Replace query using a preformatted string (no leading '?'). None removes it."""
 
@@ -169,6 +189,18 @@ class Url:
         Each segment is percent-encoded, except that % and / characters are also encoded (to %25 and %2F).
         """
 
+    def insert_path_segment(self, index: int, segment: str) -> Self:
+        """Insert a path segment at the given index (clamped to the number of segments).
+        The segment is percent-encoded like in `with_path_segments`. Existing segments are kept as-is.
+        A trailing slash of a directory-style path stays last (`/a/b/` -> `/a/b/new/`).
+        Raises ValueError for cannot-be-a-base URLs.
+        """
+
+    def pop_path_segment(self) -> Self:
+        """Remove the last path segment. A trailing empty segment (directory-style path) is removed first.
+        Raises ValueError for cannot-be-a-base URLs.
+        """
+
     def with_port(self, port: int | None) -> Self:
         """Change this URL's port number. None removes explicit port."""
 
@@ -189,9 +221,22 @@ class Url:
     def with_scheme(self, scheme: str) -> Self:
         """Change this URL's scheme."""
 
+    def upgrade_to_https(self) -> Self:
+        """Switch http URL to https. Default port is adjusted (http://h:80/ becomes https://h/), other ports are kept.
+        https URL is returned as is. Raises ValueError for other schemes.
+        """
+
+    def downgrade_to_http(self) -> Self:
+        """Switch https URL to http. Default port is adjusted (https://h:443/ becomes http://h/), other ports are kept.
+        http URL is returned as is. Raises ValueError for other schemes.
+        """
+
     def with_fragment(self, fragment: str | None) -> Self:
         """Change this URL's fragment identifier."""
 
+    def to_json(self) -> str:
+        """JSON representation, the URL string. Url values (and dict keys) are serialized like this in JSON bodies."""
+
     def __truediv__(self, join_input: str) -> Self:
         """Path join shorthand: url / 'segment' == url.join('segment')."""
 
@@ -240,6 +285,11 @@ class Mime:
     def get_param(self, name: str) -> str | None:
         """Return first parameter value whose name (case-insensitive) matches, else None."""
 
+    def matches(self, pattern: "Mime | str") -> bool:
+        """Whether the essence matches the pattern (e.g. 'application/json' or 'text/*'), ignoring parameters.
+        Wildcard '*' matches any type or subtype.
+        """
+
     def __contains__(self, item: Any) -> bool: ...
     def __copy__(self) -> Self: ...
     def __hash__(self) -> int: ...
@@ -305,6 +355,14 @@ class HeaderMap(MutableMapping[str, str]):
     def getall(self, key: str) -> list[str]:
         """All values for key. Empty if key does not exist."""
 
+    def get_all_str(self, key: str) -> list[str]:
+        """All values for key decoded as UTF-8, so non-ASCII values are also allowed. Empty if key does not exist.
+        Raises ValueError if a value is not valid UTF-8.
+        """
+
+    def get_str(self, key: str, default: str | None = None) -> str | None:
+        """First value for key decoded as UTF-8 (see `get_all_str`), or default if key does not exist."""
+
     def insert(self, key: str, value: str, *, is_sensitive: bool = False) -> list[str]:
         """Replace all existing values for key with value. Returns list of removed values (may be empty)."""
 
@@ -313,18 +371,42 @@ class HeaderMap(MutableMapping[str, str]):
         values currently associated with the key. Return True if key already existed, else False.
         """
 
+    def setdefault_append(self, key: str, value: str, *, is_sensitive: bool = False) -> list[str]:
+        """Append value only if the key is absent. Returns all values for the key: the existing ones if the key was
+        present (map is not modified), else the appended value.
+        """
+
+    def replace_all(self, key: str, values: Sequence[str]) -> list[str]:
+        """Replace all existing values for key with the given values, keeping their order.
+        Returns list of removed values (may be empty). Empty values removes the key.
+        """
+
     def extend(self, other: HeadersType) -> None:
         """Append pairs from mapping / iterable."""
 
+    def update_if_absent(self, other: HeadersType) -> None:
+        """Append pairs from mapping / iterable, skipping keys that were already present."""
+
     @overload
     def popall(self, key: str) -> list[str]:
-        """Remove all values for key and return list. With default returns it instead of raising KeyError."""
+        """Remove all values for key (case-insensitive) and return list. With default returns it instead of KeyError."""
     @overload
     def popall(self, key: str, /, default: _T) -> list[str] | _T:
-        """Remove all values for key and return list. With default returns it instead of raising KeyError."""
+        """Remove all values for key (case-insensitive) and return list. With default returns it instead of KeyError."""
+
+    def dict_multi_value(self, *, hide_sensitive: bool = False) -> dict[str, str | list[str]]:
+        """Dict: single-value headers -> str; multi-value -> list[str].
+        With hide_sensitive, values marked as sensitive are shown as "Sensitive".
+        """
 
-    def dict_multi_value(self) -> dict[str, str | list[str]]:
-        """Dict: single-value headers -> str; multi-value -> list[str]."""
+    def is_sensitive(self, key: str) -> bool:
+        """Return True if any value for the key is marked as sensitive. False if key is absent."""
+
+    def sorted_items(self) -> list[tuple[str, str]]:
+        """Items sorted by lowercase name then value. Duplicates are kept. Useful for canonicalization."""
+
+    def filter(self, predicate: Callable[[str, str], bool]) -> Self:
+        """New map with the entries for which `predicate(name, value)` is true. Duplicates are kept."""
 
     def copy(self) -> Self:
         """Copy the map."""
@@ -332,13 +414,16 @@ class HeaderMap(MutableMapping[str, str]):
     def __copy__(self) -> Self: ...
     @overload
     def pop(self, key: str) -> str:
-        """Remove & return FIRST value for key. With default returns default instead of KeyError."""
+        """Remove & return FIRST value for key (case-insensitive). With default returns default instead of KeyError."""
     @overload
     def pop(self, key: str, default: _T = ...) -> str | _T:
-        """Remove & return FIRST value for key. With default returns default instead of KeyError."""
+        """Remove & return FIRST value for key (case-insensitive). With default returns default instead of KeyError."""
 
     def popitem(self) -> tuple[str, str]:
-        """Remove & return an arbitrary (key, FIRST value) respecting value ordering; KeyError if empty."""
+        """Remove & return (key, FIRST value) of the first key in iteration order; KeyError if empty.
+
+        Values of a key are popped in their order. Order between keys may change after removals.
+        """
 
     def clear(self) -> None:
         """Empty the map."""
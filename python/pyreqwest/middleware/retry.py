@@ -0,0 +1,142 @@
+"""Retry middleware."""
+
+import asyncio
+import time
+from dataclasses import dataclass
+from datetime import timedelta
+
+from pyreqwest.exceptions import PyreqwestError, StatusError
+from pyreqwest.middleware import Next, SyncNext
+from pyreqwest.request import Request
+from pyreqwest.response import Response, SyncResponse
+
+IDEMPOTENT_METHODS = frozenset({"GET", "HEAD", "OPTIONS", "PUT", "DELETE", "TRACE"})
+
+
+@dataclass(frozen=True)
+class RetryPolicy:
+    """Retry policy for `ClientBuilder.with_retries_middleware`.
+
+    Requests failing with a retryable error (see `PyreqwestError.is_retryable`) or responding with a status in
+    `retry_statuses` are retried until `max_attempts` is reached. The delay before retry N is
+    `backoff * backoff_multiplier ** (N - 1)`. Requests with a body that can not be copied (e.g. a generator stream)
+    are not retried.
+    """
+
+    max_attempts: int = 3
+    """Total number of attempts, including the first one."""
+
+    backoff: timedelta = timedelta(milliseconds=100)
+    """Delay before the first retry."""
+
+    backoff_multiplier: float = 2.0
+    """Multiplier applied to the delay after each retry."""
+
+    retry_statuses: frozenset[int] = frozenset({429, 502, 503, 504})
+    """Response statuses that are retried."""
+
+    idempotent_only: bool = True
+    """Retry only idempotent methods (GET, HEAD, OPTIONS, PUT, DELETE, TRACE)."""
+
+    def __post_init__(self) -> None:
+        """Validate the policy."""
+        if self.max_attempts < 1:
+            msg = "max_attempts must be at least 1"
+            raise ValueError(msg)
+        if self.backoff < timedelta(0) or self.backoff_multiplier < 0:
+            msg = "backoff and backoff_multiplier must be non-negative"
+            raise ValueError(msg)
+        object.__setattr__(self, "retry_statuses", frozenset(self.retry_statuses))
+
+    def allows_method(self, method: str) -> bool:
+        """Whether requests with the method can be retried."""
+        return not self.idempotent_only or method.upper() in IDEMPOTENT_METHODS
+
+    def delay(self, retry: int) -> float:
+        """Delay in seconds before the given retry (1 for the first retry)."""
+        return float(self.backoff.total_seconds() * self.backoff_multiplier ** (retry - 1))
+
+
+class RetryMiddleware:
+    """Middleware retrying requests according to `RetryPolicy`. Added via `ClientBuilder.with_retries_middleware`."""
+
+    def __init__(self, policy: RetryPolicy, *, require_body_copy: bool = False) -> None:
+        """Create the middleware. With `require_body_copy`, a request body that can not be copied raises TypeError
+        instead of the request being sent without retries.
+        """
+        self.policy = policy
+        self.require_body_copy = require_body_copy
+
+    async def __call__(self, request: Request, next_handler: Next) -> Response:
+        """Send the request, retrying failed attempts."""
+        if not self.policy.allows_method(request.method):
+            return await next_handler.run(request)
+
+        for retry in range(1, self.policy.max_attempts):
+            attempt = _try_copy(request, self.require_body_copy)
+            if attempt is None:
+                break  # Body can not be replayed
+            try:
+                response = await next_handler.run(attempt)
+            except PyreqwestError as e:
+                if not _should_retry_error(self.policy, e):
+                    raise
+            else:
+                if response.status not in self.policy.retry_statuses:
+                    return response
+                await response.aclose()  # Release the connection before the next attempt
+            await asyncio.sleep(self.policy.delay(retry))
+
+        return await next_handler.run(request)
+
+
+class SyncRetryMiddleware:
+    """Sync middleware retrying requests according to `RetryPolicy`.
+
+    Added via `SyncClientBuilder.with_retries_middleware`.
+    """
+
+    def __init__(self, policy: RetryPolicy, *, require_body_copy: bool = False) -> None:
+        """Create the middleware. With `require_body_copy`, a request body that can not be copied raises TypeError
+        instead of the request being sent without retries.
+        """
+        self.policy = policy
+        self.require_body_copy = require_body_copy
+
+    def __call__(self, request: Request, next_handler: SyncNext) -> SyncResponse:
+        """Send the request, retrying failed attempts."""
+        if not self.policy.allows_method(request.method):
+            return next_handler.run(request)
+
+        for retry in range(1, self.policy.max_attempts):
+            attempt = _try_copy(request, self.require_body_copy)
+            if attempt is None:
+                break  # Body can not be replayed
+            try:
+                response = next_handler.run(attempt)
+            except PyreqwestError as e:
+                if not _should_retry_error(self.policy, e):
+                    raise
+            else:
+                if response.status not in self.policy.retry_statuses:
+                    return response
+                response.close()  # Release the connection before the next attempt
+            time.sleep(self.policy.delay(retry))
+
+        return next_handler.run(request)
+
+
+def _try_copy(request: Request, require_body_copy: bool) -> Request | None:
+    try:
+        return request.copy()
+    except TypeError as e:  # Stream body does not implement __copy__
+        if require_body_copy:
+            msg = "Request body can not be copied for retrying, stream body must implement __copy__"
+            raise TypeError(msg) from e
+        return None
+
+
+def _should_retry_error(policy: RetryPolicy, error: PyreqwestError) -> bool:
+    if isinstance(error, StatusError):
+        return error.details["status"] in policy.retry_statuses
+    return error.is_retryable
@@ -34,6 +34,11 @@ class FormBuilder:
     def percent_encode_noop(self) -> Self:
         """Configure this Form to skip percent-encoding."""
 
+    def require_known_length(self, enable: bool = True) -> Self:
+        """Fail building the request if any part has unknown length (e.g. `PartBuilder.from_stream`).
+        Use when the server requires a Content-Length. Default is false.
+        """
+
 class PartBuilder:
     """Build an individual multipart part. Create with from_* then optionally set mime, filename, headers.
     Add via FormBuilder.part().
@@ -58,12 +63,12 @@ class PartBuilder:
         """
 
     @staticmethod
-    async def from_file(path: Path) -> "PartBuilder":
-        """Makes a file parameter."""
+    async def from_file(path: Path, *, file_name: str | None = None, mime: Mime | str | None = None) -> "PartBuilder":
+        """Makes a file parameter. Filename and mime are derived from the path unless given."""
 
     @staticmethod
-    def from_sync_file(path: Path) -> "PartBuilder":
-        """Makes a file parameter. File read is blocking."""
+    def from_sync_file(path: Path, *, file_name: str | None = None, mime: Mime | str | None = None) -> "PartBuilder":
+        """Makes a file parameter. Filename and mime are derived from the path unless given. File read is blocking."""
 
     def mime(self, mime: Mime | str) -> Self:
         """Set the mime of this part."""
@@ -8,6 +8,8 @@ class ProxyBuilder:
     """Configuration of a proxy that a Client should pass requests to.
 
     The resulting instance is passed to `ClientBuilder.proxy(...)`.
+    Proxy URL scheme can be http, https, socks4, socks4a, socks5 or socks5h (e.g. `socks5h://127.0.0.1:1080`).
+    With socks5h and socks4a the proxy resolves the target host names.
     Based on reqwest's `Proxy` type.
     See also Rust [docs](https://docs.rs/reqwest/latest/reqwest/struct.Proxy.html) for more details.
     """
@@ -31,6 +33,8 @@ class ProxyBuilder:
     def custom(fun: Callable[[Url], Url | str | None]) -> "ProxyBuilder":
         """Provide a custom function to determine what traffic to proxy to where.
 
+        The function receives the destination URL without path and query. Connections are pooled per destination
+        (not per proxy), so the function should always return the same proxy for the same destination.
         Any exception raised or an invalid/relative return value surfaces as a `RequestPanicError`.
         """
 
@@ -6,9 +6,11 @@ from pyreqwest._pyreqwest.request import (
     Request,
     RequestBody,
     RequestBuilder,
+    RequestTemplate,
     StreamRequest,
     SyncConsumedRequest,
     SyncRequestBuilder,
+    SyncRequestTemplate,
     SyncStreamRequest,
 )
 
@@ -22,4 +24,6 @@ __all__ = [  # noqa: RUF022
     "SyncRequestBuilder",
     "BaseRequestBuilder",
     "RequestBody",
+    "RequestTemplate",
+    "SyncRequestTemplate",
 ]
@@ -1,5 +1,6 @@
+from collections.abc import AsyncIterable, Callable, Iterable, Sequence
 from datetime import timedelta
-from typing import Any, Self
+from typing import Any, Literal, Self
 
 from pyreqwest.bytes import Bytes
 from pyreqwest.http import HeaderMap, Url
@@ -50,7 +51,9 @@ class Request:
         """Replace extensions. Given value is shallow copied."""
 
     def copy(self) -> Self:
-        """Copy the request. Byte-bodies are zero-copied. Stream bodies are re-created via their own copy logic."""
+        """Copy the request. Byte-bodies are zero-copied. Stream bodies are re-created via their own copy logic,
+        TypeError is raised when the stream does not implement `__copy__`.
+        """
 
     def __copy__(self) -> Self: ...
     def repr_full(self) -> str:
@@ -79,6 +82,10 @@ class StreamRequest(Request):
     def read_buffer_limit(self) -> int:
         """Max bytes buffered when reading streamed body."""
 
+    @property
+    def prefetch_chunks(self) -> int:
+        """Max buffered chunk batches read ahead from the connection when streaming body."""
+
 class SyncConsumedRequest(Request):
     """Synchronous request that will fully read the response body when sent."""
 
@@ -98,6 +105,22 @@ class SyncStreamRequest(Request):
     def read_buffer_limit(self) -> int:
         """Max bytes buffered when reading streamed body."""
 
+    @property
+    def prefetch_chunks(self) -> int:
+        """Max buffered chunk batches read ahead from the connection when streaming body."""
+
+class RequestTemplate:
+    """Frozen request that can be cheaply cloned into fresh sendable requests. Created via `RequestBuilder.freeze()`."""
+
+    def clone(self) -> ConsumedRequest:
+        """Create a new request from the template."""
+
+class SyncRequestTemplate:
+    """Frozen synchronous request that can be cheaply cloned into fresh sendable requests."""
+
+    def clone(self) -> SyncConsumedRequest:
+        """Create a new request from the template."""
+
 class RequestBody:
     """Represents request body content (bytes, text, or async stream). Bodies are single-use."""
 
@@ -113,8 +136,18 @@ class RequestBody:
     def from_stream(stream: Stream) -> "RequestBody":
         """Create body from async byte stream."""
 
+    @staticmethod
+    def from_json_stream(items: Iterable[Any] | AsyncIterable[Any]) -> "RequestBody":
+        """Create body streaming the items as a JSON array. Items are serialized with the default JSON serializer.
+        Content-Type is not set as the body has no headers, use `RequestBuilder.body_json_stream` for that."""
+
+    @staticmethod
+    def from_chunks(chunks: Sequence[bytes | bytearray | memoryview]) -> "RequestBody":
+        """Create body from in-memory chunks. Chunks are joined without Python iteration overhead.
+        The body is buffered so it is replayed on 307/308 redirects."""
+
     def copy_bytes(self) -> Bytes | None:
-        """Return bytes zero-copy. None for stream."""
+        """Return bytes zero-copy. Chunks from `from_chunks` are joined into a copy. None for stream."""
 
     def get_stream(self) -> Stream | None:
         """Return underlying stream if streaming body else None."""
@@ -123,9 +156,22 @@ class RequestBody:
         """Copy body (Zero-copied bytes. Stream supplies its own copy)."""
 
 class BaseRequestBuilder:
-    def error_for_status(self, enable: bool) -> Self:
+    @property
+    def url(self) -> Url:
+        """Get the pending request URL."""
+
+    @url.setter
+    def url(self, value: Url | str) -> None:
+        """Set the pending request URL."""
+
+    def error_for_status(self, enable: bool = True) -> Self:
         """Enable automatic HTTP error raising (4xx/5xx)."""
 
+    def error_for_json(self, predicate: Callable[[Any], Any] | None) -> Self:
+        """Raise ApiError when predicate returns a truthy value for the decoded JSON response body.
+        The predicate result is available in `details["error"]`. Only supported for fully consumed requests.
+        """
+
     def header(self, name: str, value: str) -> Self:
         """Append single header value."""
 
@@ -138,6 +184,12 @@ class BaseRequestBuilder:
     def bearer_auth(self, token: str) -> Self:
         """Add Bearer token Authorization header."""
 
+    def version(self, value: Literal["HTTP/1.0", "HTTP/1.1", "HTTP/2"]) -> Self:
+        """Set the HTTP version of this request. The connection protocol is still negotiated when connecting
+        (ALPN or `ClientBuilder.http2_prior_knowledge`). "HTTP/1.0" and "HTTP/1.1" apply to HTTP/1 connections and
+        sending fails when "HTTP/2" is required but the connection is HTTP/1.
+        """
+
     def body_bytes(self, body: bytes | bytearray | memoryview) -> Self:
         """Set body from raw bytes."""
 
@@ -147,11 +199,30 @@ class BaseRequestBuilder:
     def body_json(self, body: Any) -> Self:
         """Serialize body as JSON. Sets Content-Type header."""
 
+    def body_json_lines(self, items: Iterable[Any]) -> Self:
+        """Serialize items as newline delimited JSON. Lists and tuples are buffered, other iterables are streamed.
+        Sets Content-Type header to application/x-ndjson."""
+
+    def body_json_stream(self, items: Iterable[Any]) -> Self:
+        """Serialize items as a streamed JSON array without buffering the whole array.
+        Sets Content-Type header to application/json."""
+
+    def add_digest(self, algorithm: Literal["sha-256", "sha-512"]) -> Self:
+        """Set the Digest header (RFC 3230) computed from the current body, e.g. `Digest: sha-256=<base64>`.
+        Call after setting the body. Streamed bodies (including multipart) are not supported."""
+
+    def prefix_path(self, prefix: str) -> Self:
+        """Insert leading path segments to the request URL, e.g. `prefix_path("v2")` turns `/users` into `/v2/users`.
+        Segments are percent-encoded, `/` separates multiple segments."""
+
     def query(self, query: QueryParams) -> Self:
         """Add/merge query parameters."""
 
-    def timeout(self, timeout: timedelta) -> Self:
-        """Set per-request total timeout."""
+    def query_replace(self, query: QueryParams) -> Self:
+        """Replace all existing query parameters (including ones in the URL) with the given ones."""
+
+    def timeout(self, timeout: timedelta | None) -> Self:
+        """Set per-request total timeout. None disables the timeout, including the client default timeout."""
 
     def multipart(self, multipart: FormBuilder) -> Self:
         """Attach multipart form body builder."""
@@ -162,6 +233,20 @@ class BaseRequestBuilder:
     def extensions(self, extensions: ExtensionsType) -> Self:
         """Arbitrary per-request data storage. Useful for passing through data to middleware and response."""
 
+    def tag(self, name: str) -> Self:
+        """Label the request for grouping metrics per logical endpoint, e.g. `tag("get_user")`.
+        Stored as `extensions["tag"]` when the request is built, so it is visible in middlewares and the response, and
+        included in `record` entries and `timings()`. Kept regardless of the order of `extensions(...)` calls.
+        """
+
+    def retry(self, max_retries: int, backoff: timedelta, *, retry_statuses: Sequence[int] = ()) -> Self:
+        """Retry the request up to max_retries times on transport errors (e.g. ConnectError, ReadError,
+        PoolTimeoutError), waiting backoff between attempts. Responses (or StatusErrors) with a status in
+        retry_statuses are also retried. Retrying is done by a request middleware using `RetryPolicy`, added in call
+        order like `with_middleware`. Body must be copyable, e.g. streams must implement `__copy__`, otherwise sending
+        raises TypeError.
+        """
+
     def streamed_read_buffer_limit(self, value: int) -> Self:
         """Max bytes buffered when reading streamed body."""
 
@@ -169,6 +254,15 @@ class BaseRequestBuilder:
     def default_streamed_read_buffer_limit() -> int:
         """Default max bytes buffered when reading streamed body."""
 
+    def prefetch_chunks(self, value: int) -> Self:
+        """Number of read buffers (each up to `streamed_read_buffer_limit` bytes) prefetched ahead of the consumer
+        when reading streamed body. Higher values improve throughput on fast connections at the cost of memory.
+        """
+
+    @staticmethod
+    def default_prefetch_chunks() -> int:
+        """Default number of prefetched read buffers when reading streamed body."""
+
 class RequestBuilder(BaseRequestBuilder):
     """Request builder. Use `build()` or `build_streamed()` to create the request to send."""
 
@@ -178,9 +272,24 @@ class RequestBuilder(BaseRequestBuilder):
     def build_streamed(self) -> StreamRequest:
         """Build request whose response body is streamed."""
 
+    async def send_head_only(self) -> Response:
+        """Send the request and return the response with status and headers only. The body is not read and the
+        connection is released right away (e.g. for health checks). Reading the body raises an error.
+        """
+
+    def freeze(self) -> RequestTemplate:
+        """Build a reusable request template. Each `clone()` yields a fresh request that full reads the response body.
+        Body must be cloneable (bytes or chunks, or a stream supporting copying)."""
+
     def body_stream(self, stream: Stream) -> Self:
         """Set streaming request body."""
 
+    def body_json_lines(self, items: Iterable[Any] | AsyncIterable[Any]) -> Self: ...
+
+    def body_json_stream(self, items: Iterable[Any] | AsyncIterable[Any]) -> Self:
+        """Serialize items as a streamed JSON array without buffering the whole array.
+        Sets Content-Type header to application/json."""
+
     def with_middleware(self, middleware: Middleware) -> Self:
         """Register a middleware component (added after client level middlewares, executed in chain order)."""
 
@@ -193,6 +302,15 @@ class SyncRequestBuilder(BaseRequestBuilder):
     def build_streamed(self) -> SyncStreamRequest:
         """Build request whose response body is streamed."""
 
+    def send_head_only(self) -> SyncResponse:
+        """Send the request and return the response with status and headers only. The body is not read and the
+        connection is released right away (e.g. for health checks). Reading the body raises an error.
+        """
+
+    def freeze(self) -> SyncRequestTemplate:
+        """Build a reusable request template. Each `clone()` yields a fresh request that full reads the response body.
+        Body must be cloneable (bytes or chunks, or a stream supporting copying)."""
+
     def body_stream(self, stream: SyncStream) -> Self:
         """Set streaming request body."""
 
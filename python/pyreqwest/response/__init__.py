@@ -2,9 +2,11 @@
 
 from pyreqwest._pyreqwest.response import (
     BaseResponse,
+    ProxyResponse,
     Response,
     ResponseBodyReader,
     ResponseBuilder,
+    ServerSentEvent,
     SyncResponse,
     SyncResponseBodyReader,
 )
@@ -16,4 +18,6 @@ __all__ = [  # noqa: RUF022
     "ResponseBuilder",
     "ResponseBodyReader",
     "SyncResponseBodyReader",
+    "ServerSentEvent",
+    "ProxyResponse",
 ]
@@ -1,8 +1,11 @@
-from typing import Any, Self
+from collections.abc import AsyncIterator, Callable, Iterator
+from http import HTTPStatus
+from os import PathLike
+from typing import Any, Generic, Literal, Self, TypeVar
 
 from pyreqwest.bytes import Bytes
 from pyreqwest.http import HeaderMap, Mime
-from pyreqwest.types import ExtensionsType, HeadersType, Stream
+from pyreqwest.types import ExtensionsType, HeadersType, Link, RequestTimings, Stream
 
 class BaseResponse:
     @property
@@ -13,6 +16,13 @@ class BaseResponse:
     def status(self, value: int) -> None:
         """Set HTTP status code."""
 
+    @property
+    def status_enum(self) -> HTTPStatus | None:
+        """HTTP status code as `http.HTTPStatus` (e.g. `HTTPStatus.NOT_FOUND`). None for non-standard status codes."""
+
+    def __bool__(self) -> bool:
+        """True if status is 2xx. Note this does not reflect whether the body is empty."""
+
     @property
     def headers(self) -> HeaderMap:
         """Get the headers. This is not a copy. Modifying it modifies the response.
@@ -23,6 +33,9 @@ class BaseResponse:
     def headers(self, headers: HeadersType) -> None:
         """Replace headers. Given value is copied."""
 
+    def copy_headers(self) -> HeaderMap:
+        """Get a copy of the headers. Modifying the copy does not modify the response."""
+
     @property
     def extensions(self) -> dict[str, Any]:
         """Arbitrary per-request data storage. This is the data that was passed via request and middlewares.
@@ -41,9 +54,27 @@ class BaseResponse:
     def version(self, value: str) -> None:
         """Set HTTP version."""
 
+    @property
+    def links(self) -> dict[str, Link]:
+        """Links from all `Link` headers keyed by their `rel`. A link with multiple space separated relations is
+        included under each of them. The first link wins for duplicate relations. Links without `rel` are ignored.
+        """
+
+    def timings(self) -> RequestTimings | None:
+        """Timing breakdown of the request when enabled via `ClientBuilder.collect_timings`, else None.
+        Each phase is the elapsed time from the request start until the phase finished (like curl `-w` timings).
+        """
+
+    def peer_certificate(self) -> bytes | None:
+        """DER encoded certificate of the server when enabled via `ClientBuilder.tls_info`, else None."""
+
     def error_for_status(self) -> None:
         """Raise StatusError for 4xx/5xx."""
 
+    def raise_for_status(self) -> Self:
+        """Raise StatusError for 4xx/5xx. Returns the same response for chaining, e.g. `resp.raise_for_status().json()`.
+        """
+
     def get_header(self, key: str) -> str | None:
         """Return first matching header value else None (case-insensitive)."""
 
@@ -53,19 +84,97 @@ class BaseResponse:
     def content_type_mime(self) -> Mime | None:
         """Parsed Content-Type header as Mime or None if absent."""
 
+    def content_disposition_filename(self) -> str | None:
+        """Suggested filename from Content-Disposition header or None if absent.
+        RFC 5987 `filename*` is preferred over `filename`.
+        """
+
 class Response(BaseResponse):
     """Asynchronous response with optionally streamed body."""
 
     async def bytes(self) -> Bytes:
         """Return entire body as bytes (cached after first read)."""
 
-    async def json(self) -> Any:
+    async def json(
+        self,
+        *,
+        object_hook: Callable[[dict[str, Any]], Any] | None = None,
+        on_duplicate_key: Literal["error", "first", "last"] = "last",
+        exact_numbers: bool = False,
+        allow_nan: bool = False,
+    ) -> Any:
         """Decode body as JSON (underlying bytes cached after first read). Uses serde for decoding.
+        A non UTF-8 charset from Content-Type is decoded like in `text()` before parsing.
         User can provide custom deserializer via `ClientBuilder.json_handler`.
+        `object_hook` is called on each decoded object (innermost first) like in `json.loads`.
+        `on_duplicate_key` controls duplicate object keys: raise JSONDecodeError, keep the first or the last value.
+        `exact_numbers` decodes integers of any size without precision loss and other numbers as `decimal.Decimal`.
+        It is slower as it decodes via the Python `json` module.
+        `allow_nan` accepts the non-standard `NaN`, `Infinity` and `-Infinity` tokens as floats (also via `json`).
+        By default they raise JSONDecodeError. These apply to the built-in deserializer only, passing them with a custom
+        `json_handler` loads raises ValueError. Custom loads reads the raw body, the charset is not decoded for it.
+        """
+
+    async def text(self, *, strip_bom: bool = True, sniff_html_charset: bool = False) -> str:
+        """Decode body to text (underlying bytes cached after first read). Uses charset from Content-Type.
+
+        A leading UTF-8 or UTF-16 byte order mark takes precedence over the charset. It is removed from the text
+        unless strip_bom is False. With sniff_html_charset, an HTML body without a Content-Type charset is decoded
+        using the `<meta charset>` declared in its first 1024 bytes. Otherwise UTF-8 is used.
+        """
+
+    async def decode(self, encoding: str) -> str:
+        """Decode body to text with the given encoding label, ignoring Content-Type charset.
+
+        Underlying bytes are cached after first read, so already read bytes are not fetched again.
         """
 
-    async def text(self) -> str:
-        """Decode body to text (underlying bytes cached after first read). Uses charset from Content-Type."""
+    async def buffer_all(self) -> None:
+        """Read the remaining body into memory. Afterwards bytes(), json() and text() can be called repeatedly.
+        Not allowed after reading body partially via body_reader.
+        """
+
+    async def body_as_mmap(self, dir: str | PathLike[str] | None = None) -> memoryview:
+        """Stream the body into a temporary file and return a read-only memory-mapped view over it. Useful for very
+        large bodies that must be re-read without holding them in RAM. The temporary file is created in `dir` (system
+        default if None), it is not visible in the file system and is released when the view is dropped.
+        Consumes the body (use before other body reading methods).
+        """
+
+    async def write_to(
+        self, path: str | PathLike[str], chunk_callback: Callable[[int], object] | None = None
+    ) -> int:
+        """Stream the body into the file at `path` (created or truncated) without holding it in memory.
+        Returns the number of bytes written. `chunk_callback` is called with the number of bytes received so far
+        after each chunk, e.g. for progress reporting. Consumes the body (use before other body reading methods).
+        """
+
+    async def json_stream_to(self, callback: Callable[[Any], object]) -> int:
+        """Incrementally decode a top level JSON array body, calling `callback` for each element as it arrives.
+        Returns the number of elements. Consumes the body (use before other body reading methods).
+        """
+
+    def stream(self) -> AsyncIterator[Bytes]:
+        """Iterate the body chunks as they arrive (`async for chunk in response.stream()`).
+        Consumes the body, afterwards bytes(), json() and text() raise RuntimeError.
+        """
+
+    def iter_sse(self) -> AsyncIterator[ServerSentEvent]:
+        """Iterate Server-Sent Events (text/event-stream) parsed from the body as they arrive.
+        Consumes the body (use before other body reading methods).
+        """
+
+    def into_proxy_response(self, *, strip_hop_by_hop: bool = True) -> "ProxyResponse[ResponseBodyReader]":
+        """Status, headers and body stream for forwarding the response verbatim (e.g. in a reverse proxy).
+        Hop-by-hop headers (Connection, Keep-Alive, Transfer-Encoding etc. and those listed in Connection) are
+        removed from the headers copy unless `strip_hop_by_hop` is false.
+        Consumes the body (use before other body reading methods).
+        """
+
+    async def aclose(self) -> None:
+        """Close the body reader and release the connection without waiting for garbage collection.
+        Reading the body afterwards raises RuntimeError. Closing again is a no-op.
+        """
 
     @property
     def body_reader(self) -> "ResponseBodyReader":
@@ -77,13 +186,84 @@ class SyncResponse(BaseResponse):
     def bytes(self) -> Bytes:
         """Return entire body as bytes (cached after first read)."""
 
-    def json(self) -> Any:
+    def json(
+        self,
+        *,
+        object_hook: Callable[[dict[str, Any]], Any] | None = None,
+        on_duplicate_key: Literal["error", "first", "last"] = "last",
+        exact_numbers: bool = False,
+        allow_nan: bool = False,
+    ) -> Any:
         """Decode body as JSON (underlying bytes cached after first read). Uses serde for decoding.
+        A non UTF-8 charset from Content-Type is decoded like in `text()` before parsing.
         User can provide custom deserializer via `SyncClientBuilder.json_handler`.
+        `object_hook` is called on each decoded object (innermost first) like in `json.loads`.
+        `on_duplicate_key` controls duplicate object keys: raise JSONDecodeError, keep the first or the last value.
+        `exact_numbers` decodes integers of any size without precision loss and other numbers as `decimal.Decimal`.
+        It is slower as it decodes via the Python `json` module.
+        `allow_nan` accepts the non-standard `NaN`, `Infinity` and `-Infinity` tokens as floats (also via `json`).
+        By default they raise JSONDecodeError. These apply to the built-in deserializer only, passing them with a custom
+        `json_handler` loads raises ValueError. Custom loads reads the raw body, the charset is not decoded for it.
+        """
+
+    def text(self, *, strip_bom: bool = True, sniff_html_charset: bool = False) -> str:
+        """Decode body to text (underlying bytes cached after first read). Uses charset from Content-Type.
+
+        A leading UTF-8 or UTF-16 byte order mark takes precedence over the charset. It is removed from the text
+        unless strip_bom is False. With sniff_html_charset, an HTML body without a Content-Type charset is decoded
+        using the `<meta charset>` declared in its first 1024 bytes. Otherwise UTF-8 is used.
+        """
+
+    def decode(self, encoding: str) -> str:
+        """Decode body to text with the given encoding label, ignoring Content-Type charset.
+
+        Underlying bytes are cached after first read, so already read bytes are not fetched again.
+        """
+
+    def buffer_all(self) -> None:
+        """Read the remaining body into memory. Afterwards bytes(), json() and text() can be called repeatedly.
+        Not allowed after reading body partially via body_reader.
+        """
+
+    def body_as_mmap(self, dir: str | PathLike[str] | None = None) -> memoryview:
+        """Stream the body into a temporary file and return a read-only memory-mapped view over it. Useful for very
+        large bodies that must be re-read without holding them in RAM. The temporary file is created in `dir` (system
+        default if None), it is not visible in the file system and is released when the view is dropped.
+        Consumes the body (use before other body reading methods).
+        """
+
+    def write_to(self, path: str | PathLike[str], chunk_callback: Callable[[int], object] | None = None) -> int:
+        """Stream the body into the file at `path` (created or truncated) without holding it in memory.
+        Returns the number of bytes written. `chunk_callback` is called with the number of bytes received so far
+        after each chunk, e.g. for progress reporting. Consumes the body (use before other body reading methods).
+        """
+
+    def json_stream_to(self, callback: Callable[[Any], object]) -> int:
+        """Incrementally decode a top level JSON array body, calling `callback` for each element as it arrives.
+        Returns the number of elements. Consumes the body (use before other body reading methods).
+        """
+
+    def iter_bytes(self) -> Iterator[Bytes]:
+        """Iterate the body chunks as they arrive (`for chunk in response.iter_bytes()`).
+        Consumes the body, afterwards bytes(), json() and text() raise RuntimeError.
+        """
+
+    def iter_sse(self) -> Iterator[ServerSentEvent]:
+        """Iterate Server-Sent Events (text/event-stream) parsed from the body as they arrive.
+        Consumes the body (use before other body reading methods).
+        """
+
+    def into_proxy_response(self, *, strip_hop_by_hop: bool = True) -> "ProxyResponse[SyncResponseBodyReader]":
+        """Status, headers and body stream for forwarding the response verbatim (e.g. in a reverse proxy).
+        Hop-by-hop headers (Connection, Keep-Alive, Transfer-Encoding etc. and those listed in Connection) are
+        removed from the headers copy unless `strip_hop_by_hop` is false.
+        Consumes the body (use before other body reading methods).
         """
 
-    def text(self) -> str:
-        """Decode body to text (underlying bytes cached after first read). Uses charset from Content-Type."""
+    def close(self) -> None:
+        """Close the body reader and release the connection without waiting for garbage collection.
+        Reading the body afterwards raises RuntimeError. Closing again is a no-op.
+        """
 
     @property
     def body_reader(self) -> "SyncResponseBodyReader":
@@ -135,6 +315,10 @@ class ResponseBuilder:
 class ResponseBodyReader:
     """Streaming body reader."""
 
+    def __aiter__(self) -> Self: ...
+    async def __anext__(self) -> Bytes:
+        """Return next raw chunk like `read_chunk`. Allows using the reader as a body stream."""
+
     async def bytes(self) -> Bytes:
         """Read remaining stream fully and return bytes (caches)."""
 
@@ -147,6 +331,10 @@ class ResponseBodyReader:
 class SyncResponseBodyReader:
     """Streaming body reader."""
 
+    def __iter__(self) -> Self: ...
+    def __next__(self) -> Bytes:
+        """Return next raw chunk like `read_chunk`. Allows using the reader as a body stream."""
+
     def bytes(self) -> Bytes:
         """Read remaining stream fully and return bytes (caches)."""
 
@@ -155,3 +343,43 @@ class SyncResponseBodyReader:
 
     def read_chunk(self) -> Bytes | None:
         """Return next raw chunk. Sizes are arbitrary and depend on OS. None when stream is exhausted."""
+
+_BodyReader = TypeVar("_BodyReader", ResponseBodyReader, SyncResponseBodyReader)
+
+class ProxyResponse(Generic[_BodyReader]):
+    """Response parts for forwarding the response verbatim. See `Response.into_proxy_response`."""
+
+    @property
+    def status(self) -> int:
+        """HTTP status code."""
+
+    @property
+    def version(self) -> str:
+        """Used HTTP version."""
+
+    @property
+    def headers(self) -> HeaderMap:
+        """Copy of the response headers."""
+
+    @property
+    def body(self) -> _BodyReader:
+        """Body stream. Iterate it for the raw chunks or pass it as a request or response body stream."""
+
+class ServerSentEvent:
+    """Server-Sent Event parsed from a text/event-stream body."""
+
+    @property
+    def event(self) -> str:
+        """Event type. Defaults to "message"."""
+
+    @property
+    def data(self) -> str:
+        """Event data. Multiple data lines are joined with newlines."""
+
+    @property
+    def id(self) -> str | None:
+        """Last event ID seen in the stream."""
+
+    @property
+    def retry(self) -> int | None:
+        """Reconnection time in milliseconds, if the event set it."""
@@ -1,7 +1,8 @@
 """Common types and interfaces used in the library."""
 
 from collections.abc import AsyncIterable, Iterable, Mapping, Sequence
-from typing import Any, TypeAlias
+from datetime import timedelta
+from typing import Any, TypeAlias, TypedDict
 
 HeadersType: TypeAlias = Mapping[str, str] | Sequence[tuple[str, str]]
 QueryParams: TypeAlias = Mapping[str, Any] | Sequence[tuple[str, Any]]
@@ -10,3 +11,26 @@ ExtensionsType: TypeAlias = Mapping[str, Any] | Sequence[tuple[str, Any]]
 
 SyncStream: TypeAlias = Iterable[bytes] | Iterable[bytearray] | Iterable[memoryview]
 Stream: TypeAlias = AsyncIterable[bytes] | AsyncIterable[bytearray] | AsyncIterable[memoryview] | SyncStream
+
+
+class Link(TypedDict):
+    """Link parsed from the `Link` header. Parameter names are lowercased."""
+
+    url: str
+    params: dict[str, str]
+
+
+class RequestTimings(TypedDict):
+    """Elapsed times from the request start until each phase finished. See `BaseResponse.timings`."""
+
+    tag: str | None
+    """Tag of the request set via `RequestBuilder.tag`."""
+    dns: timedelta | None
+    """DNS resolution finished. None when an existing connection was reused or the address was served from the
+    `dns_cache_ttl` cache."""
+    connect: timedelta | None
+    """Connection established, including the TLS handshake. None when an existing connection was reused."""
+    ttfb: timedelta
+    """Response headers received."""
+    total: timedelta
+    """Response returned by `send`. Includes reading the body unless the response is streamed."""
@@ -1,11 +1,12 @@
-use crate::client::internal::ConnectionLimiter;
 use crate::client::internal::Spawner;
+use crate::client::internal::{ConnectionLimiter, SpawnerConfig};
 use crate::client::runtime::RuntimeHandle;
+use crate::exceptions::ClientClosedError;
 use crate::http::{HeaderMap, Url, UrlType};
 use crate::internal::json::JsonHandler;
-use crate::internal::types::Method;
+use crate::internal::types::{Method, QueryParams};
 use crate::middleware::NextInner;
-use crate::request::{BaseRequestBuilder, RequestBuilder, SyncRequestBuilder};
+use crate::request::{BaseRequestBuilder, RequestBuilder, StreamRequest, SyncRequestBuilder, SyncStreamRequest};
 use pyo3::prelude::*;
 use pyo3::{PyTraverseError, PyVisit};
 use std::sync::Arc;
@@ -21,6 +22,7 @@ pub struct BaseClient {
     json_handler: Option<JsonHandler>,
     total_timeout: Option<Duration>,
     connection_limiter: Option<ConnectionLimiter>,
+    spawner_config: SpawnerConfig,
     error_for_status: bool,
     default_headers: Option<HeaderMap>,
     close_cancellation: CancellationToken,
@@ -55,6 +57,7 @@ impl BaseClient {
         json_handler: Option<JsonHandler>,
         total_timeout: Option<Duration>,
         connection_limiter: Option<ConnectionLimiter>,
+        spawner_config: SpawnerConfig,
         error_for_status: bool,
         default_headers: Option<HeaderMap>,
         base_url: Option<Url>,
@@ -66,6 +69,7 @@ impl BaseClient {
             json_handler,
             total_timeout,
             connection_limiter,
+            spawner_config,
             error_for_status,
             default_headers,
             base_url,
@@ -80,9 +84,15 @@ impl BaseClient {
         is_blocking: bool,
     ) -> PyResult<BaseRequestBuilder> {
         let py = url.py();
+        if self.close_cancellation.is_cancelled() {
+            return Err(ClientClosedError::from_causes("Client was closed", vec![]));
+        }
 
         let url: reqwest::Url = match self.base_url.as_ref() {
-            Some(base_url) => base_url.join(url.extract()?)?.into(),
+            Some(base_url) => match url.extract::<&str>() {
+                Ok(url) => base_url.join(url)?.into(),
+                Err(_) => base_url.join(url.str()?.to_str()?)?.into(), // E.g. Url, keeping its query and fragment
+            },
             None => url.extract::<UrlType>()?.0,
         };
         let json_handler = self.json_handler.as_ref().map(|v| v.clone_ref(py));
@@ -92,6 +102,7 @@ impl BaseClient {
                 self.client.clone(),
                 self.runtime.clone(),
                 self.connection_limiter.clone(),
+                self.spawner_config,
                 self.close_cancellation.child_token(),
             );
 
@@ -118,6 +129,23 @@ impl BaseClient {
         })
     }
 
+    pub fn create_get_request_builder(
+        &self,
+        url: Bound<PyAny>,
+        is_blocking: bool,
+        params: Option<QueryParams>,
+        headers: Option<HeaderMap>,
+    ) -> PyResult<BaseRequestBuilder> {
+        let mut builder = self.create_request_builder(http::Method::GET.into(), url, is_blocking)?;
+        if let Some(params) = params {
+            builder.inner_query(&params)?;
+        }
+        if let Some(headers) = headers {
+            builder.inner_headers(&headers)?;
+        }
+        Ok(builder)
+    }
+
     pub fn init_middleware_next(&self) -> PyResult<Option<NextInner>> {
         self.middlewares
             .as_ref()
@@ -133,8 +161,15 @@ impl Client {
         RequestBuilder::new_py(slf.py(), builder)
     }
 
-    pub fn get(slf: PyRef<Self>, url: Bound<PyAny>) -> PyResult<Py<RequestBuilder>> {
-        Self::request(slf, http::Method::GET.into(), url)
+    #[pyo3(signature = (url, *, params=None, headers=None))]
+    pub fn get(
+        slf: PyRef<Self>,
+        url: Bound<PyAny>,
+        params: Option<QueryParams>,
+        headers: Option<HeaderMap>,
+    ) -> PyResult<Py<RequestBuilder>> {
+        let builder = slf.as_super().create_get_request_builder(url, false, params, headers)?;
+        RequestBuilder::new_py(slf.py(), builder)
     }
 
     pub fn post(slf: PyRef<Self>, url: Bound<PyAny>) -> PyResult<Py<RequestBuilder>> {
@@ -157,6 +192,12 @@ impl Client {
         Self::request(slf, http::Method::HEAD.into(), url)
     }
 
+    pub fn stream(slf: PyRef<Self>, method: Method, url: Bound<PyAny>) -> PyResult<Py<StreamRequest>> {
+        let py = slf.py();
+        let builder = Self::request(slf, method, url)?;
+        RequestBuilder::build_streamed(builder.bind(py).borrow_mut(), py)
+    }
+
     async fn __aenter__(slf: Py<Self>) -> Py<Self> {
         slf
     }
@@ -191,8 +232,15 @@ impl SyncClient {
         SyncRequestBuilder::new_py(slf.py(), builder)
     }
 
-    pub fn get(slf: PyRef<Self>, url: Bound<PyAny>) -> PyResult<Py<SyncRequestBuilder>> {
-        Self::request(slf, http::Method::GET.into(), url)
+    #[pyo3(signature = (url, *, params=None, headers=None))]
+    pub fn get(
+        slf: PyRef<Self>,
+        url: Bound<PyAny>,
+        params: Option<QueryParams>,
+        headers: Option<HeaderMap>,
+    ) -> PyResult<Py<SyncRequestBuilder>> {
+        let builder = slf.as_super().create_get_request_builder(url, true, params, headers)?;
+        SyncRequestBuilder::new_py(slf.py(), builder)
     }
 
     pub fn post(slf: PyRef<Self>, url: Bound<PyAny>) -> PyResult<Py<SyncRequestBuilder>> {
@@ -215,6 +263,12 @@ impl SyncClient {
         Self::request(slf, http::Method::HEAD.into(), url)
     }
 
+    pub fn stream(slf: PyRef<Self>, method: Method, url: Bound<PyAny>) -> PyResult<Py<SyncStreamRequest>> {
+        let py = slf.py();
+        let builder = Self::request(slf, method, url)?;
+        SyncRequestBuilder::build_streamed(builder.bind(py).borrow_mut(), py)
+    }
+
     fn __enter__(slf: Py<Self>) -> Py<Self> {
         slf
     }
@@ -1,21 +1,29 @@
 use crate::asyncio::is_async_callable;
 use crate::client::Client;
 use crate::client::client::{BaseClient, SyncClient};
-use crate::client::internal::ConnectionLimiter;
+use crate::client::internal::{
+    ConnectionLimiter, DnsCache, GaiResolver, RequestCompression, ResolvConfResolver, SpawnerConfig,
+    TimingsConnectLayer, TimingsResolver, TlsConfig, parse_scoped_ip, scope_interface,
+};
 use crate::client::runtime::Runtime;
 use crate::client::runtime::RuntimeHandle;
-use crate::cookie::{CookieStore, CookieStorePyProxy};
+use crate::cookie::{CookieStore, CookieStorePyProxy, CookieType};
 use crate::exceptions::BuilderError;
 use crate::http::{HeaderMap, Url, UrlType};
 use crate::internal::json::JsonHandler;
+use crate::internal::types::RequestVersion;
+use crate::middleware::{RecordMiddleware, SyncRecordMiddleware};
 use crate::proxy::ProxyBuilder;
-use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::exceptions::{PyFileNotFoundError, PyOSError, PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyDict, PyType};
 use pyo3::{PyTraverseError, PyVisit, intern};
 use pyo3_bytes::PyBytes;
+use reqwest::dns::Resolve;
 use reqwest::redirect;
 use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -36,6 +44,24 @@ pub struct BaseClientBuilder {
     default_headers: Option<HeaderMap>,
     runtime: Option<Py<Runtime>>,
     base_url: Option<Url>,
+    record_sink: Option<Py<PyAny>>,
+    retry_policy: Option<Py<PyAny>>,
+    spawner_config: SpawnerConfig,
+    dns_cache_ttl: Option<Duration>,
+    resolver_from_system: bool,
+    http_version_pref: Option<http::Version>,
+    http3_prior_knowledge: bool,
+    http3_max_idle_timeout: Option<Duration>,
+    http3_congestion_bbr: bool,
+    default_cookie_store: bool,
+    has_cookie_provider: bool,
+    tls_config: TlsConfig,
+}
+
+enum RedirectAction {
+    Follow,
+    Stop,
+    Error,
 }
 
 #[pyclass(extends=BaseClientBuilder)]
@@ -46,12 +72,12 @@ pub struct SyncClientBuilder;
 
 #[pymethods]
 impl BaseClientBuilder {
-    fn base_url(mut slf: PyRefMut<Self>, base_url: UrlType) -> PyResult<PyRefMut<Self>> {
-        if !base_url.0.as_str().ends_with('/') {
+    fn base_url(mut slf: PyRefMut<Self>, url: UrlType) -> PyResult<PyRefMut<Self>> {
+        if !url.0.as_str().ends_with('/') {
             return Err(PyValueError::new_err("base_url must end with a trailing slash '/'"));
         }
         slf.check_inner()?;
-        slf.base_url = Some(base_url.into());
+        slf.base_url = Some(url.into());
         Ok(slf)
     }
 
@@ -67,9 +93,43 @@ impl BaseClientBuilder {
         Ok(slf)
     }
 
-    fn error_for_status(mut slf: PyRefMut<Self>, value: bool) -> PyResult<PyRefMut<Self>> {
+    #[pyo3(signature = (algorithm, min_size=1024))]
+    fn compress_requests(mut slf: PyRefMut<Self>, algorithm: String, min_size: usize) -> PyResult<PyRefMut<Self>> {
         slf.check_inner()?;
-        slf.error_for_status = value;
+        slf.spawner_config.request_compression = Some(RequestCompression::new(&algorithm, min_size)?);
+        Ok(slf)
+    }
+
+    #[pyo3(signature = (enable=true))]
+    fn error_for_status(mut slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        slf.error_for_status = enable;
+        Ok(slf)
+    }
+
+    fn request_jitter(mut slf: PyRefMut<Self>, max_delay: Option<Duration>) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        slf.spawner_config.request_jitter = max_delay;
+        Ok(slf)
+    }
+
+    #[pyo3(signature = (enable=true))]
+    fn retry_on_incomplete_message(mut slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        slf.spawner_config.retry_on_incomplete_message = enable;
+        Ok(slf)
+    }
+
+    #[pyo3(signature = (enable=true))]
+    fn collect_timings(mut slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        slf.spawner_config.collect_timings = enable;
+        Ok(slf)
+    }
+
+    fn max_response_header_size(mut slf: PyRefMut<Self>, size: Option<usize>) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        slf.spawner_config.max_response_header_size = size;
         Ok(slf)
     }
 
@@ -77,32 +137,115 @@ impl BaseClientBuilder {
         Self::apply(slf, |builder| Ok(builder.user_agent(value)))
     }
 
+    #[pyo3(signature = (name, version, comment=None))]
+    fn user_agent_product(
+        slf: PyRefMut<Self>,
+        name: String,
+        version: String,
+        comment: Option<String>,
+    ) -> PyResult<PyRefMut<Self>> {
+        if !is_http_token(&name) {
+            return Err(PyValueError::new_err(format!("Invalid user agent product name: {:?}", name)));
+        }
+        if !is_http_token(&version) {
+            return Err(PyValueError::new_err(format!("Invalid user agent product version: {:?}", version)));
+        }
+        let value = match comment {
+            Some(comment) if is_http_comment_text(&comment) => format!("{}/{} ({})", name, version, comment),
+            Some(comment) => return Err(PyValueError::new_err(format!("Invalid user agent comment: {:?}", comment))),
+            None => format!("{}/{}", name, version),
+        };
+        Self::apply(slf, |builder| Ok(builder.user_agent(value)))
+    }
+
     fn default_headers(mut slf: PyRefMut<'_, Self>, headers: HeaderMap) -> PyResult<PyRefMut<'_, Self>> {
         slf.check_inner()?;
         slf.default_headers = Some(headers);
         Ok(slf)
     }
 
-    fn default_cookie_store(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+    #[pyo3(signature = (prefix="PYREQWEST_HEADER_"))]
+    fn headers_from_env<'py>(mut slf: PyRefMut<'py, Self>, prefix: &str) -> PyResult<PyRefMut<'py, Self>> {
+        slf.check_inner()?;
+        let mut vars: Vec<(String, String)> = std::env::vars_os()
+            .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)))
+            .filter(|(key, _)| key.starts_with(prefix))
+            .collect();
+        vars.sort();
+
+        let mut headers = http::HeaderMap::new();
+        for (key, value) in vars {
+            let name = key[prefix.len()..].replace('_', "-");
+            let name = http::HeaderName::from_str(&name)
+                .map_err(|e| PyValueError::new_err(format!("Invalid header name in env var {}: {}", key, e)))?;
+            let value = http::HeaderValue::from_str(&value)
+                .map_err(|e| PyValueError::new_err(format!("Invalid header value in env var {}: {}", key, e)))?;
+            headers.append(name, value);
+        }
+
+        match slf.default_headers.as_ref() {
+            Some(default_headers) => default_headers.mut_map(|map| {
+                map.extend(headers);
+                Ok(())
+            })?,
+            None => slf.default_headers = Some(HeaderMap::from(headers)),
+        }
+        Ok(slf)
+    }
+
+    fn record(mut slf: PyRefMut<Self>, sink: Py<PyAny>) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        slf.record_sink = Some(sink);
+        Ok(slf)
+    }
+
+    fn with_retries_middleware<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        policy: Bound<'py, PyAny>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        slf.check_inner()?;
+        static RETRY_POLICY: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+        let policy_type = RETRY_POLICY.import(slf.py(), "pyreqwest.middleware.retry", "RetryPolicy")?;
+        if !policy.is_instance(policy_type)? {
+            return Err(PyTypeError::new_err("Expected a RetryPolicy"));
+        }
+        slf.retry_policy = Some(policy.unbind());
+        Ok(slf)
+    }
+
+    #[pyo3(signature = (enable=true))]
+    fn default_cookie_store(mut slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+        slf.default_cookie_store = enable;
         Self::apply(slf, |builder| Ok(builder.cookie_store(enable)))
     }
 
-    fn cookie_provider(slf: PyRefMut<'_, Self>, provider: Py<CookieStore>) -> PyResult<PyRefMut<'_, Self>> {
+    fn cookie_provider(mut slf: PyRefMut<'_, Self>, provider: Py<CookieStore>) -> PyResult<PyRefMut<'_, Self>> {
+        slf.has_cookie_provider = true;
+        Self::apply(slf, |builder| Ok(builder.cookie_provider(Arc::new(CookieStorePyProxy(provider)))))
+    }
+
+    fn cookies(mut slf: PyRefMut<'_, Self>, initial: Vec<CookieType>, url: UrlType) -> PyResult<PyRefMut<'_, Self>> {
+        let provider = Py::new(slf.py(), CookieStore::with_cookies(initial, &url.0)?)?;
+        slf.has_cookie_provider = true;
         Self::apply(slf, |builder| Ok(builder.cookie_provider(Arc::new(CookieStorePyProxy(provider)))))
     }
 
+    #[pyo3(signature = (enable=true))]
     fn gzip(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
         Self::apply(slf, |builder| Ok(builder.gzip(enable)))
     }
 
+    #[pyo3(signature = (enable=true))]
     fn brotli(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
         Self::apply(slf, |builder| Ok(builder.brotli(enable)))
     }
 
+    #[pyo3(signature = (enable=true))]
     fn zstd(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
         Self::apply(slf, |builder| Ok(builder.zstd(enable)))
     }
 
+    #[pyo3(signature = (enable=true))]
     fn deflate(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
         Self::apply(slf, |builder| Ok(builder.deflate(enable)))
     }
@@ -111,6 +254,27 @@ impl BaseClientBuilder {
         Self::apply(slf, |builder| Ok(builder.redirect(redirect::Policy::limited(max_redirects))))
     }
 
+    #[pyo3(signature = (enable=true))]
+    fn follow_redirects(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+        let policy = if enable {
+            redirect::Policy::default()
+        } else {
+            redirect::Policy::none()
+        };
+        Self::apply(slf, |builder| Ok(builder.redirect(policy)))
+    }
+
+    fn redirect_policy_fn(slf: PyRefMut<Self>, callback: Py<PyAny>) -> PyResult<PyRefMut<Self>> {
+        let policy = redirect::Policy::custom(move |attempt| match Self::call_redirect_policy(&callback, &attempt) {
+            Ok(RedirectAction::Follow) => attempt.follow(),
+            Ok(RedirectAction::Stop) => attempt.stop(),
+            Ok(RedirectAction::Error) => attempt.error("redirect rejected by redirect_policy_fn"),
+            Err(err) => attempt.error(err.to_string()), // Raised as RedirectError
+        });
+        Self::apply(slf, |builder| Ok(builder.redirect(policy)))
+    }
+
+    #[pyo3(signature = (enable=true))]
     fn referer(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
         Self::apply(slf, |builder| Ok(builder.referer(enable)))
     }
@@ -158,23 +322,31 @@ impl BaseClientBuilder {
         Ok(slf)
     }
 
+    #[pyo3(signature = (enable=true))]
     fn http1_allow_obsolete_multiline_headers_in_responses(
         slf: PyRefMut<Self>,
-        value: bool,
+        enable: bool,
     ) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.http1_allow_obsolete_multiline_headers_in_responses(value)))
+        Self::apply(slf, |builder| Ok(builder.http1_allow_obsolete_multiline_headers_in_responses(enable)))
     }
 
-    fn http1_ignore_invalid_headers_in_responses(slf: PyRefMut<Self>, value: bool) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.http1_ignore_invalid_headers_in_responses(value)))
+    #[pyo3(signature = (enable=true))]
+    fn http1_ignore_invalid_headers_in_responses(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+        Self::apply(slf, |builder| Ok(builder.http1_ignore_invalid_headers_in_responses(enable)))
     }
 
-    fn http1_allow_spaces_after_header_name_in_responses(slf: PyRefMut<Self>, value: bool) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.http1_allow_spaces_after_header_name_in_responses(value)))
+    #[pyo3(signature = (enable=true))]
+    fn http1_allow_spaces_after_header_name_in_responses(
+        slf: PyRefMut<Self>,
+        enable: bool,
+    ) -> PyResult<PyRefMut<Self>> {
+        Self::apply(slf, |builder| Ok(builder.http1_allow_spaces_after_header_name_in_responses(enable)))
     }
 
     fn http1_only(slf: PyRefMut<Self>) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.http1_only()))
+        let mut slf = Self::apply(slf, |builder| Ok(builder.http1_only()))?;
+        slf.http_version_pref = Some(http::Version::HTTP_11);
+        Ok(slf)
     }
 
     fn http09_responses(slf: PyRefMut<Self>) -> PyResult<PyRefMut<Self>> {
@@ -182,7 +354,48 @@ impl BaseClientBuilder {
     }
 
     fn http2_prior_knowledge(slf: PyRefMut<Self>) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.http2_prior_knowledge()))
+        let mut slf = Self::apply(slf, |builder| Ok(builder.http2_prior_knowledge()))?;
+        slf.http_version_pref = Some(http::Version::HTTP_2);
+        Ok(slf)
+    }
+
+    fn http3_prior_knowledge(mut slf: PyRefMut<Self>) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        slf.http3_prior_knowledge = true;
+        Ok(slf)
+    }
+
+    fn http3_max_idle_timeout(mut slf: PyRefMut<Self>, timeout: Option<Duration>) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        slf.http3_max_idle_timeout = timeout;
+        Ok(slf)
+    }
+
+    #[pyo3(signature = (enable=true))]
+    fn http3_congestion_bbr(mut slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        slf.http3_congestion_bbr = enable;
+        Ok(slf)
+    }
+
+    fn prefer_versions(slf: PyRefMut<Self>, versions: Vec<RequestVersion>) -> PyResult<PyRefMut<Self>> {
+        let versions: Vec<http::Version> = versions.into_iter().map(|v| v.0).collect();
+        match versions.as_slice() {
+            [http::Version::HTTP_2, http::Version::HTTP_11] => {
+                // reqwest can not go back to the default ALPN negotiation after a single version was chosen
+                if slf.http_version_pref.is_some() {
+                    return Err(PyValueError::new_err(
+                        "Default version preference can not be combined with http1_only or http2_prior_knowledge",
+                    ));
+                }
+                Self::apply(slf, Ok)
+            }
+            [http::Version::HTTP_11] => Self::http1_only(slf),
+            [http::Version::HTTP_2] => Self::http2_prior_knowledge(slf),
+            _ => Err(PyValueError::new_err(
+                "Unsupported version preference, expected ['HTTP/2', 'HTTP/1.1'], ['HTTP/1.1'] or ['HTTP/2']",
+            )),
+        }
     }
 
     fn http2_initial_stream_window_size(slf: PyRefMut<Self>, value: Option<u32>) -> PyResult<PyRefMut<Self>> {
@@ -193,8 +406,18 @@ impl BaseClientBuilder {
         Self::apply(slf, |builder| Ok(builder.http2_initial_connection_window_size(value)))
     }
 
-    fn http2_adaptive_window(slf: PyRefMut<Self>, enabled: bool) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.http2_adaptive_window(enabled)))
+    #[pyo3(signature = (*, stream=None, connection=None))]
+    fn http2_windows(slf: PyRefMut<Self>, stream: Option<u32>, connection: Option<u32>) -> PyResult<PyRefMut<Self>> {
+        Self::apply(slf, |builder| {
+            Ok(builder
+                .http2_initial_stream_window_size(stream)
+                .http2_initial_connection_window_size(connection))
+        })
+    }
+
+    #[pyo3(signature = (enable=true))]
+    fn http2_adaptive_window(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+        Self::apply(slf, |builder| Ok(builder.http2_adaptive_window(enable)))
     }
 
     fn http2_max_frame_size(slf: PyRefMut<Self>, value: Option<u32>) -> PyResult<PyRefMut<Self>> {
@@ -213,21 +436,47 @@ impl BaseClientBuilder {
         Self::apply(slf, |builder| Ok(builder.http2_keep_alive_timeout(timeout)))
     }
 
-    fn http2_keep_alive_while_idle(slf: PyRefMut<Self>, enabled: bool) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.http2_keep_alive_while_idle(enabled)))
+    #[pyo3(signature = (enable=true))]
+    fn http2_keep_alive_while_idle(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+        Self::apply(slf, |builder| Ok(builder.http2_keep_alive_while_idle(enable)))
+    }
+
+    #[pyo3(signature = (interval, timeout=None, while_idle=false))]
+    fn http2_keep_alive(
+        slf: PyRefMut<Self>,
+        interval: Option<Duration>,
+        timeout: Option<Duration>,
+        while_idle: bool,
+    ) -> PyResult<PyRefMut<Self>> {
+        let slf = Self::http2_keep_alive_interval(slf, interval)?;
+        let slf = match timeout {
+            Some(timeout) => Self::http2_keep_alive_timeout(slf, timeout)?,
+            None => slf,
+        };
+        Self::http2_keep_alive_while_idle(slf, while_idle)
     }
 
-    fn tcp_nodelay(slf: PyRefMut<Self>, enabled: bool) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.tcp_nodelay(enabled)))
+    #[pyo3(signature = (enable=true))]
+    fn tcp_nodelay(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+        Self::apply(slf, |builder| Ok(builder.tcp_nodelay(enable)))
     }
 
     fn local_address(slf: PyRefMut<Self>, addr: Option<String>) -> PyResult<PyRefMut<Self>> {
+        let (addr, scope_id) = match addr.as_deref().map(parse_scoped_ip).transpose()? {
+            Some(SocketAddr::V6(addr)) => (Some(IpAddr::V6(*addr.ip())), addr.scope_id()),
+            Some(addr) => (Some(addr.ip()), 0),
+            None => (None, 0),
+        };
+        let interface = match scope_id {
+            0 => None,
+            scope_id => Some(scope_interface(scope_id)?),
+        };
         Self::apply(slf, |builder| {
-            let addr = addr
-                .map(|v| IpAddr::from_str(v.as_str()))
-                .transpose()
-                .map_err(|e| PyValueError::new_err(e.to_string()))?;
-            Ok(builder.local_address(addr))
+            let builder = builder.local_address(addr);
+            match interface {
+                Some(interface) => Ok(Self::bind_interface(builder, &interface)),
+                None => Ok(builder),
+            }
         })
     }
 
@@ -239,11 +488,37 @@ impl BaseClientBuilder {
 
     #[cfg(not(any(target_os = "linux", target_os = "macos")))]
     fn interface(slf: PyRefMut<Self>, value: String) -> PyResult<PyRefMut<Self>> {
-        Err(PyValueError::new_err("interface is not supported on this platform"))
+        Err(PyValueError::new_err(
+            "interface is not supported on this platform, use local_address with the interface IP address instead",
+        ))
     } // :NOCOV_END
 
-    fn tcp_keepalive(slf: PyRefMut<Self>, duration: Option<Duration>) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.tcp_keepalive(duration)))
+    #[cfg(unix)]
+    fn unix_socket(slf: PyRefMut<Self>, path: PathBuf) -> PyResult<PyRefMut<Self>> {
+        Self::apply(slf, |builder| Ok(builder.unix_socket(path)))
+    }
+
+    // :NOCOV_START
+    #[cfg(not(unix))]
+    fn unix_socket(slf: PyRefMut<Self>, path: PathBuf) -> PyResult<PyRefMut<Self>> {
+        Err(PyValueError::new_err("unix_socket is not supported on this platform"))
+    } // :NOCOV_END
+
+    #[pyo3(signature = (duration, interval=None, retries=None))]
+    fn tcp_keepalive(
+        slf: PyRefMut<Self>,
+        duration: Option<Duration>,
+        interval: Option<Duration>,
+        retries: Option<u32>,
+    ) -> PyResult<PyRefMut<Self>> {
+        let mut slf = Self::apply(slf, |builder| Ok(builder.tcp_keepalive(duration)))?;
+        if let Some(interval) = interval {
+            slf = Self::tcp_keepalive_interval(slf, Some(interval))?;
+        }
+        if let Some(retries) = retries {
+            slf = Self::tcp_keepalive_retries(slf, Some(retries))?;
+        }
+        Ok(slf)
     }
 
     fn tcp_keepalive_interval(slf: PyRefMut<Self>, interval: Option<Duration>) -> PyResult<PyRefMut<Self>> {
@@ -266,61 +541,130 @@ impl BaseClientBuilder {
     } // :NOCOV_END
 
     fn add_root_certificate_der(slf: PyRefMut<Self>, cert: PyBytes) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| {
+        let mut slf = Self::apply(slf, |builder| {
             let cert =
                 reqwest::Certificate::from_der(cert.as_slice()).map_err(|e| PyValueError::new_err(e.to_string()))?;
             Ok(builder.add_root_certificate(cert))
-        })
+        })?;
+        slf.tls_config.add_root_certificate_der(cert.as_slice());
+        Ok(slf)
     }
 
     fn add_root_certificate_pem(slf: PyRefMut<Self>, cert: PyBytes) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| {
+        let mut slf = Self::apply(slf, |builder| {
             let cert =
                 reqwest::Certificate::from_pem(cert.as_slice()).map_err(|e| PyValueError::new_err(e.to_string()))?;
             Ok(builder.add_root_certificate(cert))
-        })
+        })?;
+        slf.tls_config.add_root_certificate_pem(cert.as_slice())?;
+        Ok(slf)
     }
 
     fn add_crl_pem(slf: PyRefMut<Self>, cert: PyBytes) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| {
+        let mut slf = Self::apply(slf, |builder| {
             let cert = reqwest::tls::CertificateRevocationList::from_pem(cert.as_slice())
                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
             Ok(builder.add_crl(cert))
-        })
+        })?;
+        slf.tls_config.add_crl_pem(cert.as_slice())?;
+        Ok(slf)
     }
 
+    #[pyo3(signature = (enable=true))]
     fn tls_built_in_root_certs(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.tls_built_in_root_certs(enable)))
+        let mut slf = Self::apply(slf, |builder| Ok(builder.tls_built_in_root_certs(enable)))?;
+        slf.tls_config.set_built_in_root_certs(enable);
+        Ok(slf)
     }
 
     fn identity_pem(slf: PyRefMut<Self>, buf: PyBytes) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| {
+        let mut slf = Self::apply(slf, |builder| {
             let identity =
                 reqwest::Identity::from_pem(buf.as_slice()).map_err(|e| PyValueError::new_err(e.to_string()))?;
             Ok(builder.identity(identity))
-        })
+        })?;
+        slf.tls_config.set_identity_pem(buf.as_slice().to_vec());
+        Ok(slf)
+    }
+
+    fn tls_client_auth_from_files(
+        slf: PyRefMut<Self>,
+        cert_path: PathBuf,
+        key_path: PathBuf,
+    ) -> PyResult<PyRefMut<Self>> {
+        let pem = slf.py().detach(|| {
+            let mut pem = read_file(&cert_path)?;
+            pem.push(b'\n');
+            pem.extend(read_file(&key_path)?);
+            Ok::<_, PyErr>(pem)
+        })?;
+        let mut slf = Self::apply(slf, |builder| {
+            let identity = reqwest::Identity::from_pem(&pem).map_err(|e| PyValueError::new_err(e.to_string()))?;
+            Ok(builder.identity(identity))
+        })?;
+        slf.tls_config.set_identity_pem(pem);
+        Ok(slf)
     }
 
+    #[pyo3(signature = (enable=true))]
     fn danger_accept_invalid_hostnames(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.danger_accept_invalid_hostnames(enable)))
+        let mut slf = Self::apply(slf, |builder| Ok(builder.danger_accept_invalid_hostnames(enable)))?;
+        slf.tls_config.accept_invalid_hostnames = enable;
+        Ok(slf)
     }
 
+    #[pyo3(signature = (enable=true))]
     fn danger_accept_invalid_certs(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.danger_accept_invalid_certs(enable)))
+        let mut slf = Self::apply(slf, |builder| Ok(builder.danger_accept_invalid_certs(enable)))?;
+        slf.tls_config.accept_invalid_certs = enable;
+        Ok(slf)
     }
 
+    fn danger_accept_invalid_certs_for(mut slf: PyRefMut<Self>, hosts: Vec<String>) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        slf.tls_config.accept_invalid_certs_for = hosts;
+        Ok(slf)
+    }
+
+    #[pyo3(signature = (enable=true))]
     fn tls_sni(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.tls_sni(enable)))
+        let mut slf = Self::apply(slf, |builder| Ok(builder.tls_sni(enable)))?;
+        slf.tls_config.set_sni(enable);
+        Ok(slf)
+    }
+
+    #[pyo3(signature = (enable=true))]
+    fn tls_info(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+        Self::apply(slf, |builder| Ok(builder.tls_info(enable)))
+    }
+
+    #[pyo3(signature = (require=true))]
+    fn ocsp_stapling(slf: PyRefMut<Self>, require: bool) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        if require {
+            // rustls passes the stapled response to the verifier but webpki does not validate OCSP responses
+            return Err(PyValueError::new_err(
+                "Requiring OCSP stapling is not supported by the TLS backend, use add_crl_pem for revocation checks",
+            ));
+        }
+        Ok(slf)
     }
 
     fn min_tls_version(slf: PyRefMut<Self>, value: String) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.min_tls_version(Self::parse_tls_version(value.as_str())?)))
+        let version = Self::parse_tls_version(value.as_str())?;
+        let mut slf = Self::apply(slf, |builder| Ok(builder.min_tls_version(version)))?;
+        slf.tls_config.set_min_version(version);
+        Ok(slf)
     }
 
     fn max_tls_version(slf: PyRefMut<Self>, value: String) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.max_tls_version(Self::parse_tls_version(value.as_str())?)))
+        let version = Self::parse_tls_version(value.as_str())?;
+        let mut slf = Self::apply(slf, |builder| Ok(builder.max_tls_version(version)))?;
+        slf.tls_config.set_max_version(version);
+        Ok(slf)
     }
 
+    #[pyo3(signature = (enable=true))]
     fn https_only(slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
         Self::apply(slf, |builder| Ok(builder.https_only(enable)))
     }
@@ -332,6 +676,19 @@ impl BaseClientBuilder {
         })
     }
 
+    fn dns_cache_ttl(mut slf: PyRefMut<Self>, ttl: Duration) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        slf.dns_cache_ttl = Some(ttl).filter(|ttl| !ttl.is_zero()); // Zero disables caching
+        Ok(slf)
+    }
+
+    #[pyo3(signature = (enable=true))]
+    fn resolver_from_system(mut slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        slf.resolver_from_system = enable;
+        Ok(slf)
+    }
+
     // :NOCOV_START
     fn __traverse__(&self, visit: PyVisit<'_>) -> Result<(), PyTraverseError> {
         if let Some(middlewares) = &self.middlewares {
@@ -339,6 +696,12 @@ impl BaseClientBuilder {
                 visit.call(mw)?;
             }
         }
+        if let Some(record_sink) = &self.record_sink {
+            visit.call(record_sink)?;
+        }
+        if let Some(retry_policy) = &self.retry_policy {
+            visit.call(retry_policy)?;
+        }
         if let Some(json_handler) = &self.json_handler {
             json_handler.__traverse__(&visit)?;
         }
@@ -347,6 +710,8 @@ impl BaseClientBuilder {
 
     fn __clear__(&mut self) {
         self.middlewares = None;
+        self.record_sink = None;
+        self.retry_policy = None;
         self.json_handler = None;
         self.runtime = None;
     } // :NOCOV_END
@@ -372,9 +737,33 @@ impl BaseClientBuilder {
                 .ok_or_else(|| PyRuntimeError::new_err("Client was already built"))?
                 .use_rustls_tls();
 
+            // Same ALPN as reqwest would use for the version preference
+            match self.http_version_pref {
+                Some(http::Version::HTTP_11) => self.tls_config.set_alpn_protocol(b"http/1.1"),
+                Some(http::Version::HTTP_2) => self.tls_config.set_alpn_protocol(b"h2"),
+                _ => {}
+            }
+            #[cfg(feature = "http3")]
+            if self.http3_prior_knowledge {
+                self.tls_config.set_alpn_protocol(b"h3");
+            }
+            if let Some(tls) = self.tls_config.custom_verifier_config()? {
+                inner_builder = inner_builder.use_preconfigured_tls(tls);
+            }
+
             if !self.http1_lower_case_headers {
                 inner_builder = inner_builder.http1_title_case_headers();
             }
+            if let Some(resolver) = self.dns_resolver()? {
+                inner_builder = inner_builder.dns_resolver2(resolver);
+            }
+            if self.spawner_config.collect_timings {
+                inner_builder = inner_builder.connector_layer(TimingsConnectLayer);
+            }
+            #[cfg(feature = "http3")]
+            {
+                inner_builder = self.inner_http3(inner_builder);
+            }
 
             let client = BaseClient::new(
                 inner_builder
@@ -386,6 +775,7 @@ impl BaseClientBuilder {
                 self.total_timeout,
                 self.max_connections
                     .map(|max| ConnectionLimiter::new(max, self.pool_timeout)),
+                self.spawner_config,
                 self.error_for_status,
                 self.default_headers.take(),
                 self.base_url.take(),
@@ -394,6 +784,73 @@ impl BaseClientBuilder {
         })
     }
 
+    // Checked before anything is consumed from the builder so it stays usable after the error
+    fn check_http3(&self) -> PyResult<()> {
+        let uses_http3 =
+            self.http3_prior_knowledge || self.http3_max_idle_timeout.is_some() || self.http3_congestion_bbr;
+        if uses_http3 && !cfg!(feature = "http3") {
+            return Err(PyValueError::new_err(
+                "http3 support not compiled, build with the http3 feature and RUSTFLAGS='--cfg reqwest_unstable'",
+            ));
+        }
+        Ok(())
+    }
+
+    // Both would silently replace each other in reqwest depending on the call order
+    fn check_cookie_store(&self) -> PyResult<()> {
+        if self.default_cookie_store && self.has_cookie_provider {
+            return Err(PyValueError::new_err(
+                "default_cookie_store(True) can not be combined with cookie_provider or cookies",
+            ));
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "http3")]
+    fn inner_http3(&self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        if self.http3_prior_knowledge {
+            builder = builder.http3_prior_knowledge();
+        }
+        if let Some(timeout) = self.http3_max_idle_timeout {
+            builder = builder.http3_max_idle_timeout(timeout);
+        }
+        if self.http3_congestion_bbr {
+            builder = builder.http3_congestion_bbr();
+        }
+        builder
+    }
+
+    fn dns_resolver(&self) -> PyResult<Option<Arc<dyn Resolve>>> {
+        let resolver: Arc<dyn Resolve> = if self.resolver_from_system {
+            Arc::new(ResolvConfResolver::new()?)
+        } else if self.dns_cache_ttl.is_some() || self.spawner_config.collect_timings {
+            Arc::new(GaiResolver)
+        } else {
+            return Ok(None); // reqwest default resolver
+        };
+        // Cache is the outermost so that only actual lookups are recorded in the timings
+        let resolver: Arc<dyn Resolve> = if self.spawner_config.collect_timings {
+            Arc::new(TimingsResolver(resolver))
+        } else {
+            resolver
+        };
+        match self.dns_cache_ttl {
+            Some(ttl) => Ok(Some(Arc::new(DnsCache::new(ttl, resolver)))),
+            None => Ok(Some(resolver)),
+        }
+    }
+
+    fn inner_with_retry_middleware(&mut self, py: Python, middleware_name: &str) -> PyResult<()> {
+        if let Some(policy) = self.retry_policy.take() {
+            let middleware = py
+                .import("pyreqwest.middleware.retry")?
+                .getattr(middleware_name)?
+                .call1((policy,))?;
+            self.inner_with_middleware(middleware)?;
+        }
+        Ok(())
+    }
+
     fn inner_with_middleware(&mut self, middleware: Bound<PyAny>) -> PyResult<()> {
         self.check_inner()?;
         self.middlewares.get_or_insert_with(Vec::new).push(middleware.unbind());
@@ -420,6 +877,17 @@ impl BaseClientBuilder {
         Ok(())
     }
 
+    // :NOCOV_START
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn bind_interface(builder: reqwest::ClientBuilder, interface: &str) -> reqwest::ClientBuilder {
+        builder.interface(interface)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    fn bind_interface(builder: reqwest::ClientBuilder, _interface: &str) -> reqwest::ClientBuilder {
+        builder // scope_interface already failed on these platforms
+    } // :NOCOV_END
+
     fn apply<F>(mut slf: PyRefMut<Self>, fun: F) -> PyResult<PyRefMut<Self>>
     where
         F: FnOnce(reqwest::ClientBuilder) -> PyResult<reqwest::ClientBuilder>,
@@ -433,6 +901,23 @@ impl BaseClientBuilder {
         Ok(slf)
     }
 
+    // Called from the connection task so the GIL is acquired here
+    fn call_redirect_policy(callback: &Py<PyAny>, attempt: &redirect::Attempt) -> PyResult<RedirectAction> {
+        Python::attach(|py| {
+            let previous = attempt.previous().last().map(|url| Url::from(url.clone()));
+            let args = (Url::from(attempt.url().clone()), previous, attempt.status().as_u16());
+            match callback.call1(py, args)?.extract::<String>(py)?.as_str() {
+                "follow" => Ok(RedirectAction::Follow),
+                "stop" => Ok(RedirectAction::Stop),
+                "error" => Ok(RedirectAction::Error),
+                other => Err(PyValueError::new_err(format!(
+                    "Invalid redirect action: '{}', expected 'follow', 'stop' or 'error'",
+                    other
+                ))),
+            }
+        })
+    }
+
     fn check_inner(&self) -> PyResult<()> {
         self.inner
             .as_ref()
@@ -461,7 +946,15 @@ impl ClientBuilder {
     }
 
     fn build(mut slf: PyRefMut<Self>, py: Python) -> PyResult<Py<Client>> {
-        Client::new_py(py, slf.as_super().build_client_base(py)?)
+        let slf_super = slf.as_super();
+        slf_super.check_http3()?;
+        slf_super.check_cookie_store()?;
+        slf_super.tls_config.check()?;
+        slf_super.inner_with_retry_middleware(py, "RetryMiddleware")?;
+        if let Some(sink) = slf_super.record_sink.take() {
+            slf_super.inner_with_middleware(Bound::new(py, RecordMiddleware::new(sink))?.into_any())?;
+        }
+        Client::new_py(py, slf_super.build_client_base(py)?)
     }
 
     fn with_middleware<'py>(
@@ -502,7 +995,15 @@ impl SyncClientBuilder {
     }
 
     fn build(mut slf: PyRefMut<Self>, py: Python) -> PyResult<Py<SyncClient>> {
-        SyncClient::new_py(py, slf.as_super().build_client_base(py)?)
+        let slf_super = slf.as_super();
+        slf_super.check_http3()?;
+        slf_super.check_cookie_store()?;
+        slf_super.tls_config.check()?;
+        slf_super.inner_with_retry_middleware(py, "SyncRetryMiddleware")?;
+        if let Some(sink) = slf_super.record_sink.take() {
+            slf_super.inner_with_middleware(Bound::new(py, SyncRecordMiddleware::new(sink))?.into_any())?;
+        }
+        SyncClient::new_py(py, slf_super.build_client_base(py)?)
     }
 
     fn with_middleware<'py>(
@@ -534,3 +1035,26 @@ impl SyncClientBuilder {
         Ok(slf)
     }
 }
+
+fn read_file(path: &Path) -> PyResult<Vec<u8>> {
+    std::fs::read(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => PyFileNotFoundError::new_err(format!("File not found: {}", path.display())),
+        _ => PyOSError::new_err(format!("Failed to read file {}: {}", path.display(), e)),
+    })
+}
+
+// Token as in RFC 9110 section 5.6.2
+fn is_http_token(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+// Comment content as in RFC 9110 section 5.6.5, without nested comments or escapes
+fn is_http_comment_text(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .bytes()
+            .all(|b| b == b' ' || b == b'\t' || (b.is_ascii_graphic() && !b"()\\".contains(&b)))
+}
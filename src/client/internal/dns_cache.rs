@@ -0,0 +1,63 @@
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// Caches DNS resolution results of the inner resolver for the given TTL
+#[derive(Clone)]
+pub struct DnsCache {
+    ttl: Duration,
+    inner: Arc<dyn Resolve>,
+    entries: Arc<Mutex<HashMap<String, CacheEntry>>>,
+}
+
+struct CacheEntry {
+    addrs: Vec<SocketAddr>,
+    expires_at: Instant,
+}
+
+impl DnsCache {
+    pub fn new(ttl: Duration, inner: Arc<dyn Resolve>) -> Self {
+        DnsCache {
+            ttl,
+            inner,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<Vec<SocketAddr>> {
+        let mut entries = self.entries.lock().ok()?;
+        match entries.get(name) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.addrs.clone()),
+            Some(_) => {
+                entries.remove(name);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, name: String, addrs: Vec<SocketAddr>) {
+        if let Ok(mut entries) = self.entries.lock() {
+            let expires_at = Instant::now() + self.ttl;
+            entries.retain(|_, entry| entry.expires_at > Instant::now());
+            entries.insert(name, CacheEntry { addrs, expires_at });
+        }
+    }
+}
+
+impl Resolve for DnsCache {
+    fn resolve(&self, name: Name) -> Resolving {
+        let this = self.clone();
+        Box::pin(async move {
+            let key = name.as_str().to_string();
+            if let Some(addrs) = this.get(&key) {
+                return Ok(Box::new(addrs.into_iter()) as Addrs);
+            }
+            let addrs: Vec<SocketAddr> = this.inner.resolve(name).await?.collect();
+            this.insert(key, addrs.clone());
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
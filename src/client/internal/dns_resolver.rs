@@ -0,0 +1,42 @@
+use crate::exceptions::BuilderError;
+use hickory_resolver::TokioResolver;
+use hickory_resolver::config::LookupIpStrategy;
+use pyo3::prelude::*;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+// Resolves using the system getaddrinfo, same as the reqwest default resolver
+pub struct GaiResolver;
+
+impl Resolve for GaiResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs = tokio::net::lookup_host((name.as_str().to_string(), 0)).await?;
+            Ok(Box::new(addrs) as Addrs)
+        })
+    }
+}
+
+// Resolves using nameservers, search domains and options read from the system resolv.conf
+pub struct ResolvConfResolver(Arc<TokioResolver>);
+
+impl ResolvConfResolver {
+    pub fn new() -> PyResult<Self> {
+        let mut builder = TokioResolver::builder_tokio()
+            .map_err(|e| BuilderError::from_err("Failed to read system DNS configuration", &e))?;
+        builder.options_mut().ip_strategy = LookupIpStrategy::Ipv4AndIpv6;
+        Ok(ResolvConfResolver(Arc::new(builder.build())))
+    }
+}
+
+impl Resolve for ResolvConfResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = self.0.clone();
+        Box::pin(async move {
+            let lookup = resolver.lookup_ip(name.as_str()).await?;
+            let addrs: Vec<SocketAddr> = lookup.into_iter().map(|ip| SocketAddr::new(ip, 0)).collect();
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
@@ -1,5 +1,17 @@
 mod connection_limiter;
+mod dns_cache;
+mod dns_resolver;
+mod request_compression;
+mod scoped_ip;
 mod spawner;
+mod timings;
+mod tls_config;
 
 pub use connection_limiter::ConnectionLimiter;
-pub use spawner::Spawner;
+pub use dns_cache::DnsCache;
+pub use dns_resolver::{GaiResolver, ResolvConfResolver};
+pub use request_compression::RequestCompression;
+pub use scoped_ip::{parse_scoped_ip, scope_interface};
+pub use spawner::{Spawner, SpawnerConfig};
+pub use timings::{Timings, TimingsConnectLayer, TimingsResolver};
+pub use tls_config::TlsConfig;
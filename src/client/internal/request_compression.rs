@@ -0,0 +1,82 @@
+use flate2::Compression;
+use flate2::write::{GzEncoder, ZlibEncoder};
+use http::HeaderValue;
+use http::header::{CONTENT_ENCODING, CONTENT_LENGTH};
+use pyo3::PyResult;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use std::io::Write;
+
+#[derive(Debug, Clone, Copy)]
+enum Algorithm {
+    Gzip,
+    Deflate,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RequestCompression {
+    algorithm: Algorithm,
+    min_size: usize,
+}
+
+impl RequestCompression {
+    pub fn new(algorithm: &str, min_size: usize) -> PyResult<Self> {
+        let algorithm = match algorithm {
+            "gzip" => Algorithm::Gzip,
+            "deflate" => Algorithm::Deflate,
+            _ => return Err(PyValueError::new_err(format!("Unsupported compression: {}", algorithm))),
+        };
+        Ok(Self { algorithm, min_size })
+    }
+
+    // Compresses in-memory bodies. Streamed and already encoded bodies are left as is.
+    pub async fn compress(&self, request: &mut reqwest::Request) -> PyResult<()> {
+        if request.headers().contains_key(CONTENT_ENCODING) {
+            return Ok(());
+        }
+        if request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .is_none_or(|body| body.len() < self.min_size)
+        {
+            return Ok(());
+        }
+        let Some(body) = request.body_mut().take() else {
+            return Ok(());
+        };
+
+        // Compressing a large body would otherwise stall all other requests running on the runtime
+        let algorithm = self.algorithm;
+        let (compressed, encoding) =
+            tokio::task::spawn_blocking(move || algorithm.encode(body.as_bytes().unwrap_or_default()))
+                .await
+                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?
+                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+        *request.body_mut() = Some(compressed.into());
+        let headers = request.headers_mut();
+        headers.remove(CONTENT_LENGTH);
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static(encoding));
+        Ok(())
+    }
+}
+
+impl Algorithm {
+    fn encode(self, body: &[u8]) -> std::io::Result<(Vec<u8>, &'static str)> {
+        match self {
+            Algorithm::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder
+                    .write_all(body)
+                    .and_then(|_| encoder.finish())
+                    .map(|v| (v, "gzip"))
+            }
+            Algorithm::Deflate => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder
+                    .write_all(body)
+                    .and_then(|_| encoder.finish())
+                    .map(|v| (v, "deflate"))
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::net::{IpAddr, SocketAddr, SocketAddrV6};
+use std::str::FromStr;
+
+// Parses an IP address with an optional IPv6 zone, e.g. "fe80::1%eth0" or "fe80::1%3".
+// The zone is kept as the scope id of the address, interface names are resolved to their index.
+pub fn parse_scoped_ip(value: &str) -> PyResult<SocketAddr> {
+    let (addr, zone) = match value.split_once('%') {
+        Some((addr, zone)) => (addr, Some(zone)),
+        None => (value, None),
+    };
+    let addr = IpAddr::from_str(addr).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    match (addr, zone) {
+        (_, None) => Ok(SocketAddr::new(addr, 0)),
+        (_, Some("")) => Err(PyValueError::new_err(format!("Empty zone in address: {}", value))),
+        (IpAddr::V4(_), Some(_)) => Err(PyValueError::new_err(format!("Zone is only supported for IPv6: {}", value))),
+        (IpAddr::V6(ip), Some(zone)) => {
+            let scope_id = match zone.parse::<u32>() {
+                Ok(scope_id) => scope_id,
+                Err(_) => interface_index(zone)?,
+            };
+            Ok(SocketAddr::V6(SocketAddrV6::new(ip, 0, 0, scope_id)))
+        }
+    }
+}
+
+// Interface of the scope id. Sockets are bound to it as the connector binds the local address without a scope id.
+// :NOCOV_START
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub fn scope_interface(scope_id: u32) -> PyResult<String> {
+    let mut buf = [0 as libc::c_char; libc::IF_NAMESIZE];
+    // SAFETY: buf has the IF_NAMESIZE capacity required by if_indextoname
+    let name = unsafe { libc::if_indextoname(scope_id, buf.as_mut_ptr()) };
+    if name.is_null() {
+        return Err(PyValueError::new_err(format!("Unknown interface index in zone: {}", scope_id)));
+    }
+    // SAFETY: on success the name is a nul-terminated string in buf
+    Ok(unsafe { std::ffi::CStr::from_ptr(name) }.to_string_lossy().into_owned())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn scope_interface(_scope_id: u32) -> PyResult<String> {
+    Err(PyValueError::new_err("Zone in address is not supported on this platform"))
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn interface_index(name: &str) -> PyResult<u32> {
+    let c_name = std::ffi::CString::new(name).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    // SAFETY: c_name is a valid nul-terminated string
+    match unsafe { libc::if_nametoindex(c_name.as_ptr()) } {
+        0 => Err(PyValueError::new_err(format!("Unknown interface in zone: {}", name))),
+        index => Ok(index),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn interface_index(_name: &str) -> PyResult<u32> {
+    Err(PyValueError::new_err("Zone in address is not supported on this platform"))
+} // :NOCOV_END
@@ -1,11 +1,17 @@
-use crate::client::internal::ConnectionLimiter;
+use crate::client::internal::{ConnectionLimiter, RequestCompression, Timings};
 use crate::client::runtime;
-use crate::exceptions::utils::map_send_error;
-use crate::exceptions::{ClientClosedError, PoolTimeoutError};
+use crate::exceptions::utils::{
+    RequestContext, add_request_context, is_incomplete_message_error, map_read_error, map_send_error,
+};
+use crate::exceptions::{ClientClosedError, PoolTimeoutError, ReadError};
 use crate::request::RequestData;
 use crate::response::BaseResponse;
+use crate::response::internal::BodyConsumeConfig;
+use http_body_util::BodyExt;
 use pyo3::coroutine::CancelHandle;
 use pyo3::prelude::*;
+use std::hash::{BuildHasher, Hasher, RandomState};
+use std::time::Duration;
 use tokio::sync::OwnedSemaphorePermit;
 use tokio_util::sync::CancellationToken;
 
@@ -13,19 +19,31 @@ pub struct Spawner {
     client: reqwest::Client,
     runtime: runtime::RuntimeHandle,
     connection_limiter: Option<ConnectionLimiter>,
+    config: SpawnerConfig,
     close_cancellation: CancellationToken,
 }
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpawnerConfig {
+    pub request_compression: Option<RequestCompression>,
+    pub retry_on_incomplete_message: bool,
+    pub request_jitter: Option<Duration>,
+    pub collect_timings: bool,
+    pub max_response_header_size: Option<usize>,
+}
 impl Spawner {
     pub fn new(
         client: reqwest::Client,
         runtime: runtime::RuntimeHandle,
         connection_limiter: Option<ConnectionLimiter>,
+        config: SpawnerConfig,
         close_cancellation: CancellationToken,
     ) -> Self {
         Self {
             client,
             runtime,
             connection_limiter,
+            config,
             close_cancellation,
         }
     }
@@ -35,14 +53,37 @@ impl Spawner {
         let client = spawner.client.clone();
         let connection_limiter = spawner.connection_limiter.clone();
         let runtime = spawner.runtime.clone();
+        let config = spawner.config;
 
         let fut = async move {
+            if let Some(request_compression) = config.request_compression {
+                request_compression.compress(&mut request.reqwest).await?;
+            }
+
+            if let Some(max_delay) = config.request_jitter {
+                tokio::time::sleep(Self::jitter(max_delay)).await;
+            }
+
             let permit = match connection_limiter.as_ref() {
                 Some(lim) => Some(Self::limit_connections(lim, &mut request.reqwest).await?),
                 _ => None,
             };
 
-            let mut resp = client.execute(request.reqwest).await.map_err(map_send_error)?;
+            let ctx = RequestContext::new(request.reqwest.method(), request.reqwest.url());
+            let buffer_body = matches!(request.body_consume_config, BodyConsumeConfig::FullyConsumed);
+            let execute = Self::execute(&client, request.reqwest, config, buffer_body);
+            let mut resp = if config.collect_timings {
+                let (resp, timings) = Timings::record(execute).await;
+                let mut resp = resp.map_err(|e| e.into_py_err(&ctx))?;
+                resp.extensions_mut().insert(timings);
+                resp
+            } else {
+                execute.await.map_err(|e| e.into_py_err(&ctx))?
+            };
+
+            if let Some(max_size) = config.max_response_header_size {
+                Self::check_header_size(&resp, max_size, &ctx)?;
+            }
 
             if let Some(extensions) = request.extensions {
                 resp.extensions_mut().insert(extensions);
@@ -55,6 +96,8 @@ impl Spawner {
                 runtime,
                 request.json_handler,
                 request.error_for_status,
+                request.error_for_json,
+                Some(ctx),
             )
             .await
         };
@@ -76,6 +119,60 @@ impl Spawner {
         rt.blocking_spawn(Self::spawn_reqwest_inner(request, CancelHandle::new()))
     }
 
+    async fn execute(
+        client: &reqwest::Client,
+        request: reqwest::Request,
+        config: SpawnerConfig,
+        buffer_body: bool,
+    ) -> Result<reqwest::Response, ExecuteError> {
+        let retry_request = (config.retry_on_incomplete_message && request.method().is_idempotent())
+            .then(|| request.try_clone())
+            .flatten();
+        let Some(retry_request) = retry_request else {
+            return Self::send(client, request).await;
+        };
+
+        // Body is buffered so that a connection dropped in the middle of the response can be retried
+        match Self::execute_buffered(client, request, buffer_body).await {
+            Err(err) if err.is_incomplete_message() => Self::execute_buffered(client, retry_request, buffer_body).await,
+            res => res,
+        }
+    }
+
+    async fn execute_buffered(
+        client: &reqwest::Client,
+        request: reqwest::Request,
+        buffer_body: bool,
+    ) -> Result<reqwest::Response, ExecuteError> {
+        let resp = Self::send(client, request).await?;
+        if !buffer_body {
+            return Ok(resp);
+        }
+        let (head, body) = http::Response::<reqwest::Body>::from(resp).into_parts();
+        let body = body.collect().await.map_err(ExecuteError::Read)?.to_bytes();
+        Ok(http::Response::from_parts(head, body).into())
+    }
+
+    async fn send(client: &reqwest::Client, request: reqwest::Request) -> Result<reqwest::Response, ExecuteError> {
+        let resp = client.execute(request).await.map_err(ExecuteError::Send)?;
+        Timings::record_ttfb();
+        Ok(resp)
+    }
+
+    fn check_header_size(resp: &reqwest::Response, max_size: usize, ctx: &RequestContext) -> PyResult<()> {
+        let size: usize = resp.headers().iter().map(|(k, v)| k.as_str().len() + v.len()).sum();
+        if size > max_size {
+            let msg = format!("response headers too large: {} bytes exceeds the limit of {} bytes", size, max_size);
+            return Err(add_request_context(ReadError::from_causes(&msg, vec![]), ctx));
+        }
+        Ok(())
+    }
+
+    fn jitter(max_delay: Duration) -> Duration {
+        let random = RandomState::new().build_hasher().finish(); // Randomly seeded, good enough for jitter
+        max_delay.mul_f64(random as f64 / u64::MAX as f64)
+    }
+
     async fn limit_connections(
         connection_limiter: &ConnectionLimiter,
         request: &mut reqwest::Request,
@@ -102,7 +199,27 @@ impl Clone for Spawner {
             client: self.client.clone(),
             runtime: self.runtime.clone(),
             connection_limiter: self.connection_limiter.clone(),
+            config: self.config,
             close_cancellation: self.close_cancellation.child_token(),
         }
     }
 }
+
+enum ExecuteError {
+    Send(reqwest::Error),
+    Read(reqwest::Error),
+}
+impl ExecuteError {
+    fn is_incomplete_message(&self) -> bool {
+        match self {
+            ExecuteError::Send(e) | ExecuteError::Read(e) => is_incomplete_message_error(e),
+        }
+    }
+
+    fn into_py_err(self, ctx: &RequestContext) -> PyErr {
+        match self {
+            ExecuteError::Send(e) => map_send_error(e, ctx),
+            ExecuteError::Read(e) => map_read_error(e, Some(ctx)),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use reqwest::dns::{Name, Resolve, Resolving};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower_layer::Layer;
+use tower_service::Service;
+
+tokio::task_local! {
+    static RECORDER: TimingsRecorder;
+}
+
+// Elapsed times from the request start until each phase finished, like curl's time_namelookup, time_connect etc.
+// DNS and connect are only recorded when a new connection was established for the request.
+#[derive(Clone, Copy, Default)]
+pub struct Timings {
+    pub dns: Option<Duration>,
+    pub connect: Option<Duration>,
+    pub ttfb: Option<Duration>,
+    pub total: Option<Duration>,
+    start: Option<Instant>,
+}
+
+#[derive(Clone)]
+struct TimingsRecorder {
+    start: Instant,
+    timings: Arc<Mutex<Timings>>,
+}
+
+impl Timings {
+    // Runs the request future while the resolver and connector wrappers record the phases into the task local
+    pub async fn record<F: Future>(fut: F) -> (F::Output, Timings) {
+        let start = Instant::now();
+        let recorder = TimingsRecorder {
+            start,
+            timings: Arc::new(Mutex::new(Timings::default())),
+        };
+        let res = RECORDER.scope(recorder.clone(), fut).await;
+        let mut timings = recorder.timings.lock().map(|t| *t).unwrap_or_default();
+        timings.start = Some(start);
+        (res, timings)
+    }
+
+    // Called when the response headers were received, the body might still be buffered afterwards
+    pub fn record_ttfb() {
+        Self::record_phase(|timings, elapsed| timings.ttfb = Some(elapsed));
+    }
+
+    pub fn finish(&mut self) {
+        self.total = self.start.map(|start| start.elapsed());
+    }
+
+    fn record_phase(set: impl FnOnce(&mut Timings, Duration)) {
+        // Connecting might be continued in a background task when a pooled connection became available
+        let _ = RECORDER.try_with(|recorder| {
+            if let Ok(mut timings) = recorder.timings.lock() {
+                set(&mut timings, recorder.start.elapsed());
+            }
+        });
+    }
+}
+
+// Records when the DNS resolution of the inner resolver finished
+pub struct TimingsResolver(pub Arc<dyn Resolve>);
+
+impl Resolve for TimingsResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let fut = self.0.resolve(name);
+        Box::pin(async move {
+            let res = fut.await;
+            Timings::record_phase(|timings, elapsed| timings.dns = Some(elapsed));
+            res
+        })
+    }
+}
+
+// Records when the connection (TCP connect and TLS handshake) was established
+#[derive(Clone)]
+pub struct TimingsConnectLayer;
+
+impl<S> Layer<S> for TimingsConnectLayer {
+    type Service = TimingsConnect<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TimingsConnect(inner)
+    }
+}
+
+#[derive(Clone)]
+pub struct TimingsConnect<S>(S);
+
+impl<S, R> Service<R> for TimingsConnect<S>
+where
+    S: Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), S::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        let fut = self.0.call(req);
+        Box::pin(async move {
+            let res = fut.await;
+            if res.is_ok() {
+                Timings::record_phase(|timings, elapsed| timings.connect = Some(elapsed));
+            }
+            res
+        })
+    }
+}
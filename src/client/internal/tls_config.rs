@@ -0,0 +1,257 @@
+use crate::exceptions::BuilderError;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rustls::client::WebPkiServerVerifier;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{CryptoProvider, WebPkiSupportedAlgorithms, verify_tls12_signature, verify_tls13_signature};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, CertificateRevocationListDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{CertificateError, ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use std::net::IpAddr;
+use std::sync::Arc;
+
+// Mirrors the TLS options given to reqwest. reqwest does not allow a custom certificate verifier so the rustls config
+// is built here as reqwest would build it, when the verifier is needed.
+pub struct TlsConfig {
+    root_certs: Vec<CertificateDer<'static>>,
+    crls: Vec<CertificateRevocationListDer<'static>>,
+    built_in_root_certs: bool,
+    identity_pem: Option<Vec<u8>>,
+    min_version: Option<reqwest::tls::Version>,
+    max_version: Option<reqwest::tls::Version>,
+    sni: bool,
+    alpn_protocols: Vec<Vec<u8>>,
+    pub accept_invalid_certs: bool,
+    pub accept_invalid_hostnames: bool,
+    pub accept_invalid_certs_for: Vec<String>,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        TlsConfig {
+            root_certs: vec![],
+            crls: vec![],
+            built_in_root_certs: true,
+            identity_pem: None,
+            min_version: None,
+            max_version: None,
+            sni: true,
+            alpn_protocols: vec![b"h2".to_vec(), b"http/1.1".to_vec()],
+            accept_invalid_certs: false,
+            accept_invalid_hostnames: false,
+            accept_invalid_certs_for: vec![],
+        }
+    }
+}
+
+impl TlsConfig {
+    pub fn add_root_certificate_der(&mut self, der: &[u8]) {
+        self.root_certs.push(CertificateDer::from(der.to_vec()));
+    }
+
+    pub fn add_root_certificate_pem(&mut self, pem: &[u8]) -> PyResult<()> {
+        for cert in CertificateDer::pem_slice_iter(pem) {
+            self.root_certs
+                .push(cert.map_err(|e| PyValueError::new_err(e.to_string()))?);
+        }
+        Ok(())
+    }
+
+    pub fn add_crl_pem(&mut self, pem: &[u8]) -> PyResult<()> {
+        for crl in CertificateRevocationListDer::pem_slice_iter(pem) {
+            self.crls.push(crl.map_err(|e| PyValueError::new_err(e.to_string()))?);
+        }
+        Ok(())
+    }
+
+    pub fn set_built_in_root_certs(&mut self, enable: bool) {
+        self.built_in_root_certs = enable;
+    }
+
+    pub fn set_identity_pem(&mut self, pem: Vec<u8>) {
+        self.identity_pem = Some(pem);
+    }
+
+    pub fn set_min_version(&mut self, version: reqwest::tls::Version) {
+        self.min_version = Some(version);
+    }
+
+    pub fn set_max_version(&mut self, version: reqwest::tls::Version) {
+        self.max_version = Some(version);
+    }
+
+    pub fn set_sni(&mut self, enable: bool) {
+        self.sni = enable;
+    }
+
+    pub fn set_alpn_protocol(&mut self, protocol: &[u8]) {
+        self.alpn_protocols = vec![protocol.to_vec()];
+    }
+
+    // Checked before anything is consumed from the builder so it stays usable after the error
+    pub fn check(&self) -> PyResult<()> {
+        if !self.accept_invalid_certs_for.is_empty() && self.accept_invalid_hostnames {
+            return Err(PyValueError::new_err(
+                "danger_accept_invalid_certs_for can not be combined with danger_accept_invalid_hostnames",
+            ));
+        }
+        Ok(())
+    }
+
+    // Config for reqwest use_preconfigured_tls, None when reqwest can build the config itself
+    pub fn custom_verifier_config(&mut self) -> PyResult<Option<ClientConfig>> {
+        if self.accept_invalid_certs_for.is_empty() || self.accept_invalid_certs {
+            return Ok(None);
+        }
+
+        let provider = CryptoProvider::get_default()
+            .cloned()
+            .unwrap_or_else(|| Arc::new(rustls::crypto::ring::default_provider()));
+
+        let mut root_store = RootCertStore::empty();
+        for cert in self.root_certs.drain(..) {
+            root_store.add(cert).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        }
+        if self.built_in_root_certs {
+            // Native stores often include invalid certificates, those are skipped like in reqwest
+            root_store.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+        }
+
+        // Without any trust anchors only the listed hosts can be connected to
+        let inner = if root_store.is_empty() {
+            None
+        } else {
+            let verifier = WebPkiServerVerifier::builder_with_provider(Arc::new(root_store), provider.clone())
+                .with_crls(self.crls.drain(..))
+                .build()
+                .map_err(|e| BuilderError::from_err("invalid TLS verification settings", &e))?;
+            Some(verifier)
+        };
+        let verifier = AcceptInvalidCertsFor::new(
+            &self.accept_invalid_certs_for,
+            inner,
+            provider.signature_verification_algorithms,
+        );
+
+        let versions = self.protocol_versions();
+        if versions.is_empty() {
+            return Err(BuilderError::from_causes("empty supported tls versions", vec![]));
+        }
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_protocol_versions(&versions)
+            .map_err(|e| BuilderError::from_err("invalid TLS versions", &e))?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(verifier));
+
+        let mut config = match self.identity_pem.take() {
+            Some(pem) => {
+                let certs = CertificateDer::pem_slice_iter(&pem)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| PyValueError::new_err(e.to_string()))?;
+                let key = PrivateKeyDer::from_pem_slice(&pem).map_err(|e| PyValueError::new_err(e.to_string()))?;
+                builder
+                    .with_client_auth_cert(certs, key)
+                    .map_err(|e| BuilderError::from_err("invalid TLS identity", &e))?
+            }
+            None => builder.with_no_client_auth(),
+        };
+        config.enable_sni = self.sni;
+        config.alpn_protocols = std::mem::take(&mut self.alpn_protocols);
+        Ok(Some(config))
+    }
+
+    fn protocol_versions(&self) -> Vec<&'static rustls::SupportedProtocolVersion> {
+        rustls::ALL_VERSIONS
+            .iter()
+            .copied()
+            .filter(|supported| {
+                let version = match supported.version {
+                    rustls::ProtocolVersion::TLSv1_2 => reqwest::tls::Version::TLS_1_2,
+                    _ => reqwest::tls::Version::TLS_1_3,
+                };
+                self.min_version.is_none_or(|min| version >= min) && self.max_version.is_none_or(|max| version <= max)
+            })
+            .collect()
+    }
+}
+
+// Skips the certificate verification for the listed hosts, other hosts are verified by the inner verifier.
+// Handshake signatures are always verified against the presented certificate.
+#[derive(Debug)]
+struct AcceptInvalidCertsFor {
+    names: Vec<String>,
+    ips: Vec<IpAddr>,
+    inner: Option<Arc<WebPkiServerVerifier>>,
+    algorithms: WebPkiSupportedAlgorithms,
+}
+
+impl AcceptInvalidCertsFor {
+    fn new(hosts: &[String], inner: Option<Arc<WebPkiServerVerifier>>, algorithms: WebPkiSupportedAlgorithms) -> Self {
+        let (mut names, mut ips) = (vec![], vec![]);
+        for host in hosts {
+            let host = host.trim_start_matches('[').trim_end_matches(']');
+            match host.parse::<IpAddr>() {
+                Ok(ip) => ips.push(ip),
+                Err(_) => names.push(host.trim_end_matches('.').to_ascii_lowercase()),
+            }
+        }
+        AcceptInvalidCertsFor {
+            names,
+            ips,
+            inner,
+            algorithms,
+        }
+    }
+
+    fn accepts(&self, server_name: &ServerName) -> bool {
+        match server_name {
+            ServerName::DnsName(name) => {
+                let name = name.as_ref().trim_end_matches('.');
+                self.names.iter().any(|host| host.eq_ignore_ascii_case(name))
+            }
+            ServerName::IpAddress(ip) => self.ips.contains(&IpAddr::from(*ip)),
+            _ => false,
+        }
+    }
+}
+
+impl ServerCertVerifier for AcceptInvalidCertsFor {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if self.accepts(server_name) {
+            return Ok(ServerCertVerified::assertion());
+        }
+        match &self.inner {
+            Some(inner) => inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now),
+            None => Err(rustls::Error::InvalidCertificate(CertificateError::UnknownIssuer)),
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
@@ -24,6 +24,19 @@ impl CookieStore {
             .collect())
     }
 
+    fn get_request_cookies(&self, url: UrlType) -> PyResult<Vec<Cookie>> {
+        Ok(self
+            .lock_read()?
+            .get_request_values(&url.0)
+            .map(|(name, value)| Cookie(cookie::Cookie::new(name.to_string(), value.to_string())))
+            .collect())
+    }
+
+    fn cookie_header(&self, url: UrlType) -> PyResult<Option<String>> {
+        let store = self.lock_read()?;
+        Ok(Self::cookie_header_inner(&store, &url.0))
+    }
+
     fn contains(&self, domain: &str, path: &str, name: &str) -> PyResult<bool> {
         Ok(self.lock_read()?.contains(domain, path, name))
     }
@@ -56,6 +69,14 @@ impl CookieStore {
         Ok(())
     }
 
+    fn merge(&self, other: &Bound<CookieStore>) -> PyResult<()> {
+        let other_cookies: Vec<_> = other.get().lock_read()?.iter_unexpired().cloned().collect();
+        let mut store = self.lock_write()?;
+        let cookies = store.iter_any().cloned().chain(other_cookies).map(Ok::<_, PyErr>);
+        *store = cookie_store::CookieStore::from_cookies(cookies, true)?; // Later cookies override on conflict
+        Ok(())
+    }
+
     fn get_all_unexpired(&self) -> PyResult<Vec<Cookie>> {
         Ok(self.lock_read()?.iter_unexpired().map(Cookie::from).collect())
     }
@@ -65,6 +86,28 @@ impl CookieStore {
     }
 }
 impl CookieStore {
+    pub fn with_cookies(cookies: Vec<CookieType>, request_url: &url::Url) -> PyResult<Self> {
+        let store = Self::new();
+        {
+            let mut inner = store.lock_write()?;
+            for cookie in cookies {
+                inner
+                    .insert_raw(&cookie.0, request_url)
+                    .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            }
+        }
+        Ok(store)
+    }
+
+    fn cookie_header_inner(store: &cookie_store::CookieStore, url: &url::Url) -> Option<String> {
+        let cookies_str = store
+            .get_request_values(url)
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        (!cookies_str.is_empty()).then_some(cookies_str)
+    }
+
     fn lock_read(&self) -> PyResult<std::sync::RwLockReadGuard<'_, cookie_store::CookieStore>> {
         self.0.read().map_err(|e| PyValueError::new_err(e.to_string()))
     }
@@ -96,16 +139,8 @@ impl reqwest::cookie::CookieStore for CookieStorePyProxy {
         let cookies_str = {
             #[allow(clippy::unwrap_used)] // Trait does not allow returning a Result
             let store = self.0.get().0.read().unwrap();
-            store
-                .get_request_values(url)
-                .map(|(name, value)| format!("{}={}", name, value))
-                .collect::<Vec<_>>()
-                .join("; ")
+            CookieStore::cookie_header_inner(&store, url)?
         };
-
-        if cookies_str.is_empty() {
-            return None;
-        }
         http::HeaderValue::from_maybe_shared(Bytes::from(cookies_str)).ok()
     }
 }
@@ -51,6 +51,7 @@ macro_rules! define_exception {
 define_exception!(RequestError);
 
 define_exception!(StatusError);
+define_exception!(ApiError);
 define_exception!(RedirectError);
 
 define_exception!(BodyDecodeError);
@@ -3,26 +3,55 @@ use crate::exceptions::exceptions::{
     BuilderError, ConnectError, ConnectTimeoutError, DecodeError, ReadError, ReadTimeoutError, RedirectError,
     RequestError, WriteError, WriteTimeoutError,
 };
-use pyo3::{PyErr, Python};
+use bytes::Bytes;
+use pyo3::intern;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_bytes::PyBytes;
 use regex::RegexSet;
 use std::error::Error;
 use std::sync::LazyLock;
 
-pub fn map_send_error(e: reqwest::Error) -> PyErr {
-    inner_map_io_error(e, ErrorKind::Send)
+// Method and URL of the request, added to the details of mapped errors
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    method: http::Method,
+    url: url::Url,
+}
+impl RequestContext {
+    pub fn new(method: &http::Method, url: &url::Url) -> Self {
+        let mut url = url.clone();
+        let _ = url.set_password(None); // Do not leak credentials into error details
+        RequestContext {
+            method: method.clone(),
+            url,
+        }
+    }
 }
 
-pub fn map_read_error(e: reqwest::Error) -> PyErr {
-    inner_map_io_error(e, ErrorKind::Read)
+pub fn map_send_error(e: reqwest::Error, ctx: &RequestContext) -> PyErr {
+    inner_map_io_error(e, ErrorKind::Send, Some(ctx))
 }
 
-fn inner_map_io_error(e: reqwest::Error, kind: ErrorKind) -> PyErr {
+pub fn map_read_error(e: reqwest::Error, ctx: Option<&RequestContext>) -> PyErr {
+    inner_map_io_error(e, ErrorKind::Read, ctx)
+}
+
+fn inner_map_io_error(e: reqwest::Error, kind: ErrorKind, ctx: Option<&RequestContext>) -> PyErr {
     if let Some(py_err) = inner_py_err(&e) {
         return py_err;
     }
-    let causes = error_causes_iter(&e).collect::<Vec<_>>();
-    if is_timeout_error(&e) {
-        if is_body_error(&e) {
+    let err = add_h2_error_code(inner_map_reqwest_error(&e, kind), &e);
+    match ctx {
+        Some(ctx) => add_request_context(err, ctx),
+        None => err,
+    }
+}
+
+fn inner_map_reqwest_error(e: &reqwest::Error, kind: ErrorKind) -> PyErr {
+    let causes = error_causes_iter(e).collect::<Vec<_>>();
+    if is_timeout_error(e) {
+        if is_body_error(e) {
             match kind {
                 ErrorKind::Send => WriteTimeoutError::from_causes("request body timeout", causes),
                 ErrorKind::Read => ReadTimeoutError::from_causes("response body timeout", causes),
@@ -30,8 +59,8 @@ fn inner_map_io_error(e: reqwest::Error, kind: ErrorKind) -> PyErr {
         } else {
             ConnectTimeoutError::from_causes("connection timeout", causes)
         }
-    } else if is_connect_error(&e) {
-        if is_body_error(&e) {
+    } else if is_connect_error(e) {
+        if is_body_error(e) {
             match kind {
                 ErrorKind::Send => WriteError::from_causes("request body connection error", causes),
                 ErrorKind::Read => ReadError::from_causes("response body connection error", causes),
@@ -39,8 +68,8 @@ fn inner_map_io_error(e: reqwest::Error, kind: ErrorKind) -> PyErr {
         } else {
             ConnectError::from_causes("connection error", causes)
         }
-    } else if is_decode_error(&e) {
-        if is_body_error(&e) {
+    } else if is_decode_error(e) {
+        if is_body_error(e) {
             BodyDecodeError::from_causes("error decoding body", causes)
         } else {
             DecodeError::from_causes("error decoding response", causes)
@@ -50,10 +79,47 @@ fn inner_map_io_error(e: reqwest::Error, kind: ErrorKind) -> PyErr {
     } else if e.is_builder() {
         BuilderError::from_causes("builder error", causes)
     } else {
-        RequestError::from_err("error sending request", &e)
+        RequestError::from_err("error sending request", e)
     }
 }
 
+pub fn add_request_context(err: PyErr, ctx: &RequestContext) -> PyErr {
+    Python::attach(|py| {
+        if let Ok(details) = err.value(py).getattr(intern!(py, "details"))
+            && let Ok(details) = details.downcast::<PyDict>()
+        {
+            let _ = details.set_item(intern!(py, "method"), ctx.method.as_str());
+            let _ = details.set_item(intern!(py, "url"), ctx.url.as_str());
+        }
+    });
+    err
+}
+
+// HTTP/2 error code of a reset stream (RST_STREAM) or a closed connection (GOAWAY)
+fn add_h2_error_code(err: PyErr, e: &reqwest::Error) -> PyErr {
+    let Some(reason) = error_causes_iter(e).find_map(|e| e.downcast_ref::<h2::Error>().and_then(|e| e.reason())) else {
+        return err;
+    };
+    Python::attach(|py| {
+        if let Ok(details) = err.value(py).getattr(intern!(py, "details"))
+            && let Ok(details) = details.downcast::<PyDict>()
+        {
+            let _ = details.set_item(intern!(py, "h2_error_code"), u32::from(reason));
+        }
+    });
+    err
+}
+
+// Attach the body bytes received before the failure to the raised error
+pub fn add_partial_body(err: PyErr, body: Bytes) -> PyErr {
+    Python::attach(|py| {
+        if err.is_instance_of::<RequestError>(py) {
+            let _ = err.value(py).setattr(intern!(py, "partial_body"), PyBytes::new(body));
+        }
+    });
+    err
+}
+
 #[derive(PartialEq, Debug)]
 enum ErrorKind {
     Send,
@@ -86,6 +152,14 @@ fn is_connect_error(err: &reqwest::Error) -> bool {
     err.is_connect() || error_causes_matches(err, &CONNECTION_ERROR_PATTERN)
 }
 
+// Connection was dropped before the full response was received (e.g. a stale pooled connection)
+pub fn is_incomplete_message_error(err: &reqwest::Error) -> bool {
+    error_causes_iter(err).any(|e| {
+        e.downcast_ref::<hyper::Error>()
+            .is_some_and(|e| e.is_incomplete_message())
+    }) || (is_decode_error(err) && error_causes_matches(err, &INCOMPLETE_MESSAGE_PATTERN))
+}
+
 fn is_decode_error(err: &reqwest::Error) -> bool {
     for e in error_causes_iter(err) {
         if e.downcast_ref::<reqwest::Error>().is_some_and(|e| e.is_decode())
@@ -135,6 +209,16 @@ static CONNECTION_ERROR_PATTERN: LazyLock<RegexSet> = LazyLock::new(|| {
     .expect("invalid connection error regex")
 });
 
+static INCOMPLETE_MESSAGE_PATTERN: LazyLock<RegexSet> = LazyLock::new(|| {
+    #[allow(clippy::expect_used)]
+    RegexSet::new([
+        r"(?i)unexpected end of file",
+        r"(?i)unexpected eof",
+        r"(?i)end of file before message length reached",
+    ])
+    .expect("invalid incomplete message regex")
+});
+
 static TIMEOUT_ERROR_PATTERN: LazyLock<RegexSet> = LazyLock::new(|| {
     #[allow(clippy::expect_used)]
     RegexSet::new([r"(?i)timed out", r"(?i)timeout"]).expect("invalid timeout error regex")
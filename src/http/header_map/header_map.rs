@@ -3,11 +3,11 @@ use crate::http::header_map::views::{HeaderMapItemsView, HeaderMapKeysView, Head
 use crate::internal::types::{HeaderName, HeaderValue};
 use crate::internal::utils::{KeyValPairs, ellipsis};
 use http::header::Entry;
-use pyo3::exceptions::{PyKeyError, PyRuntimeError};
+use pyo3::exceptions::{PyKeyError, PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyEllipsis, PyList, PyString};
 use pyo3::{IntoPyObjectExt, intern};
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::sync::{Arc, Mutex};
 
 type Inner = Option<http::HeaderMap>;
@@ -125,6 +125,7 @@ impl HeaderMap {
     fn popitem<'py>(&self, py: Python<'py>) -> PyResult<(HeaderName, Bound<'py, PyAny>)> {
         self.mut_map(|map| {
             let key = match map.iter().next() {
+                // First key in iteration order. Its values are popped in order
                 Some((key, _)) => Ok(key.clone()),
                 None => Err(PyKeyError::new_err("popitem(): HeaderMap is empty")),
             }?;
@@ -185,6 +186,18 @@ impl HeaderMap {
         self.get_all(key)
     }
 
+    fn get_all_str(&self, key: &str) -> PyResult<Vec<String>> {
+        self.ref_map(|map| map.get_all(key).into_iter().map(Self::decode_utf8).collect())
+    }
+
+    #[pyo3(signature = (key, default=None))]
+    fn get_str(&self, key: &str, default: Option<String>) -> PyResult<Option<String>> {
+        self.ref_map(|map| match map.get(key) {
+            Some(v) => Self::decode_utf8(v).map(Some),
+            None => Ok(default),
+        })
+    }
+
     #[pyo3(signature = (key, value, *, is_sensitive=false))]
     fn insert(&self, key: HeaderName, value: HeaderValue, is_sensitive: bool) -> PyResult<Vec<HeaderValue>> {
         let mut value = value.0;
@@ -211,10 +224,56 @@ impl HeaderMap {
         })
     }
 
+    #[pyo3(signature = (key, value, *, is_sensitive=false))]
+    fn setdefault_append(&self, key: HeaderName, value: HeaderValue, is_sensitive: bool) -> PyResult<Vec<HeaderValue>> {
+        let mut value = value.0;
+        value.set_sensitive(is_sensitive);
+
+        self.mut_map(|map| {
+            let existing: Vec<HeaderValue> = map.get_all(&key.0).iter().cloned().map(HeaderValue).collect();
+            if !existing.is_empty() {
+                return Ok(existing);
+            }
+            map.try_append(key.0, value.clone())
+                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+            Ok(vec![HeaderValue(value)])
+        })
+    }
+
+    fn replace_all(&self, key: HeaderName, values: Vec<HeaderValue>) -> PyResult<Vec<HeaderValue>> {
+        self.mut_map(|map| {
+            let removed = match map.try_entry(&key.0) {
+                Ok(Entry::Occupied(entry)) => entry.remove_entry_mult().1.map(HeaderValue).collect(),
+                Ok(Entry::Vacant(_)) => vec![],
+                Err(e) => return Err(PyRuntimeError::new_err(e.to_string())),
+            };
+            for value in values {
+                map.try_append(&key.0, value.0)
+                    .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+            }
+            Ok(removed)
+        })
+    }
+
     fn extend(&self, other: KeyValPairs) -> PyResult<()> {
         self.mut_map(|map| HeaderMap::extend_inner(map, other))
     }
 
+    fn update_if_absent(&self, other: KeyValPairs) -> PyResult<()> {
+        self.mut_map(|map| {
+            let mut added = HashSet::new();
+            other.for_each(|(k, v): (HeaderName, HeaderValue)| {
+                if map.contains_key(&k.0) && !added.contains(&k.0) {
+                    return Ok(());
+                }
+                map.try_append(&k.0, v.0)
+                    .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+                added.insert(k.0);
+                Ok(())
+            })
+        })
+    }
+
     #[pyo3(signature = (key, default=PopArg::NotPresent(ellipsis())))]
     fn popall<'py>(&self, py: Python<'py>, key: &str, default: PopArg<'py>) -> PyResult<Bound<'py, PyAny>> {
         let key = match http::HeaderName::try_from(key) {
@@ -234,8 +293,42 @@ impl HeaderMap {
         })
     }
 
-    fn dict_multi_value<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
-        self.ref_map(|map| Self::dict_multi_value_inner(map, py, false))
+    #[pyo3(signature = (*, hide_sensitive=false))]
+    fn dict_multi_value<'py>(&self, py: Python<'py>, hide_sensitive: bool) -> PyResult<Bound<'py, PyDict>> {
+        self.ref_map(|map| Self::dict_multi_value_inner(map, py, hide_sensitive))
+    }
+
+    fn is_sensitive(&self, key: &str) -> PyResult<bool> {
+        self.ref_map(|map| Ok(map.get_all(key).iter().any(|v| v.is_sensitive())))
+    }
+
+    fn sorted_items(&self) -> PyResult<Vec<(HeaderName, HeaderValue)>> {
+        self.ref_map(|map| {
+            let mut items: Vec<_> = map.iter().collect();
+            items.sort_by(|(k1, v1), (k2, v2)| {
+                k1.as_str()
+                    .cmp(k2.as_str())
+                    .then_with(|| v1.as_bytes().cmp(v2.as_bytes()))
+            });
+            Ok(items
+                .into_iter()
+                .map(|(k, v)| (HeaderName(k.clone()), HeaderValue(v.clone())))
+                .collect())
+        })
+    }
+
+    fn filter(&self, predicate: Bound<PyAny>) -> PyResult<Self> {
+        let map = self.try_clone_inner()?; // Predicate may access this map
+        let mut filtered = http::HeaderMap::with_capacity(map.len());
+        for (key, value) in map.iter() {
+            if predicate
+                .call1((HeaderName(key.clone()), HeaderValue(value.clone())))?
+                .is_truthy()?
+            {
+                filtered.append(key.clone(), value.clone());
+            }
+        }
+        Ok(HeaderMap::from(filtered))
     }
 
     fn copy(&self) -> PyResult<Self> {
@@ -318,6 +411,12 @@ impl HeaderMap {
         })
     }
 
+    // Unlike str conversion of the values, allows non-ASCII UTF-8
+    fn decode_utf8(value: &http::HeaderValue) -> PyResult<String> {
+        String::from_utf8(value.as_bytes().to_vec())
+            .map_err(|e| PyValueError::new_err(format!("Header value is not valid UTF-8: {}", e)))
+    }
+
     fn pop_inner<'py>(
         map: &mut http::HeaderMap,
         py: Python<'py>,
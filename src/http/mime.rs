@@ -37,7 +37,7 @@ impl Mime {
     }
 
     #[getter]
-    fn essence_str(&self) -> &str {
+    pub fn essence_str(&self) -> &str {
         self.0.essence_str()
     }
 
@@ -45,6 +45,14 @@ impl Mime {
         self.0.get_param(name).map(|v| v.as_str())
     }
 
+    fn matches(&self, pattern: MimeType) -> bool {
+        let pattern = pattern.0;
+        let type_matches = pattern.type_() == mime::STAR || pattern.type_() == self.0.type_();
+        let subtype_matches = pattern.subtype() == mime::STAR
+            || (pattern.subtype() == self.0.subtype() && pattern.suffix() == self.0.suffix());
+        type_matches && subtype_matches
+    }
+
     fn __copy__(&self) -> Self {
         Mime(self.0.clone())
     }
@@ -59,8 +67,17 @@ impl Mime {
     }
 
     fn __hash__(&self) -> u64 {
+        // Hash the parts compared by equality, the source string may differ e.g. by whitespace
         let mut hasher = DefaultHasher::new();
-        self.0.hash(&mut hasher);
+        self.0.essence_str().hash(&mut hasher);
+        for (name, value) in self.0.params() {
+            name.as_str().hash(&mut hasher);
+            if name == mime::CHARSET {
+                value.as_str().to_ascii_lowercase().hash(&mut hasher);
+            } else {
+                value.as_str().hash(&mut hasher);
+            }
+        }
         hasher.finish()
     }
 
@@ -1,15 +1,38 @@
+use crate::http::Mime;
 use crate::internal::types::QueryParams;
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD_NO_PAD;
+use bytes::Bytes;
+use percent_encoding::{AsciiSet, CONTROLS, percent_decode_str};
 use pyo3::basic::CompareOp;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::sync::OnceLockExt;
 use pyo3::types::{PyDict, PyIterator, PyList, PyString};
 use pyo3::{IntoPyObjectExt, intern};
+use pyo3_bytes::PyBytes;
 use serde::Serialize;
+use std::collections::HashSet;
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::net::IpAddr;
 use std::str::FromStr;
 use std::sync::OnceLock;
+use url::Position;
+
+// Same as the path segment encode set used by url::PathSegmentsMut
+pub const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'<')
+    .add(b'>')
+    .add(b'`')
+    .add(b'?')
+    .add(b'{')
+    .add(b'}')
+    .add(b'/')
+    .add(b'%')
+    .add(b'\\');
 
 #[pyclass(frozen)]
 pub struct Url {
@@ -164,6 +187,16 @@ impl Url {
         self.url.fragment()
     }
 
+    #[getter]
+    fn is_data_uri(&self) -> bool {
+        self.url.scheme() == "data"
+    }
+
+    fn data_uri_decode(&self, py: Python) -> PyResult<(Mime, PyBytes)> {
+        let (mime, data) = py.detach(|| self.data_uri_decode_inner())?;
+        Ok((Mime::new(mime), PyBytes::from(Bytes::from(data))))
+    }
+
     fn with_query(&self, query: Option<QueryParams>) -> PyResult<Self> {
         let mut url = self.url.clone();
         url.set_query(None);
@@ -177,6 +210,38 @@ impl Url {
         Ok(Url::new(url))
     }
 
+    fn with_query_merged(&self, query: QueryParams) -> PyResult<Self> {
+        let mut overrides = self.url.clone();
+        overrides.set_query(None);
+        Self::extend_query_inner(&mut overrides, Some(query))?;
+        let overrides: Vec<(String, String)> = overrides.query_pairs().into_owned().collect();
+
+        // Overridden keys take the position of their first existing occurrence, new keys go last
+        let mut merged: Vec<(String, String)> = vec![];
+        let mut overridden: HashSet<String> = HashSet::new();
+        for (key, val) in self.url.query_pairs() {
+            if !overrides.iter().any(|(k, _)| *k == key) {
+                merged.push((key.into_owned(), val.into_owned()));
+            } else if !overridden.contains(key.as_ref()) {
+                merged.extend(overrides.iter().filter(|(k, _)| *k == key).cloned());
+                overridden.insert(key.into_owned());
+            }
+        }
+        merged.extend(overrides.into_iter().filter(|(k, _)| !overridden.contains(k)));
+        Ok(self.with_query_pairs(merged))
+    }
+
+    fn with_query_removed(&self, key: &str) -> Self {
+        let pairs = self.url.query_pairs().into_owned().filter(|(k, _)| k != key).collect();
+        self.with_query_pairs(pairs)
+    }
+
+    fn with_query_set(&self, key: &str, value: &str) -> Self {
+        let mut pairs: Vec<(String, String)> = self.url.query_pairs().into_owned().filter(|(k, _)| k != key).collect();
+        pairs.push((key.to_string(), value.to_string()));
+        self.with_query_pairs(pairs)
+    }
+
     pub fn with_query_string(&self, query: Option<&str>) -> Self {
         let mut url = self.url.clone();
         url.set_query(query);
@@ -201,6 +266,53 @@ impl Url {
         Ok(Url::new(url))
     }
 
+    fn insert_path_segment(&self, index: usize, segment: &str) -> PyResult<Self> {
+        let mut url = self.url.clone();
+        let mut segments = url
+            .path_segments()
+            .ok_or_else(|| PyValueError::new_err("cannot be base"))?
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        // Trailing empty segment of a directory-style path stays last, root path has only the inserted segment
+        let trailing_slash = segments.len() > 1 && segments.last().is_some_and(String::is_empty);
+        if segments.last().is_some_and(String::is_empty) {
+            segments.pop();
+        }
+        let tail = segments.split_off(index.min(segments.len()));
+        {
+            let mut path = url
+                .path_segments_mut()
+                .map_err(|_| PyValueError::new_err("cannot be base"))?;
+            path.pop_if_empty();
+            for _ in &tail {
+                path.pop();
+            }
+            path.push(segment);
+        }
+        if !tail.is_empty() || trailing_slash {
+            // Following segments are appended as-is, path_segments_mut would encode their percent-encoding again
+            let mut path = url.path().to_string();
+            for segment in &tail {
+                path.push('/');
+                path.push_str(segment);
+            }
+            if trailing_slash {
+                path.push('/');
+            }
+            url.set_path(&path);
+        }
+        Ok(Url::new(url))
+    }
+
+    fn pop_path_segment(&self) -> PyResult<Self> {
+        let mut url = self.url.clone();
+        url.path_segments_mut()
+            .map_err(|_| PyValueError::new_err("cannot be base"))?
+            .pop_if_empty()
+            .pop();
+        Ok(Url::new(url))
+    }
+
     fn with_port(&self, port: Option<u16>) -> PyResult<Self> {
         let mut url = self.url.clone();
         url.set_port(port)
@@ -243,6 +355,14 @@ impl Url {
         Ok(Url::new(url))
     }
 
+    fn upgrade_to_https(&self) -> PyResult<Self> {
+        self.switch_http_scheme("http", "https")
+    }
+
+    fn downgrade_to_http(&self) -> PyResult<Self> {
+        self.switch_http_scheme("https", "http")
+    }
+
     fn with_fragment(&self, fragment: Option<&str>) -> Self {
         let mut url = self.url.clone();
         url.set_fragment(fragment);
@@ -253,6 +373,10 @@ impl Url {
         Url::new(self.url.clone())
     }
 
+    fn to_json<'py>(&self, py: Python<'py>) -> Bound<'py, PyString> {
+        PyString::new(py, self.as_str())
+    }
+
     fn __truediv__(&self, other: &str) -> PyResult<Self> {
         self.join(other)
     }
@@ -316,6 +440,48 @@ impl Url {
         }
     }
 
+    fn data_uri_decode_inner(&self) -> PyResult<(mime::Mime, Vec<u8>)> {
+        if !self.is_data_uri() {
+            return Err(PyValueError::new_err("Not a data URI"));
+        }
+        // Query is part of the data, fragment is not
+        let content = &self.url[Position::BeforePath..Position::AfterQuery];
+        let (header, data) = content
+            .split_once(',')
+            .ok_or_else(|| PyValueError::new_err("Invalid data URI, missing ','"))?;
+
+        let header = percent_decode_str(header).decode_utf8_lossy();
+        let (media_type, is_base64) = match header.rsplit_once(';') {
+            Some((media_type, ext)) if ext.trim().eq_ignore_ascii_case("base64") => (media_type.trim(), true),
+            _ => (header.trim(), false),
+        };
+        let mime = if media_type.is_empty() {
+            mime::TEXT_PLAIN
+        } else if media_type.starts_with(';') {
+            format!("text/plain{}", media_type)
+                .parse::<mime::Mime>()
+                .map_err(|e| PyValueError::new_err(e.to_string()))?
+        } else {
+            media_type
+                .parse::<mime::Mime>()
+                .map_err(|e| PyValueError::new_err(e.to_string()))?
+        };
+
+        let data: Vec<u8> = percent_decode_str(data).collect();
+        if !is_base64 {
+            return Ok((mime, data));
+        }
+        let data: Vec<u8> = data.into_iter().filter(|b| !b.is_ascii_whitespace()).collect();
+        let data = BASE64_STANDARD_NO_PAD
+            .decode(
+                data.strip_suffix(b"==")
+                    .or_else(|| data.strip_suffix(b"="))
+                    .unwrap_or(&data),
+            )
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok((mime, data))
+    }
+
     fn parse_inner(url: &str) -> PyResult<url::Url> {
         url::Url::parse(url).map_err(|e| PyValueError::new_err(e.to_string()))
     }
@@ -333,6 +499,15 @@ impl Url {
         })
     }
 
+    fn with_query_pairs(&self, pairs: Vec<(String, String)>) -> Self {
+        let mut url = self.url.clone();
+        url.set_query(None);
+        if !pairs.is_empty() {
+            url.query_pairs_mut().extend_pairs(pairs);
+        }
+        Url::new(url)
+    }
+
     fn extend_query_inner(url: &mut url::Url, query: Option<QueryParams>) -> PyResult<()> {
         if let Some(query) = query.map(|q| q.0) {
             let mut pairs = vec![];
@@ -350,6 +525,21 @@ impl Url {
         Ok(())
     }
 
+    fn switch_http_scheme(&self, from: &str, to: &str) -> PyResult<Self> {
+        let mut url = self.url.clone();
+        if url.scheme() == to {
+            return Ok(Url::new(url));
+        }
+        if url.scheme() != from {
+            return Err(PyValueError::new_err(format!("Expected {} or {} URL", from, to)));
+        }
+        // Default port of the old scheme is never stored (e.g. "http://h:80/" is "http://h/"),
+        // so the new scheme uses its own default port. Other explicit ports are kept.
+        url.set_scheme(to)
+            .map_err(|_| PyValueError::new_err("Invalid scheme"))?;
+        Ok(Url::new(url))
+    }
+
     pub fn as_str(&self) -> &str {
         self.url.as_str()
     }
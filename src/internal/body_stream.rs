@@ -1,6 +1,8 @@
 use crate::asyncio::{PyCoroWaiter, TaskLocal, py_coro_waiter};
+use crate::internal::json::{JsonHandler, json_dumps};
+use bytes::BytesMut;
 use futures_util::{FutureExt, Stream};
-use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::sync::PyOnceLock;
 use pyo3::types::PyEllipsis;
@@ -15,6 +17,7 @@ pub struct BodyStream {
     task_local: Option<TaskLocal>,
     cur_waiter: Option<StreamWaiter>,
     is_async: bool,
+    items: StreamItems,
 }
 impl Stream for BodyStream {
     type Item = PyResult<PyBytes>;
@@ -43,10 +46,9 @@ impl Stream for BodyStream {
                     Ok(res) => {
                         Python::attach(|py| {
                             if self.is_end_marker(py, &res) {
-                                Poll::Ready(None) // Stream ended
+                                Poll::Ready(self.items.finish().map(Ok)) // Stream ended
                             } else {
-                                let bytes = res.extract::<PyBytes>(py)?;
-                                Poll::Ready(Some(Ok(bytes)))
+                                Poll::Ready(Some(self.items.encode(py, res)))
                             }
                         })
                     }
@@ -59,6 +61,18 @@ impl Stream for BodyStream {
 }
 impl BodyStream {
     pub fn new(stream: Bound<PyAny>) -> PyResult<Self> {
+        Self::new_inner(stream, StreamItems::Bytes)
+    }
+
+    pub fn new_json_lines(stream: Bound<PyAny>, json_handler: Option<JsonHandler>) -> PyResult<Self> {
+        Self::new_inner(stream, StreamItems::JsonLines(json_handler))
+    }
+
+    pub fn new_json_array(stream: Bound<PyAny>, json_handler: Option<JsonHandler>) -> PyResult<Self> {
+        Self::new_inner(stream, StreamItems::JsonArray(json_handler, JsonArrayState::Start))
+    }
+
+    fn new_inner(stream: Bound<PyAny>, items: StreamItems) -> PyResult<Self> {
         let is_async = is_async_iter(&stream)?;
         Ok(BodyStream {
             is_async,
@@ -66,6 +80,7 @@ impl BodyStream {
             stream: Some(stream.unbind()),
             task_local: None,
             cur_waiter: None,
+            items,
         })
     }
 
@@ -141,12 +156,15 @@ impl BodyStream {
     }
 
     pub fn try_clone(&self, py: Python) -> PyResult<Self> {
-        let new_stream = self
+        let stream = self
             .stream
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Expected stream"))?
-            .bind(py)
-            .call_method0(intern!(py, "__copy__"))?;
+            .bind(py);
+        if !stream.hasattr(intern!(py, "__copy__"))? {
+            return Err(PyTypeError::new_err("Stream body can not be copied, it must implement __copy__"));
+        }
+        let new_stream = stream.call_method0(intern!(py, "__copy__"))?;
 
         Ok(BodyStream {
             is_async: self.is_async,
@@ -154,6 +172,7 @@ impl BodyStream {
             stream: Some(new_stream.unbind()),
             task_local: None,
             cur_waiter: None,
+            items: self.items.clone_ref(py),
         })
     }
 
@@ -162,6 +181,10 @@ impl BodyStream {
         visit.call(&self.stream)?;
         visit.call(&self.py_iter)?;
         self.task_local.as_ref().map(|v| v.__traverse__(&visit)).transpose()?;
+        if let StreamItems::JsonLines(Some(json_handler)) | StreamItems::JsonArray(Some(json_handler), _) = &self.items
+        {
+            json_handler.__traverse__(&visit)?;
+        }
         Ok(())
     }
 
@@ -170,9 +193,75 @@ impl BodyStream {
         self.py_iter = None;
         self.task_local = None;
         self.cur_waiter = None;
+        self.items = StreamItems::Bytes;
     } // :NOCOV_END
 }
 
+pub fn json_line(py: Python, item: Py<PyAny>, json_handler: Option<&JsonHandler>) -> PyResult<bytes::Bytes> {
+    let json = json_dumps(py, item, json_handler)?;
+    let mut line = BytesMut::with_capacity(json.len() + 1);
+    line.extend_from_slice(&json);
+    line.extend_from_slice(b"\n");
+    Ok(line.freeze())
+}
+
+enum StreamItems {
+    Bytes,
+    JsonLines(Option<JsonHandler>),
+    JsonArray(Option<JsonHandler>, JsonArrayState),
+}
+impl StreamItems {
+    fn encode(&mut self, py: Python, item: Py<PyAny>) -> PyResult<PyBytes> {
+        match self {
+            StreamItems::Bytes => item.extract::<PyBytes>(py),
+            StreamItems::JsonLines(json_handler) => Ok(PyBytes::new(json_line(py, item, json_handler.as_ref())?)),
+            StreamItems::JsonArray(json_handler, state) => {
+                let json = json_dumps(py, item, json_handler.as_ref())?;
+                let mut buf = BytesMut::with_capacity(json.len() + 1);
+                buf.extend_from_slice(if *state == JsonArrayState::Start { b"[" } else { b"," });
+                buf.extend_from_slice(&json);
+                *state = JsonArrayState::Items;
+                Ok(PyBytes::new(buf.freeze()))
+            }
+        }
+    }
+
+    // Trailing bytes to send after the last item
+    fn finish(&mut self) -> Option<PyBytes> {
+        match self {
+            StreamItems::Bytes | StreamItems::JsonLines(_) => None,
+            StreamItems::JsonArray(_, state) => {
+                let tail: &'static [u8] = match state {
+                    JsonArrayState::Start => b"[]",
+                    JsonArrayState::Items => b"]",
+                    JsonArrayState::Done => return None,
+                };
+                *state = JsonArrayState::Done;
+                Some(PyBytes::new(bytes::Bytes::from_static(tail)))
+            }
+        }
+    }
+
+    fn clone_ref(&self, py: Python) -> Self {
+        match self {
+            StreamItems::Bytes => StreamItems::Bytes,
+            StreamItems::JsonLines(json_handler) => {
+                StreamItems::JsonLines(json_handler.as_ref().map(|v| v.clone_ref(py)))
+            }
+            StreamItems::JsonArray(json_handler, _) => {
+                StreamItems::JsonArray(json_handler.as_ref().map(|v| v.clone_ref(py)), JsonArrayState::Start)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum JsonArrayState {
+    Start,
+    Items,
+    Done,
+}
+
 fn is_async_iter(obj: &Bound<PyAny>) -> PyResult<bool> {
     static ASYNC_TYPE: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
     obj.is_instance(ASYNC_TYPE.import(obj.py(), "collections.abc", "AsyncIterable")?)
@@ -0,0 +1,36 @@
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+// Digest algorithms for the RFC 3230 Digest header
+#[derive(Clone, Copy)]
+pub enum DigestAlgorithm {
+    Sha256,
+    Sha512,
+}
+impl DigestAlgorithm {
+    pub fn header_value(self, body: &[u8]) -> String {
+        let (name, algorithm) = match self {
+            DigestAlgorithm::Sha256 => ("sha-256", &ring::digest::SHA256),
+            DigestAlgorithm::Sha512 => ("sha-512", &ring::digest::SHA512),
+        };
+        let digest = ring::digest::digest(algorithm, body);
+        format!("{}={}", name, BASE64_STANDARD.encode(digest.as_ref()))
+    }
+}
+impl<'py> FromPyObject<'py> for DigestAlgorithm {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let algorithm = ob.extract::<&str>()?;
+        if algorithm.eq_ignore_ascii_case("sha-256") {
+            Ok(DigestAlgorithm::Sha256)
+        } else if algorithm.eq_ignore_ascii_case("sha-512") {
+            Ok(DigestAlgorithm::Sha512)
+        } else {
+            Err(PyValueError::new_err(format!(
+                "Unsupported digest algorithm: {}, expected 'sha-256' or 'sha-512'",
+                algorithm
+            )))
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use crate::http::HeaderMap;
+use crate::internal::types::JsonValue;
 use crate::response::BaseResponseBodyReader;
 use bytes::Bytes;
 use pyo3::PyVisit;
-use pyo3::exceptions::PyRuntimeError;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyDict, PyList};
 use pyo3_bytes::PyBytes;
 
 #[pyclass(frozen)]
@@ -84,6 +85,40 @@ impl JsonHandler {
     } // :NOCOV_END
 }
 
+pub fn json_dumps(py: Python, data: Py<PyAny>, json_handler: Option<&JsonHandler>) -> PyResult<Bytes> {
+    if let Some(handler) = json_handler
+        && handler.has_dumps()
+    {
+        handler.call_dumps(py, JsonDumpsContext { data })
+    } else {
+        let json_val: JsonValue = data.bind(py).extract()?;
+        py.detach(|| {
+            serde_json::to_vec(&json_val)
+                .map(Bytes::from)
+                .map_err(|e| PyValueError::new_err(e.to_string()))
+        })
+    }
+}
+
+// Calls the hook on each decoded JSON object, innermost first (like `object_hook` of Python's json module)
+pub fn apply_object_hook<'py>(obj: Bound<'py, PyAny>, hook: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    if let Ok(dict) = obj.downcast::<PyDict>() {
+        let items: Vec<_> = dict.iter().collect();
+        for (key, val) in items {
+            dict.set_item(key, apply_object_hook(val, hook)?)?;
+        }
+        hook.call1((dict,))
+    } else if let Ok(list) = obj.downcast::<PyList>() {
+        let items: Vec<_> = list.iter().collect();
+        for (idx, val) in items.into_iter().enumerate() {
+            list.set_item(idx, apply_object_hook(val, hook)?)?;
+        }
+        Ok(obj)
+    } else {
+        Ok(obj)
+    }
+}
+
 #[pymethods]
 impl JsonLoadsContext {
     // :NOCOV_START
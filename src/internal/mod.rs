@@ -1,4 +1,5 @@
 pub mod body_stream;
+pub mod digest;
 pub mod json;
 pub mod types;
 pub mod utils;
@@ -1,7 +1,8 @@
+use crate::http::Url;
 use crate::internal::utils::KeyValPairs;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyInt, PyString};
+use pyo3::types::{PyDict, PyInt, PyList, PyString, PyTuple};
 use pyo3::{Bound, FromPyObject, IntoPyObject, Py, PyAny, PyErr, PyResult, Python, intern};
 use pythonize::{depythonize, pythonize};
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,8 @@ pub struct HeaderValue(pub http::HeaderValue);
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct Version(pub http::Version);
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct RequestVersion(pub http::Version);
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct StatusCode(pub http::StatusCode);
 #[derive(Serialize, Deserialize)]
 pub struct JsonValue(pub serde_json::Value);
@@ -162,6 +165,18 @@ impl<'py> FromPyObject<'py> for Version {
     }
 }
 
+// Versions a request can be sent with, "HTTP/2" is the same as "HTTP/2.0"
+impl<'py> FromPyObject<'py> for RequestVersion {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        Ok(match ob.extract::<&str>()? {
+            "HTTP/1.0" => RequestVersion(http::Version::HTTP_10),
+            "HTTP/1.1" => RequestVersion(http::Version::HTTP_11),
+            "HTTP/2" | "HTTP/2.0" => RequestVersion(http::Version::HTTP_2),
+            _ => Err(PyValueError::new_err("Invalid HTTP version. Use 'HTTP/1.0', 'HTTP/1.1' or 'HTTP/2'"))?,
+        })
+    }
+}
+
 impl<'py> FromPyObject<'py> for Extensions {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
         if let Ok(dict) = ob.downcast_exact::<PyDict>() {
@@ -216,6 +231,31 @@ impl<'py> IntoPyObject<'py> for JsonValue {
 }
 impl<'py> FromPyObject<'py> for JsonValue {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
-        Ok(depythonize(ob)?)
+        match depythonize(ob) {
+            Ok(value) => Ok(value),
+            Err(_) => Ok(depythonize(&urls_to_json(ob)?)?), // Slow path only when plain conversion fails
+        }
+    }
+}
+
+// Replaces Url values and keys by their JSON (string) form
+fn urls_to_json<'py>(ob: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let py = ob.py();
+    if let Ok(url) = ob.downcast::<Url>() {
+        Ok(PyString::new(py, url.get().as_str()).into_any())
+    } else if let Ok(dict) = ob.downcast::<PyDict>() {
+        let res = PyDict::new(py);
+        for (key, val) in dict.iter() {
+            res.set_item(urls_to_json(&key)?, urls_to_json(&val)?)?;
+        }
+        Ok(res.into_any())
+    } else if ob.is_instance_of::<PyList>() || ob.is_instance_of::<PyTuple>() {
+        let items = ob
+            .try_iter()?
+            .map(|item| urls_to_json(&item?))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(PyList::new(py, items)?.into_any())
+    } else {
+        Ok(ob.clone())
     }
 }
@@ -42,8 +42,8 @@ mod pyreqwest {
         use super::*;
         #[pymodule_export]
         use crate::request::{
-            BaseRequestBuilder, ConsumedRequest, Request, RequestBody, RequestBuilder, StreamRequest,
-            SyncConsumedRequest, SyncRequestBuilder, SyncStreamRequest,
+            BaseRequestBuilder, ConsumedRequest, Request, RequestBody, RequestBuilder, RequestTemplate, StreamRequest,
+            SyncConsumedRequest, SyncRequestBuilder, SyncRequestTemplate, SyncStreamRequest,
         };
         #[pymodule_init]
         fn init(module: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -56,7 +56,8 @@ mod pyreqwest {
         use super::*;
         #[pymodule_export]
         use crate::response::{
-            BaseResponse, Response, ResponseBodyReader, ResponseBuilder, SyncResponse, SyncResponseBodyReader,
+            BaseResponse, ProxyResponse, Response, ResponseBodyReader, ResponseBuilder, ServerSentEvent,
+            ServerSentEventIterator, SyncResponse, SyncResponseBodyReader, SyncServerSentEventIterator,
         };
         #[pymodule_init]
         fn init(module: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -1,3 +1,5 @@
 mod next;
+mod record;
 
 pub use next::{Next, NextInner, SyncNext};
+pub use record::{RecordMiddleware, SyncRecordMiddleware};
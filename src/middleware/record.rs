@@ -0,0 +1,116 @@
+use crate::http::HeaderMap;
+use crate::middleware::{Next, SyncNext};
+use crate::response::{Response, SyncResponse};
+use pyo3::coroutine::CancelHandle;
+use pyo3::intern;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::time::Instant;
+
+#[pyclass(frozen)]
+pub struct RecordMiddleware {
+    sink: Py<PyAny>,
+}
+
+#[pyclass(frozen)]
+pub struct SyncRecordMiddleware {
+    sink: Py<PyAny>,
+}
+
+#[pymethods]
+impl RecordMiddleware {
+    async fn __call__(
+        &self,
+        request: Py<PyAny>,
+        next: Py<Next>,
+        #[pyo3(cancel_handle)] cancel: CancelHandle,
+    ) -> PyResult<Py<Response>> {
+        let record = Python::attach(|py| request_record(request.bind(py)).map(Bound::unbind))?;
+        let start = Instant::now();
+        let resp = next.get().run(request, cancel).await;
+        Python::attach(|py| {
+            let resp = resp.map(|resp| resp.into_bound(py));
+            self.sink
+                .call1(py, (response_record(record.bind(py), start, &resp)?,))?;
+            Ok(resp?.unbind())
+        })
+    }
+
+    // :NOCOV_START
+    fn __traverse__(&self, visit: pyo3::PyVisit<'_>) -> Result<(), pyo3::PyTraverseError> {
+        visit.call(&self.sink)
+    } // :NOCOV_END
+}
+impl RecordMiddleware {
+    pub fn new(sink: Py<PyAny>) -> Self {
+        RecordMiddleware { sink }
+    }
+}
+
+#[pymethods]
+impl SyncRecordMiddleware {
+    fn __call__<'py>(&self, request: Bound<'py, PyAny>, next: Bound<'py, SyncNext>) -> PyResult<Py<SyncResponse>> {
+        let py = request.py();
+        let record = request_record(&request)?;
+        let start = Instant::now();
+        let resp = next.try_borrow()?.run(&request).map(|resp| resp.into_bound(py));
+        self.sink.call1(py, (response_record(&record, start, &resp)?,))?;
+        Ok(resp?.unbind())
+    }
+
+    // :NOCOV_START
+    fn __traverse__(&self, visit: pyo3::PyVisit<'_>) -> Result<(), pyo3::PyTraverseError> {
+        visit.call(&self.sink)
+    } // :NOCOV_END
+}
+impl SyncRecordMiddleware {
+    pub fn new(sink: Py<PyAny>) -> Self {
+        SyncRecordMiddleware { sink }
+    }
+}
+
+fn request_record<'py>(request: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyDict>> {
+    let py = request.py();
+    let record = PyDict::new(py);
+    record.set_item(intern!(py, "method"), request.getattr(intern!(py, "method"))?)?;
+    record.set_item(intern!(py, "url"), request.getattr(intern!(py, "url"))?)?;
+    record.set_item(
+        intern!(py, "request_headers"),
+        headers_summary(&request.getattr(intern!(py, "headers"))?)?,
+    )?;
+    let extensions = request.getattr(intern!(py, "extensions"))?;
+    record.set_item(intern!(py, "tag"), extensions.call_method1(intern!(py, "get"), (intern!(py, "tag"),))?)?;
+    Ok(record)
+}
+
+fn response_record<'py, T>(
+    record: &Bound<'py, PyDict>,
+    start: Instant,
+    resp: &PyResult<Bound<'py, T>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let py = record.py();
+    record.set_item(intern!(py, "elapsed"), start.elapsed())?;
+    match resp {
+        Ok(resp) => {
+            let resp = resp.as_any();
+            record.set_item(intern!(py, "status"), resp.getattr(intern!(py, "status"))?)?;
+            record
+                .set_item(intern!(py, "response_headers"), headers_summary(&resp.getattr(intern!(py, "headers"))?)?)?;
+            record.set_item(intern!(py, "error"), py.None())?;
+        }
+        Err(err) => {
+            record.set_item(intern!(py, "status"), py.None())?;
+            record.set_item(intern!(py, "response_headers"), py.None())?;
+            record.set_item(intern!(py, "error"), err.clone_ref(py).into_value(py))?;
+        }
+    }
+    Ok(record.clone())
+}
+
+fn headers_summary<'py>(headers: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyDict>> {
+    let py = headers.py();
+    headers
+        .downcast::<HeaderMap>()?
+        .get()
+        .ref_map(|map| HeaderMap::dict_multi_value_inner(map, py, true))
+}
@@ -1,5 +1,6 @@
 use crate::allow_threads::AllowThreads;
 use crate::client::RuntimeHandle;
+use crate::exceptions::BuilderError;
 use crate::multipart::PartBuilder;
 use pyo3::coroutine::CancelHandle;
 use pyo3::exceptions::PyRuntimeError;
@@ -10,6 +11,8 @@ use std::path::PathBuf;
 pub struct FormBuilder {
     inner: Option<reqwest::multipart::Form>,
     is_async: bool,
+    has_unknown_length: bool,
+    require_known_length: bool,
 }
 #[pymethods]
 impl FormBuilder {
@@ -18,6 +21,8 @@ impl FormBuilder {
         FormBuilder {
             inner: Some(reqwest::multipart::Form::new()),
             is_async: false,
+            has_unknown_length: false,
+            require_known_length: false,
         }
     }
 
@@ -57,6 +62,9 @@ impl FormBuilder {
         if !slf.is_async {
             slf.is_async = part.is_async();
         }
+        if !part.has_known_length() {
+            slf.has_unknown_length = true;
+        }
         let part = part.build()?;
         Self::apply(slf, |builder| Ok(builder.part(name, part)))
     }
@@ -72,9 +80,22 @@ impl FormBuilder {
     fn percent_encode_noop(slf: PyRefMut<Self>) -> PyResult<PyRefMut<Self>> {
         Self::apply(slf, |builder| Ok(builder.percent_encode_noop()))
     }
+
+    #[pyo3(signature = (enable=true))]
+    fn require_known_length(mut slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
+        slf.inner_ref()?;
+        slf.require_known_length = enable;
+        Ok(slf)
+    }
 }
 impl FormBuilder {
     pub fn build(&mut self) -> PyResult<reqwest::multipart::Form> {
+        if self.require_known_length && self.has_unknown_length {
+            return Err(BuilderError::from_causes(
+                "Multipart form has a part with unknown length, use PartBuilder.from_stream_with_length",
+                vec![],
+            ));
+        }
         self.inner
             .take()
             .ok_or_else(|| PyRuntimeError::new_err("Form was already built"))
@@ -12,17 +12,18 @@ use std::path::PathBuf;
 pub struct PartBuilder {
     inner: Option<reqwest::multipart::Part>,
     is_async: bool,
+    has_known_length: bool,
 }
 #[pymethods]
 impl PartBuilder {
     #[staticmethod]
     fn from_text(py: Python, value: String) -> Self {
-        py.detach(|| Self::new(reqwest::multipart::Part::text(value), false))
+        py.detach(|| Self::new(reqwest::multipart::Part::text(value), false, true))
     }
 
     #[staticmethod]
     fn from_bytes(py: Python, value: PyBytes) -> Self {
-        py.detach(|| Self::new(reqwest::multipart::Part::bytes(Vec::from(value.into_inner())), false))
+        py.detach(|| Self::new(reqwest::multipart::Part::bytes(Vec::from(value.into_inner())), false, true))
     }
 
     #[staticmethod]
@@ -30,7 +31,7 @@ impl PartBuilder {
         let mut stream = BodyStream::new(stream)?;
         stream.set_task_local(py)?;
         let is_async = stream.is_async();
-        py.detach(|| Ok(Self::new(reqwest::multipart::Part::stream(stream.into_reqwest(false)?), is_async)))
+        py.detach(|| Ok(Self::new(reqwest::multipart::Part::stream(stream.into_reqwest(false)?), is_async, false)))
     }
 
     #[staticmethod]
@@ -42,21 +43,29 @@ impl PartBuilder {
             Ok(Self::new(
                 reqwest::multipart::Part::stream_with_length(stream.into_reqwest(false)?, length),
                 is_async,
+                true,
             ))
         })
     }
 
     #[staticmethod]
-    async fn from_file(path: PathBuf, #[pyo3(cancel_handle)] cancel: CancelHandle) -> PyResult<Self> {
+    #[pyo3(signature = (path, *, file_name=None, mime=None))]
+    async fn from_file(
+        path: PathBuf,
+        file_name: Option<String>,
+        mime: Option<MimeType>,
+        #[pyo3(cancel_handle)] cancel: CancelHandle,
+    ) -> PyResult<Self> {
         let fut = RuntimeHandle::global_handle()?.spawn_handled(reqwest::multipart::Part::file(path), cancel);
         let part = AllowThreads(fut).await??;
-        Ok(Self::new(part, false))
+        Ok(Self::new(Self::file_overrides(part, file_name, mime)?, false, true))
     }
 
     #[staticmethod]
-    fn from_sync_file(path: PathBuf) -> PyResult<Self> {
+    #[pyo3(signature = (path, *, file_name=None, mime=None))]
+    fn from_sync_file(path: PathBuf, file_name: Option<String>, mime: Option<MimeType>) -> PyResult<Self> {
         let part = RuntimeHandle::global_handle()?.blocking_spawn(reqwest::multipart::Part::file(path))?;
-        Ok(Self::new(part, false))
+        Ok(Self::new(Self::file_overrides(part, file_name, mime)?, false, true))
     }
 
     fn mime<'py>(slf: PyRefMut<'py, Self>, mime: MimeType) -> PyResult<PyRefMut<'py, Self>> {
@@ -76,13 +85,30 @@ impl PartBuilder {
     }
 }
 impl PartBuilder {
-    fn new(part: reqwest::multipart::Part, is_async: bool) -> Self {
+    fn new(part: reqwest::multipart::Part, is_async: bool, has_known_length: bool) -> Self {
         PartBuilder {
             inner: Some(part),
             is_async,
+            has_known_length,
         }
     }
 
+    fn file_overrides(
+        mut part: reqwest::multipart::Part,
+        file_name: Option<String>,
+        mime: Option<MimeType>,
+    ) -> PyResult<reqwest::multipart::Part> {
+        if let Some(file_name) = file_name {
+            part = part.file_name(file_name);
+        }
+        if let Some(mime) = mime {
+            part = part
+                .mime_str(mime.0.as_ref())
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        }
+        Ok(part)
+    }
+
     pub fn build(&mut self) -> PyResult<reqwest::multipart::Part> {
         self.inner
             .take()
@@ -93,6 +119,10 @@ impl PartBuilder {
         self.is_async
     }
 
+    pub fn has_known_length(&self) -> bool {
+        self.has_known_length
+    }
+
     fn apply<F>(mut slf: PyRefMut<Self>, fun: F) -> PyResult<PyRefMut<Self>>
     where
         F: FnOnce(reqwest::multipart::Part) -> PyResult<reqwest::multipart::Part>,
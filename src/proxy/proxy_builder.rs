@@ -15,6 +15,7 @@ pub struct ProxyBuilder {
 impl ProxyBuilder {
     #[staticmethod]
     fn http(py: Python, url: UrlType) -> PyResult<Self> {
+        Self::check_scheme(&url.0)?;
         py.detach(|| {
             let proxy =
                 reqwest::Proxy::http(url.0).map_err(|e| PyValueError::new_err(format!("Invalid proxy: {}", e)))?;
@@ -24,6 +25,7 @@ impl ProxyBuilder {
 
     #[staticmethod]
     fn https(py: Python, url: UrlType) -> PyResult<Self> {
+        Self::check_scheme(&url.0)?;
         py.detach(|| {
             let proxy =
                 reqwest::Proxy::https(url.0).map_err(|e| PyValueError::new_err(format!("Invalid proxy: {}", e)))?;
@@ -33,6 +35,7 @@ impl ProxyBuilder {
 
     #[staticmethod]
     fn all(py: Python, url: UrlType) -> PyResult<Self> {
+        Self::check_scheme(&url.0)?;
         py.detach(|| {
             let proxy =
                 reqwest::Proxy::all(url.0).map_err(|e| PyValueError::new_err(format!("Invalid proxy: {}", e)))?;
@@ -76,6 +79,16 @@ impl ProxyBuilder {
             .ok_or_else(|| PyRuntimeError::new_err("Proxy was already built"))
     }
 
+    fn check_scheme(url: &reqwest::Url) -> PyResult<()> {
+        match url.scheme() {
+            "http" | "https" | "socks4" | "socks4a" | "socks5" | "socks5h" => Ok(()),
+            scheme => Err(PyValueError::new_err(format!(
+                "Invalid proxy: unsupported scheme '{}', expected http, https, socks4, socks4a, socks5 or socks5h",
+                scheme
+            ))),
+        }
+    }
+
     fn handle_custom_proxy(fun: &Py<PyAny>, url: &reqwest::Url) -> PyResult<Option<reqwest::Url>> {
         Python::attach(|py| {
             Ok(fun
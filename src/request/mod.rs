@@ -2,10 +2,12 @@ mod consumed_request;
 mod request;
 mod request_body;
 mod request_builder;
+mod request_template;
 mod stream_request;
 
 pub use consumed_request::{ConsumedRequest, SyncConsumedRequest};
 pub use request::{Request, RequestData};
 pub use request_body::RequestBody;
 pub use request_builder::{BaseRequestBuilder, RequestBuilder, SyncRequestBuilder};
+pub use request_template::{RequestTemplate, SyncRequestTemplate};
 pub use stream_request::{StreamRequest, SyncStreamRequest};
@@ -5,8 +5,8 @@ use crate::internal::json::JsonHandler;
 use crate::internal::types::{Extensions, Method};
 use crate::middleware::{Next, NextInner, SyncNext};
 use crate::request::RequestBody;
-use crate::response::BaseResponse;
 use crate::response::internal::BodyConsumeConfig;
+use crate::response::{BaseResponse, SyncResponse};
 use pyo3::coroutine::CancelHandle;
 use pyo3::exceptions::{PyNotImplementedError, PyRuntimeError};
 use pyo3::prelude::*;
@@ -135,6 +135,16 @@ impl Request {
         }
     }
 
+    #[getter]
+    fn get_prefetch_chunks(&self) -> PyResult<usize> {
+        match self.ref_inner()?.request.body_consume_config {
+            BodyConsumeConfig::Streamed(conf) => Ok(conf.prefetch_chunks),
+            BodyConsumeConfig::FullyConsumed => {
+                Err(PyRuntimeError::new_err("Expected streamed request, found fully consumed request"))
+            }
+        }
+    }
+
     // :NOCOV_START
     #[classmethod]
     pub fn from_request_and_body(
@@ -156,6 +166,9 @@ impl Request {
         if let Some(extensions) = &inner.request.extensions {
             visit.call(&extensions.0)?;
         }
+        if let Some(error_for_json) = &inner.request.error_for_json {
+            visit.call(error_for_json)?;
+        }
         if let Some(middlewares_next) = &inner.middlewares_next {
             middlewares_next.__traverse__(&visit)?;
         }
@@ -193,7 +206,9 @@ impl Request {
             Some(middlewares_next) => AllowThreads(middlewares_next.run_inner(py_request, cancel)).await,
             None => Self::spawn_request(py_request, cancel).await,
         }?
-        .check_error_for_status()
+        .check_error_for_status()?
+        .check_error_for_json()
+        .await
     }
 
     pub fn blocking_send_inner(py_request: &Py<PyAny>) -> PyResult<BaseResponse> {
@@ -204,11 +219,15 @@ impl Request {
             inner.middlewares_next.take().map(SyncNext::new).transpose()
         })?;
 
-        match middlewares_next {
+        let resp = match middlewares_next {
             Some(middlewares_next) => Python::attach(|py| middlewares_next.run_inner(py_request.bind(py))),
             None => Self::blocking_spawn_request(py_request),
         }?
-        .check_error_for_status()
+        .check_error_for_status()?;
+        if !resp.has_error_for_json()? {
+            return Ok(resp);
+        }
+        Python::attach(|py| SyncResponse::check_error_for_json(py, resp))
     }
 
     pub async fn spawn_request(request: &Py<PyAny>, cancel: CancelHandle) -> PyResult<BaseResponse> {
@@ -356,6 +375,7 @@ pub struct RequestData {
     pub body_consume_config: BodyConsumeConfig,
     pub json_handler: Option<JsonHandler>,
     pub error_for_status: bool,
+    pub error_for_json: Option<Py<PyAny>>,
 }
 impl RequestData {
     fn try_clone(&self, py: Python) -> PyResult<Self> {
@@ -371,6 +391,7 @@ impl RequestData {
             body_consume_config: self.body_consume_config,
             json_handler: self.json_handler.as_ref().map(|v| v.clone_ref(py)),
             error_for_status: self.error_for_status,
+            error_for_json: self.error_for_json.as_ref().map(|v| v.clone_ref(py)),
         })
     }
 }
@@ -1,8 +1,9 @@
 use crate::internal::body_stream::BodyStream;
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
 use pyo3::sync::MutexExt;
+use pyo3::types::PyList;
 use pyo3::{PyTraverseError, PyVisit};
 use pyo3_bytes::PyBytes;
 use std::sync::{Mutex, MutexGuard};
@@ -27,9 +28,20 @@ impl RequestBody {
         Ok(Self::new(InnerBody::Stream(BodyStream::new(stream)?)))
     }
 
+    #[staticmethod]
+    pub fn from_json_stream(items: Bound<PyAny>) -> PyResult<Self> {
+        Ok(Self::new(InnerBody::Stream(BodyStream::new_json_array(items, None)?)))
+    }
+
+    #[staticmethod]
+    pub fn from_chunks(chunks: Vec<PyBytes>) -> Self {
+        Self::new(InnerBody::Chunks(chunks.into_iter().map(PyBytes::into_inner).collect()))
+    }
+
     fn copy_bytes(&self, py: Python) -> PyResult<Option<PyBytes>> {
         match self.lock(py)?.as_ref() {
             Some(InnerBody::Bytes(bytes)) => Ok(Some(py.detach(|| PyBytes::from(bytes.clone())))),
+            Some(InnerBody::Chunks(chunks)) => Ok(Some(py.detach(|| PyBytes::from(join_chunks(chunks.clone()))))),
             Some(InnerBody::Stream(_)) => Ok(None),
             None => Err(PyRuntimeError::new_err("Request body already consumed")),
         }
@@ -39,6 +51,10 @@ impl RequestBody {
         match self.lock(py)?.as_ref() {
             Some(InnerBody::Bytes(_)) => Ok(None),
             Some(InnerBody::Stream(stream)) => Ok(Some(stream.get_stream()?.clone_ref(py))),
+            Some(InnerBody::Chunks(chunks)) => {
+                let chunks = chunks.iter().map(|chunk| PyBytes::from(chunk.clone()));
+                Ok(Some(PyList::new(py, chunks)?.into_any().unbind()))
+            }
             None => Err(PyRuntimeError::new_err("Request body already consumed")),
         }
     }
@@ -55,6 +71,10 @@ impl RequestBody {
                 let stream_repr = stream.get_stream()?.bind(py).repr()?;
                 Ok(format!("{}(stream={})", type_name, stream_repr.to_str()?))
             }
+            Some(InnerBody::Chunks(chunks)) => {
+                let len: usize = chunks.iter().map(Bytes::len).sum();
+                Ok(format!("{}(chunks={}, len={})", type_name, chunks.len(), len))
+            }
             None => Ok(format!("{}(<already consumed>)", type_name)),
         }
     }
@@ -65,7 +85,7 @@ impl RequestBody {
             return Ok(());
         };
         match inner.as_ref() {
-            Some(InnerBody::Bytes(_)) => Ok(()),
+            Some(InnerBody::Bytes(_)) | Some(InnerBody::Chunks(_)) => Ok(()),
             Some(InnerBody::Stream(stream)) => stream.__traverse__(visit),
             None => Ok(()),
         }
@@ -80,11 +100,22 @@ impl RequestBody {
         let body = match self.lock(py)?.as_ref() {
             Some(InnerBody::Bytes(bytes)) => InnerBody::Bytes(py.detach(|| bytes.clone())),
             Some(InnerBody::Stream(stream)) => InnerBody::Stream(Python::attach(|py| stream.try_clone(py))?),
+            Some(InnerBody::Chunks(chunks)) => InnerBody::Chunks(py.detach(|| chunks.clone())),
             None => return Err(PyRuntimeError::new_err("Request body already consumed")),
         };
         Ok(Self::new(body))
     }
 
+    // Buffered body bytes, chunks are joined. None for streamed bodies
+    pub fn bytes(&self, py: Python) -> PyResult<Option<Bytes>> {
+        match self.lock(py)?.as_ref() {
+            Some(InnerBody::Bytes(bytes)) => Ok(Some(bytes.clone())),
+            Some(InnerBody::Chunks(chunks)) => Ok(Some(py.detach(|| join_chunks(chunks.clone())))),
+            Some(InnerBody::Stream(_)) => Ok(None),
+            None => Err(PyRuntimeError::new_err("Request body already consumed")),
+        }
+    }
+
     pub fn take_inner(&self, py: Python) -> PyResult<Self> {
         Ok(Self::new(
             self.lock(py)?
@@ -95,7 +126,7 @@ impl RequestBody {
 
     pub fn set_task_local(&self, py: Python) -> PyResult<()> {
         match self.lock(py)?.as_mut() {
-            Some(InnerBody::Bytes(_)) => Ok(()),
+            Some(InnerBody::Bytes(_)) | Some(InnerBody::Chunks(_)) => Ok(()),
             Some(InnerBody::Stream(stream)) => stream.set_task_local(py),
             None => Err(PyRuntimeError::new_err("Request body already consumed")),
         }
@@ -106,6 +137,8 @@ impl RequestBody {
         match self.lock(py)?.take() {
             Some(InnerBody::Bytes(bytes)) => Ok(reqwest::Body::from(bytes)),
             Some(InnerBody::Stream(stream)) => stream.into_reqwest(is_blocking),
+            // Joined into a single buffer as reqwest can only replay buffered bodies on 307/308 redirects
+            Some(InnerBody::Chunks(chunks)) => Ok(reqwest::Body::from(py.detach(|| join_chunks(chunks)))),
             None => Err(PyRuntimeError::new_err("Request body already consumed")),
         }
     }
@@ -122,7 +155,25 @@ impl From<Bytes> for RequestBody {
     }
 }
 
+impl From<BodyStream> for RequestBody {
+    fn from(stream: BodyStream) -> Self {
+        Self::new(InnerBody::Stream(stream))
+    }
+}
+
+fn join_chunks(mut chunks: Vec<Bytes>) -> Bytes {
+    if chunks.len() == 1 {
+        return chunks.pop().unwrap_or_default(); // Single chunk is not copied
+    }
+    let mut buf = BytesMut::with_capacity(chunks.iter().map(Bytes::len).sum());
+    for chunk in chunks {
+        buf.extend_from_slice(&chunk);
+    }
+    buf.freeze()
+}
+
 enum InnerBody {
     Bytes(Bytes),
     Stream(BodyStream),
+    Chunks(Vec<Bytes>),
 }
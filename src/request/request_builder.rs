@@ -1,20 +1,30 @@
+use crate::allow_threads::AllowThreads;
 use crate::client::internal::Spawner;
 use crate::exceptions::BuilderError;
-use crate::http::HeaderMap;
-use crate::internal::json::{JsonDumpsContext, JsonHandler};
-use crate::internal::types::{Extensions, FormParams, HeaderName, HeaderValue, JsonValue, QueryParams};
+use crate::http::{HeaderMap, PATH_SEGMENT, Url, UrlType};
+use crate::internal::body_stream::{BodyStream, json_line};
+use crate::internal::digest::DigestAlgorithm;
+use crate::internal::json::{JsonHandler, json_dumps};
+use crate::internal::types::{Extensions, FormParams, HeaderName, HeaderValue, QueryParams, RequestVersion};
 use crate::middleware::NextInner;
 use crate::multipart::FormBuilder;
 use crate::request::consumed_request::{ConsumedRequest, SyncConsumedRequest};
 use crate::request::request::RequestData;
+use crate::request::request_template::{RequestTemplate, SyncRequestTemplate};
 use crate::request::stream_request::{StreamRequest, SyncStreamRequest};
 use crate::request::{Request, RequestBody};
-use crate::response::internal::{BodyConsumeConfig, DEFAULT_READ_BUFFER_LIMIT, StreamedReadConfig};
-use bytes::Bytes;
+use crate::response::internal::{
+    BodyConsumeConfig, DEFAULT_PREFETCH_CHUNKS, DEFAULT_READ_BUFFER_LIMIT, StreamedReadConfig,
+};
+use crate::response::{Response, SyncResponse};
+use bytes::{Bytes, BytesMut};
 use http::header::CONTENT_TYPE;
+use percent_encoding::utf8_percent_encode;
+use pyo3::coroutine::CancelHandle;
 use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::{PyTraverseError, PyVisit};
+use pyo3::types::{PyDict, PyList, PyTuple};
+use pyo3::{PyTraverseError, PyVisit, intern};
 use pyo3_bytes::PyBytes;
 use std::sync::Arc;
 use std::time::Duration;
@@ -22,13 +32,17 @@ use std::time::Duration;
 #[pyclass(subclass)]
 pub struct BaseRequestBuilder {
     inner: Option<reqwest::RequestBuilder>,
+    build_error: Option<reqwest::Error>,
     spawner: Option<Spawner>,
     body: Option<RequestBody>,
     extensions: Option<Extensions>,
+    tag: Option<String>,
     middlewares_next: Option<NextInner>,
     json_handler: Option<JsonHandler>,
     error_for_status: bool,
+    error_for_json: Option<Py<PyAny>>,
     streamed_read_buffer_limit: Option<usize>,
+    prefetch_chunks: Option<usize>,
     is_blocking: bool,
 }
 
@@ -43,13 +57,30 @@ impl RequestBuilder {
     fn build(mut slf: PyRefMut<Self>, py: Python) -> PyResult<Py<ConsumedRequest>> {
         let slf_super = slf.as_super();
         let body_config = slf_super.body_consume_config(false)?;
-        ConsumedRequest::new_py(py, slf_super.inner_build(body_config)?)
+        ConsumedRequest::new_py(py, slf_super.inner_build(py, body_config)?)
     }
 
-    fn build_streamed(mut slf: PyRefMut<Self>, py: Python) -> PyResult<Py<StreamRequest>> {
+    pub fn build_streamed(mut slf: PyRefMut<Self>, py: Python) -> PyResult<Py<StreamRequest>> {
         let slf_super = slf.as_super();
         let body_config = slf_super.body_consume_config(true)?;
-        StreamRequest::new_py(py, slf_super.inner_build(body_config)?)
+        StreamRequest::new_py(py, slf_super.inner_build(py, body_config)?)
+    }
+
+    async fn send_head_only(slf: Py<Self>, #[pyo3(cancel_handle)] cancel: CancelHandle) -> PyResult<Py<Response>> {
+        let request = Python::attach(|py| {
+            let mut slf = slf.bind(py).try_borrow_mut()?;
+            slf.as_super().streamed_read_buffer_limit = Some(0); // Body is not read at all
+            Self::build_streamed(slf, py)
+        })?;
+        let mut resp = AllowThreads(Request::send_inner(request.as_any(), cancel)).await?;
+        if let Some(body) = resp.take_body_reader()? {
+            AllowThreads(body.close()).await;
+        }
+        Python::attach(|py| Response::new_py(py, resp))
+    }
+
+    fn freeze(slf: PyRefMut<Self>, py: Python) -> PyResult<RequestTemplate> {
+        Ok(RequestTemplate::new(Self::build(slf, py)?))
     }
 }
 impl RequestBuilder {
@@ -63,13 +94,27 @@ impl SyncRequestBuilder {
     fn build(mut slf: PyRefMut<Self>, py: Python) -> PyResult<Py<SyncConsumedRequest>> {
         let slf_super = slf.as_super();
         let body_config = slf_super.body_consume_config(false)?;
-        SyncConsumedRequest::new_py(py, slf_super.inner_build(body_config)?)
+        SyncConsumedRequest::new_py(py, slf_super.inner_build(py, body_config)?)
     }
 
-    fn build_streamed(mut slf: PyRefMut<Self>, py: Python) -> PyResult<Py<SyncStreamRequest>> {
+    pub fn build_streamed(mut slf: PyRefMut<Self>, py: Python) -> PyResult<Py<SyncStreamRequest>> {
         let slf_super = slf.as_super();
         let body_config = slf_super.body_consume_config(true)?;
-        SyncStreamRequest::new_py(py, slf_super.inner_build(body_config)?)
+        SyncStreamRequest::new_py(py, slf_super.inner_build(py, body_config)?)
+    }
+
+    fn send_head_only(mut slf: PyRefMut<Self>, py: Python) -> PyResult<Py<SyncResponse>> {
+        slf.as_super().streamed_read_buffer_limit = Some(0); // Body is not read at all
+        let request = Self::build_streamed(slf, py)?;
+        let mut resp = Request::blocking_send_inner(request.as_any())?;
+        if let Some(body) = resp.take_body_reader()? {
+            SyncResponse::runtime(&resp)?.blocking_spawn(body.close());
+        }
+        SyncResponse::new_py(py, resp)
+    }
+
+    fn freeze(slf: PyRefMut<Self>, py: Python) -> PyResult<SyncRequestTemplate> {
+        Ok(SyncRequestTemplate::new(Self::build(slf, py)?))
     }
 }
 impl SyncRequestBuilder {
@@ -80,18 +125,36 @@ impl SyncRequestBuilder {
 
 #[pymethods]
 impl BaseRequestBuilder {
-    fn error_for_status(mut slf: PyRefMut<Self>, value: bool) -> PyResult<PyRefMut<Self>> {
+    #[pyo3(signature = (enable=true))]
+    fn error_for_status(mut slf: PyRefMut<Self>, enable: bool) -> PyResult<PyRefMut<Self>> {
         slf.check_inner()?;
-        slf.error_for_status = value;
+        slf.error_for_status = enable;
         Ok(slf)
     }
 
+    fn error_for_json(mut slf: PyRefMut<Self>, predicate: Option<Py<PyAny>>) -> PyResult<PyRefMut<Self>> {
+        slf.check_inner()?;
+        slf.error_for_json = predicate;
+        Ok(slf)
+    }
+
+    #[getter]
+    fn get_url(&mut self) -> PyResult<Url> {
+        self.apply_request(|req| req.url().clone().into())
+    }
+
+    #[setter]
+    fn set_url(&mut self, value: UrlType) -> PyResult<()> {
+        self.apply_request(|req| *req.url_mut() = value.0)
+    }
+
     fn header(slf: PyRefMut<Self>, name: HeaderName, value: HeaderValue) -> PyResult<PyRefMut<Self>> {
         Self::apply(slf, |builder| Ok(builder.header(name.0, value.0)))
     }
 
     fn headers(slf: PyRefMut<'_, Self>, headers: HeaderMap) -> PyResult<PyRefMut<'_, Self>> {
-        Self::apply(slf, |builder| Ok(builder.headers(headers.try_take_inner()?)))
+        let headers = headers.try_take_inner()?;
+        Self::apply(slf, |builder| Ok(builder.headers(headers)))
     }
 
     fn basic_auth(slf: PyRefMut<Self>, username: String, password: Option<String>) -> PyResult<PyRefMut<Self>> {
@@ -102,6 +165,10 @@ impl BaseRequestBuilder {
         Self::apply(slf, |builder| Ok(builder.bearer_auth(token)))
     }
 
+    fn version(slf: PyRefMut<Self>, value: RequestVersion) -> PyResult<PyRefMut<Self>> {
+        Self::apply(slf, |builder| Ok(builder.version(value.0)))
+    }
+
     fn body_bytes(mut slf: PyRefMut<Self>, body: PyBytes) -> PyResult<PyRefMut<Self>> {
         slf.check_inner()?;
         slf.body = Some(RequestBody::from_bytes(body));
@@ -114,21 +181,34 @@ impl BaseRequestBuilder {
         Ok(slf)
     }
 
-    fn body_json<'py>(mut slf: PyRefMut<'py, Self>, data: Py<PyAny>, py: Python) -> PyResult<PyRefMut<'py, Self>> {
+    fn body_json<'py>(mut slf: PyRefMut<'py, Self>, body: Py<PyAny>, py: Python) -> PyResult<PyRefMut<'py, Self>> {
+        slf.check_inner()?;
+        let bytes = json_dumps(py, body, slf.json_handler.as_ref())?;
+        slf.body = Some(RequestBody::from(bytes));
+        Self::apply(slf, |builder| Ok(builder.header(CONTENT_TYPE, "application/json")))
+    }
+
+    fn body_json_lines<'py>(mut slf: PyRefMut<'py, Self>, items: Bound<'py, PyAny>) -> PyResult<PyRefMut<'py, Self>> {
         slf.check_inner()?;
-        let bytes = if let Some(handler) = slf.json_handler.as_ref()
-            && handler.has_dumps()
-        {
-            handler.call_dumps(py, JsonDumpsContext { data })?
+        let py = items.py();
+        let json_handler = slf.json_handler.as_ref().map(|v| v.clone_ref(py));
+        let body = if items.is_instance_of::<PyList>() || items.is_instance_of::<PyTuple>() {
+            let mut buf = BytesMut::new();
+            for item in items.try_iter()? {
+                buf.extend_from_slice(&json_line(py, item?.unbind(), json_handler.as_ref())?);
+            }
+            RequestBody::from(buf.freeze())
         } else {
-            let json_val: JsonValue = data.bind(py).extract()?;
-            slf.py().detach(|| {
-                serde_json::to_vec(&json_val)
-                    .map(Bytes::from)
-                    .map_err(|e| PyValueError::new_err(e.to_string()))
-            })?
+            RequestBody::from(BodyStream::new_json_lines(items, json_handler)?)
         };
-        slf.body = Some(RequestBody::from(bytes));
+        slf.body = Some(body);
+        Self::apply(slf, |builder| Ok(builder.header(CONTENT_TYPE, "application/x-ndjson")))
+    }
+
+    fn body_json_stream<'py>(mut slf: PyRefMut<'py, Self>, items: Bound<'py, PyAny>) -> PyResult<PyRefMut<'py, Self>> {
+        slf.check_inner()?;
+        let json_handler = slf.json_handler.as_ref().map(|v| v.clone_ref(items.py()));
+        slf.body = Some(RequestBody::from(BodyStream::new_json_array(items, json_handler)?));
         Self::apply(slf, |builder| Ok(builder.header(CONTENT_TYPE, "application/json")))
     }
 
@@ -138,13 +218,58 @@ impl BaseRequestBuilder {
         Ok(slf)
     }
 
+    fn add_digest(mut slf: PyRefMut<Self>, algorithm: DigestAlgorithm) -> PyResult<PyRefMut<Self>> {
+        let body = match slf.body.as_ref() {
+            Some(body) => body.bytes(slf.py())?.map(Some),
+            None => slf.apply_request(|req| match req.body() {
+                Some(body) => body.as_bytes().map(|b| Some(Bytes::copy_from_slice(b))),
+                None => Some(None),
+            })?,
+        };
+        let Some(body) = body else {
+            return Err(PyValueError::new_err(
+                "Digest requires a buffered body, streamed bodies are not supported",
+            ));
+        };
+        let value = http::HeaderValue::from_str(&algorithm.header_value(body.as_deref().unwrap_or_default()))
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        slf.apply_request(|req| req.headers_mut().insert("digest", value))?;
+        Ok(slf)
+    }
+
+    fn prefix_path<'py>(mut slf: PyRefMut<'py, Self>, prefix: &str) -> PyResult<PyRefMut<'py, Self>> {
+        let prefix = prefix
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| utf8_percent_encode(segment, PATH_SEGMENT).to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+        slf.apply_request(|req| {
+            let path = format!("/{}{}", prefix, req.url().path());
+            req.url_mut().set_path(&path);
+        })?;
+        Ok(slf)
+    }
+
     fn query<'py>(slf: PyRefMut<'py, Self>, query: Bound<'_, PyAny>) -> PyResult<PyRefMut<'py, Self>> {
         let query = query.extract::<QueryParams>()?.0;
         Self::apply(slf, |builder| Ok(builder.query(&query)))
     }
 
-    fn timeout(slf: PyRefMut<Self>, timeout: Duration) -> PyResult<PyRefMut<Self>> {
-        Self::apply(slf, |builder| Ok(builder.timeout(timeout)))
+    fn query_replace<'py>(mut slf: PyRefMut<'py, Self>, query: Bound<'_, PyAny>) -> PyResult<PyRefMut<'py, Self>> {
+        let query = query.extract::<QueryParams>()?.0;
+        slf.apply_request(|req| req.url_mut().set_query(None))?;
+        Self::apply(slf, |builder| Ok(builder.query(&query)))
+    }
+
+    fn timeout(mut slf: PyRefMut<Self>, timeout: Option<Duration>) -> PyResult<PyRefMut<Self>> {
+        match timeout {
+            Some(timeout) => Self::apply(slf, |builder| Ok(builder.timeout(timeout))),
+            None => {
+                slf.apply_request(|req| *req.timeout_mut() = None)?; // Also clears the client default timeout
+                Ok(slf)
+            }
+        }
     }
 
     fn multipart<'py>(slf: PyRefMut<'py, Self>, multipart: Bound<'_, FormBuilder>) -> PyResult<PyRefMut<'py, Self>> {
@@ -170,6 +295,12 @@ impl BaseRequestBuilder {
         Ok(slf)
     }
 
+    fn tag(mut slf: PyRefMut<'_, Self>, name: String) -> PyResult<PyRefMut<'_, Self>> {
+        slf.check_inner()?;
+        slf.tag = Some(name);
+        Ok(slf)
+    }
+
     fn with_middleware<'py>(
         mut slf: PyRefMut<'py, Self>,
         middleware: Bound<'py, PyAny>,
@@ -182,6 +313,39 @@ impl BaseRequestBuilder {
         Ok(slf)
     }
 
+    #[pyo3(signature = (max_retries, backoff, *, retry_statuses=Vec::new()))]
+    fn retry(
+        slf: PyRefMut<'_, Self>,
+        max_retries: usize,
+        backoff: Duration,
+        retry_statuses: Vec<u16>,
+    ) -> PyResult<PyRefMut<'_, Self>> {
+        slf.check_inner()?;
+        let py = slf.py();
+        let retry_module = py.import("pyreqwest.middleware.retry")?;
+        let policy_kwargs = PyDict::new(py);
+        policy_kwargs.set_item(intern!(py, "max_attempts"), max_retries.saturating_add(1))?;
+        policy_kwargs.set_item(intern!(py, "backoff"), backoff)?;
+        policy_kwargs.set_item(intern!(py, "backoff_multiplier"), 1.0)?; // Fixed backoff
+        policy_kwargs.set_item(intern!(py, "retry_statuses"), retry_statuses)?;
+        policy_kwargs.set_item(intern!(py, "idempotent_only"), false)?; // Explicitly enabled for the request
+        let policy = retry_module
+            .getattr(intern!(py, "RetryPolicy"))?
+            .call((), Some(&policy_kwargs))?;
+
+        let middleware_name = if slf.is_blocking {
+            intern!(py, "SyncRetryMiddleware")
+        } else {
+            intern!(py, "RetryMiddleware")
+        };
+        let middleware_kwargs = PyDict::new(py);
+        middleware_kwargs.set_item(intern!(py, "require_body_copy"), true)?;
+        let middleware = retry_module
+            .getattr(middleware_name)?
+            .call((policy,), Some(&middleware_kwargs))?;
+        Self::with_middleware(slf, middleware)
+    }
+
     fn streamed_read_buffer_limit(mut slf: PyRefMut<'_, Self>, value: usize) -> PyResult<PyRefMut<'_, Self>> {
         slf.check_inner()?;
         slf.streamed_read_buffer_limit = Some(value);
@@ -193,6 +357,20 @@ impl BaseRequestBuilder {
         DEFAULT_READ_BUFFER_LIMIT
     }
 
+    fn prefetch_chunks(mut slf: PyRefMut<'_, Self>, value: usize) -> PyResult<PyRefMut<'_, Self>> {
+        slf.check_inner()?;
+        if value == 0 {
+            return Err(PyValueError::new_err("prefetch_chunks must be at least 1"));
+        }
+        slf.prefetch_chunks = Some(value);
+        Ok(slf)
+    }
+
+    #[staticmethod]
+    fn default_prefetch_chunks() -> usize {
+        DEFAULT_PREFETCH_CHUNKS
+    }
+
     // :NOCOV_START
     fn __traverse__(&self, visit: PyVisit<'_>) -> Result<(), PyTraverseError> {
         if let Some(extensions) = &self.extensions {
@@ -204,6 +382,9 @@ impl BaseRequestBuilder {
         if let Some(json_handler) = &self.json_handler {
             json_handler.__traverse__(&visit)?;
         }
+        if let Some(error_for_json) = &self.error_for_json {
+            visit.call(error_for_json)?;
+        }
         if let Some(body) = &self.body {
             body.__traverse__(visit)?;
         }
@@ -217,6 +398,7 @@ impl BaseRequestBuilder {
         self.extensions = None;
         self.middlewares_next = None;
         self.json_handler = None;
+        self.error_for_json = None;
     } // :NOCOV_END
 }
 impl BaseRequestBuilder {
@@ -230,28 +412,43 @@ impl BaseRequestBuilder {
     ) -> Self {
         BaseRequestBuilder {
             inner: Some(inner),
+            build_error: None,
             spawner: Some(spawner),
             body: None,
             extensions: None,
+            tag: None,
             middlewares_next,
             json_handler,
             error_for_status,
+            error_for_json: None,
             streamed_read_buffer_limit: None,
+            prefetch_chunks: None,
             is_blocking,
         }
     }
 
-    fn inner_build(&mut self, consume_body: BodyConsumeConfig) -> PyResult<Request> {
+    fn inner_build(&mut self, py: Python, consume_body: BodyConsumeConfig) -> PyResult<Request> {
         let request = self
-            .inner
-            .take()
-            .ok_or_else(|| PyRuntimeError::new_err("Request was already built"))?
+            .take_inner()?
             .build()
             .map_err(|e| BuilderError::from_err("Failed to build request", &e))?;
 
         if request.body().is_some() && self.body.is_some() {
             return Err(BuilderError::from_causes("Can not set body when multipart or form is used", vec![]));
         }
+        if self.error_for_json.is_some() && matches!(consume_body, BodyConsumeConfig::Streamed(_)) {
+            return Err(BuilderError::from_causes("Can not use error_for_json with a streamed request", vec![]));
+        }
+
+        let mut extensions = self.extensions.take();
+        if let Some(tag) = self.tag.take() {
+            // Tag is kept separately so it survives extensions() being called after tag()
+            extensions
+                .get_or_insert_with(|| Extensions(PyDict::new(py).unbind()))
+                .0
+                .bind(py)
+                .set_item(intern!(py, "tag"), tag)?;
+        }
 
         let request_data = RequestData {
             spawner: self
@@ -259,10 +456,11 @@ impl BaseRequestBuilder {
                 .take()
                 .ok_or_else(|| PyRuntimeError::new_err("Request was already built"))?,
             reqwest: request,
-            extensions: self.extensions.take(),
+            extensions,
             body_consume_config: consume_body,
             json_handler: self.json_handler.take(),
             error_for_status: self.error_for_status,
+            error_for_json: self.error_for_json.take(),
         };
         Ok(Request::new(request_data, self.body.take(), self.middlewares_next.take()))
     }
@@ -273,6 +471,9 @@ impl BaseRequestBuilder {
                 read_buffer_limit: self
                     .streamed_read_buffer_limit
                     .unwrap_or(BaseRequestBuilder::default_streamed_read_buffer_limit()),
+                prefetch_chunks: self
+                    .prefetch_chunks
+                    .unwrap_or(BaseRequestBuilder::default_prefetch_chunks()),
             };
             Ok(BodyConsumeConfig::Streamed(config))
         } else {
@@ -285,14 +486,31 @@ impl BaseRequestBuilder {
     }
 
     pub fn inner_headers(&mut self, headers: &HeaderMap) -> PyResult<&mut Self> {
-        self.apply_inner(|b| Ok(b.headers(headers.try_clone_inner()?)))
+        let headers = headers.try_clone_inner()?;
+        self.apply_inner(|b| Ok(b.headers(headers)))
+    }
+
+    pub fn inner_query(&mut self, query: &QueryParams) -> PyResult<&mut Self> {
+        self.apply_inner(|b| Ok(b.query(&query.0)))
     }
 
     fn check_inner(&self) -> PyResult<()> {
-        self.inner
-            .as_ref()
-            .ok_or_else(|| PyRuntimeError::new_err("Request was already built"))
-            .map(|_| ())
+        match self.inner {
+            Some(_) => Ok(()),
+            None => Err(self.missing_inner_error()),
+        }
+    }
+
+    fn take_inner(&mut self) -> PyResult<reqwest::RequestBuilder> {
+        self.inner.take().ok_or_else(|| self.missing_inner_error())
+    }
+
+    fn missing_inner_error(&self) -> PyErr {
+        match &self.build_error {
+            // Builder was left with an invalid request, keep failing with the original error
+            Some(e) => BuilderError::from_err("Failed to build request", e),
+            None => PyRuntimeError::new_err("Request was already built"),
+        }
     }
 
     fn apply<F>(mut slf: PyRefMut<Self>, fun: F) -> PyResult<PyRefMut<Self>>
@@ -300,10 +518,7 @@ impl BaseRequestBuilder {
         F: FnOnce(reqwest::RequestBuilder) -> PyResult<reqwest::RequestBuilder>,
         F: Send,
     {
-        let builder = slf
-            .inner
-            .take()
-            .ok_or_else(|| PyRuntimeError::new_err("Request was already built"))?;
+        let builder = slf.take_inner()?;
         slf.inner = Some(slf.py().detach(|| fun(builder))?);
         Ok(slf)
     }
@@ -312,11 +527,27 @@ impl BaseRequestBuilder {
     where
         F: FnOnce(reqwest::RequestBuilder) -> PyResult<reqwest::RequestBuilder>,
     {
-        let builder = self
-            .inner
-            .take()
-            .ok_or_else(|| PyRuntimeError::new_err("Request was already built"))?;
+        let builder = self.take_inner()?;
         self.inner = Some(fun(builder)?);
         Ok(self)
     }
+
+    fn apply_request<T, F>(&mut self, fun: F) -> PyResult<T>
+    where
+        F: FnOnce(&mut reqwest::Request) -> T,
+    {
+        let (client, request) = self.take_inner()?.build_split();
+        match request {
+            Ok(mut request) => {
+                let res = fun(&mut request);
+                self.inner = Some(reqwest::RequestBuilder::from_parts(client, request));
+                Ok(res)
+            }
+            Err(e) => {
+                let err = BuilderError::from_err("Failed to build request", &e);
+                self.build_error = Some(e);
+                Err(err)
+            }
+        }
+    }
 }
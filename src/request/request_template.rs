@@ -0,0 +1,58 @@
+use crate::request::{ConsumedRequest, SyncConsumedRequest};
+use pyo3::prelude::*;
+
+#[pyclass(frozen)]
+pub struct RequestTemplate {
+    request: Py<ConsumedRequest>,
+}
+
+#[pyclass(frozen)]
+pub struct SyncRequestTemplate {
+    request: Py<SyncConsumedRequest>,
+}
+
+#[pymethods]
+impl RequestTemplate {
+    fn clone(&self, py: Python) -> PyResult<Py<ConsumedRequest>> {
+        let request = self.request.bind(py).try_borrow()?;
+        ConsumedRequest::new_py(py, request.as_super().try_clone_inner(py, None)?)
+    }
+
+    fn __repr__(&self, py: Python) -> PyResult<String> {
+        let request = self.request.bind(py).try_borrow()?;
+        Ok(format!("RequestTemplate({})", request.as_super().__repr__(py)?))
+    }
+
+    // :NOCOV_START
+    fn __traverse__(&self, visit: pyo3::PyVisit<'_>) -> Result<(), pyo3::PyTraverseError> {
+        visit.call(&self.request)
+    } // :NOCOV_END
+}
+impl RequestTemplate {
+    pub fn new(request: Py<ConsumedRequest>) -> Self {
+        RequestTemplate { request }
+    }
+}
+
+#[pymethods]
+impl SyncRequestTemplate {
+    fn clone(&self, py: Python) -> PyResult<Py<SyncConsumedRequest>> {
+        let request = self.request.bind(py).try_borrow()?;
+        SyncConsumedRequest::new_py(py, request.as_super().try_clone_inner(py, None)?)
+    }
+
+    fn __repr__(&self, py: Python) -> PyResult<String> {
+        let request = self.request.bind(py).try_borrow()?;
+        Ok(format!("SyncRequestTemplate({})", request.as_super().__repr__(py)?))
+    }
+
+    // :NOCOV_START
+    fn __traverse__(&self, visit: pyo3::PyVisit<'_>) -> Result<(), pyo3::PyTraverseError> {
+        visit.call(&self.request)
+    } // :NOCOV_END
+}
+impl SyncRequestTemplate {
+    pub fn new(request: Py<SyncConsumedRequest>) -> Self {
+        SyncRequestTemplate { request }
+    }
+}
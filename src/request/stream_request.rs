@@ -41,7 +41,9 @@ impl StreamRequest {
                 .try_borrow_mut()?
                 .take_body_reader()
         })?;
-        AllowThreads(body.close()).await;
+        if let Some(body) = body {
+            AllowThreads(body.close()).await;
+        }
         Ok(())
     }
 
@@ -101,8 +103,9 @@ impl SyncStreamRequest {
             .into_bound(py)
             .into_super()
             .try_borrow_mut()?;
-        let body = resp.take_body_reader()?;
-        SyncResponse::runtime(&resp)?.blocking_spawn(body.close());
+        if let Some(body) = resp.take_body_reader()? {
+            SyncResponse::runtime(&resp)?.blocking_spawn(body.close());
+        }
         Ok(())
     }
 
@@ -1,5 +1,5 @@
 use crate::client::RuntimeHandle;
-use crate::exceptions::utils::map_read_error;
+use crate::exceptions::utils::{RequestContext, add_partial_body, map_read_error};
 use bytes::{Bytes, BytesMut};
 use futures_util::FutureExt;
 use http_body_util::BodyExt;
@@ -12,6 +12,7 @@ use tokio::sync::OwnedSemaphorePermit;
 use tokio_util::sync::CancellationToken;
 
 pub const DEFAULT_READ_BUFFER_LIMIT: usize = 65536;
+pub const DEFAULT_PREFETCH_CHUNKS: usize = 1;
 
 pub struct BodyReader {
     body_receiver: Option<Receiver>,
@@ -27,20 +28,27 @@ impl BodyReader {
         mut request_semaphore_permit: Option<OwnedSemaphorePermit>,
         read_config: BodyConsumeConfig,
         runtime: RuntimeHandle,
+        request_context: Option<RequestContext>,
     ) -> PyResult<(Self, http::response::Parts)> {
-        let buffer_limit = match read_config {
-            BodyConsumeConfig::FullyConsumed => None,
-            BodyConsumeConfig::Streamed(cfg) => Some(cfg.read_buffer_limit),
+        let (buffer_limit, prefetch_chunks) = match read_config {
+            BodyConsumeConfig::FullyConsumed => (None, DEFAULT_PREFETCH_CHUNKS),
+            BodyConsumeConfig::Streamed(cfg) => (Some(cfg.read_buffer_limit), cfg.prefetch_chunks),
         };
 
-        let (init_chunks, has_more) = Self::read_limit(&mut response, buffer_limit).await?;
+        let (init_chunks, has_more) = Self::read_limit(&mut response, buffer_limit, request_context.as_ref()).await?;
         let (head, body) = Self::response_parts(response);
 
         let mut body_receiver: Option<Receiver> = None;
         if let Some(buffer_limit) = buffer_limit {
             if has_more {
-                body_receiver =
-                    Some(Reader::start(body, request_semaphore_permit.take(), buffer_limit, runtime.clone()));
+                body_receiver = Some(Reader::start(
+                    body,
+                    request_semaphore_permit.take(),
+                    buffer_limit,
+                    prefetch_chunks,
+                    request_context,
+                    runtime.clone(),
+                ));
             }
         } else {
             assert!(!has_more, "Should have fully consumed the response");
@@ -112,8 +120,12 @@ impl BodyReader {
             None => BytesMut::new(),
         };
 
-        while let Some(chunk) = self.next_chunk(cancel).await? {
-            bytes.extend_from_slice(&chunk);
+        loop {
+            match self.next_chunk(cancel).await {
+                Ok(Some(chunk)) => bytes.extend_from_slice(&chunk),
+                Ok(None) => break,
+                Err(err) => return Err(add_partial_body(err, bytes.freeze())),
+            }
         }
 
         let bytes = bytes.freeze();
@@ -135,15 +147,17 @@ impl BodyReader {
         let mut remaining = amount;
 
         while remaining > 0 {
-            if let Some(mut chunk) = self.next_chunk(cancel).await? {
-                if chunk.len() > remaining {
-                    let extra = chunk.split_off(remaining);
-                    self.chunks.push_front(extra);
+            match self.next_chunk(cancel).await {
+                Ok(Some(mut chunk)) => {
+                    if chunk.len() > remaining {
+                        let extra = chunk.split_off(remaining);
+                        self.chunks.push_front(extra);
+                    }
+                    collected.extend_from_slice(&chunk);
+                    remaining -= chunk.len();
                 }
-                collected.extend_from_slice(&chunk);
-                remaining -= chunk.len();
-            } else {
-                break; // No more data
+                Ok(None) => break, // No more data
+                Err(err) => return Err(add_partial_body(err, collected.freeze())),
             }
         }
 
@@ -171,6 +185,7 @@ impl BodyReader {
     async fn read_limit(
         response: &mut reqwest::Response,
         byte_limit: Option<usize>,
+        request_context: Option<&RequestContext>,
     ) -> PyResult<(VecDeque<Bytes>, bool)> {
         if byte_limit == Some(0) {
             return Ok((VecDeque::new(), true));
@@ -181,7 +196,17 @@ impl BodyReader {
         let mut consumed_bytes = 0;
 
         while has_more {
-            if let Some(chunk) = response.chunk().await.map_err(map_read_error)? {
+            let chunk = match response.chunk().await {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    let partial_body = init_chunks.iter().fold(BytesMut::new(), |mut buf, chunk| {
+                        buf.extend_from_slice(chunk);
+                        buf
+                    });
+                    return Err(add_partial_body(map_read_error(e, request_context), partial_body.freeze()));
+                }
+            };
+            if let Some(chunk) = chunk {
                 consumed_bytes += chunk.len();
                 init_chunks.push_back(chunk);
 
@@ -228,9 +253,11 @@ impl Reader {
         mut body: reqwest::Body,
         mut request_semaphore_permit: Option<OwnedSemaphorePermit>,
         buffer_size: usize,
+        prefetch_chunks: usize,
+        request_context: Option<RequestContext>,
         runtime: RuntimeHandle,
     ) -> Receiver {
-        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        let (tx, rx) = tokio::sync::mpsc::channel(prefetch_chunks.max(1));
         let close_token = CancellationToken::new();
         let close_token_child = close_token.child_token();
 
@@ -244,8 +271,14 @@ impl Reader {
         let _ = runtime.spawn(async move {
             let fut = async move {
                 loop {
-                    match body.frame().await.transpose().map_err(map_read_error) {
+                    match body
+                        .frame()
+                        .await
+                        .transpose()
+                        .map_err(|e| map_read_error(e, request_context.as_ref()))
+                    {
                         Err(err) => {
+                            reader.finalize().await; // Deliver what was received before the error
                             let _ = reader.tx.send(Err(err)).await;
                             break; // Stop on error
                         }
@@ -312,11 +345,13 @@ pub enum BodyConsumeConfig {
 #[derive(Debug, Clone, Copy)]
 pub struct StreamedReadConfig {
     pub read_buffer_limit: usize,
+    pub prefetch_chunks: usize,
 }
 impl Default for StreamedReadConfig {
     fn default() -> Self {
         Self {
             read_buffer_limit: DEFAULT_READ_BUFFER_LIMIT,
+            prefetch_chunks: DEFAULT_PREFETCH_CHUNKS,
         }
     }
 }
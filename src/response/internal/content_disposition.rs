@@ -0,0 +1,77 @@
+use percent_encoding::percent_decode_str;
+
+// Suggested filename from a Content-Disposition header value.
+// Extended "filename*" parameter (RFC 5987) takes precedence over plain "filename".
+pub fn content_disposition_filename(value: &str) -> Option<String> {
+    let mut filename = None;
+    for (name, val) in params(value) {
+        if name.eq_ignore_ascii_case("filename*") {
+            if let Some(decoded) = decode_ext_value(&val) {
+                return Some(decoded);
+            }
+        } else if name.eq_ignore_ascii_case("filename") && filename.is_none() {
+            filename = Some(val);
+        }
+    }
+    filename
+}
+
+// Parameters after the disposition type, quoted values are unescaped
+fn params(value: &str) -> Vec<(String, String)> {
+    let mut res = Vec::new();
+    let mut chars = value.chars().peekable();
+
+    // Skip disposition type
+    for c in chars.by_ref() {
+        if c == ';' {
+            break;
+        }
+    }
+
+    loop {
+        let name: String = chars.by_ref().take_while(|c| *c != '=').collect();
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return res;
+        }
+
+        while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+
+        let mut val = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => val.extend(chars.next()),
+                    '"' => break,
+                    c => val.push(c),
+                }
+            }
+            for c in chars.by_ref() {
+                if c == ';' {
+                    break;
+                }
+            }
+        } else {
+            val = chars.by_ref().take_while(|c| *c != ';').collect();
+            val = val.trim().to_string();
+        }
+        res.push((name, val));
+    }
+}
+
+// Decode RFC 5987 ext-value: charset'language'percent-encoded
+fn decode_ext_value(value: &str) -> Option<String> {
+    let mut parts = value.splitn(3, '\'');
+    let charset = parts.next()?;
+    let _language = parts.next()?;
+    let encoded = parts.next()?;
+
+    let decoded = percent_decode_str(encoded);
+    if charset.eq_ignore_ascii_case("utf-8") {
+        decoded.decode_utf8().ok().map(|s| s.into_owned())
+    } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+        Some(decoded.map(char::from).collect())
+    } else {
+        None
+    }
+}
@@ -0,0 +1,37 @@
+use encoding_rs::{Encoding, UTF_8, UTF_16BE, UTF_16LE};
+
+const SNIFF_LIMIT: usize = 1024; // Like in browsers, only the start of the document is scanned
+
+// Encoding declared by a <meta charset="..."> or <meta http-equiv="Content-Type" content="...; charset=..."> tag
+pub fn html_meta_charset(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = String::from_utf8_lossy(bytes.get(..SNIFF_LIMIT).unwrap_or(bytes)).to_ascii_lowercase();
+    let mut rest = head.as_str();
+    while let Some(start) = rest.find("<meta") {
+        let tag = rest.get(start..).unwrap_or_default();
+        let (tag, next) = tag.split_once('>').unwrap_or((tag, ""));
+        if let Some(encoding) = meta_tag_charset(tag) {
+            return Some(encoding);
+        }
+        rest = next;
+    }
+    None
+}
+
+fn meta_tag_charset(tag: &str) -> Option<&'static Encoding> {
+    let (_, value) = tag.split_once("charset")?;
+    let value = value
+        .trim_start()
+        .strip_prefix('=')?
+        .trim_start()
+        .trim_start_matches(['"', '\'']);
+    let label = value
+        .split(|c: char| matches!(c, '"' | '\'' | ';' | '/') || c.is_ascii_whitespace())
+        .next()?;
+    let encoding = Encoding::for_label(label.as_bytes())?;
+    // The document was readable as ASCII so it can not be UTF-16, same as in the HTML spec
+    Some(if encoding == UTF_16LE || encoding == UTF_16BE {
+        UTF_8
+    } else {
+        encoding
+    })
+}
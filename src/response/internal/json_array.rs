@@ -0,0 +1,93 @@
+use crate::exceptions::JSONDecodeError;
+use pyo3::PyResult;
+use serde_json::json;
+
+// Incrementally splits a top level JSON array into its elements while body chunks arrive
+#[derive(Default)]
+pub struct JsonArrayParser {
+    state: State,
+    element: Vec<u8>,
+    depth: usize,
+    in_string: bool,
+    escape: bool,
+    pos: usize,
+}
+
+#[derive(Default, PartialEq)]
+enum State {
+    #[default]
+    Start,
+    FirstElement,
+    NextElement,
+    End,
+}
+
+impl JsonArrayParser {
+    pub fn feed(&mut self, chunk: &[u8]) -> PyResult<Vec<serde_json::Value>> {
+        let mut values = vec![];
+        for &byte in chunk {
+            self.pos += 1;
+            match self.state {
+                State::Start | State::End => {
+                    if byte.is_ascii_whitespace() {
+                        continue;
+                    }
+                    if self.state == State::Start && byte == b'[' {
+                        self.state = State::FirstElement;
+                    } else {
+                        return Err(self.error("Expected a single JSON array"));
+                    }
+                }
+                State::FirstElement | State::NextElement => {
+                    if self.in_string {
+                        if self.escape {
+                            self.escape = false;
+                        } else if byte == b'\\' {
+                            self.escape = true;
+                        } else if byte == b'"' {
+                            self.in_string = false;
+                        }
+                        self.element.push(byte);
+                        continue;
+                    }
+                    match byte {
+                        b',' | b']' if self.depth == 0 => {
+                            let is_empty = self.element.iter().all(u8::is_ascii_whitespace);
+                            if is_empty && (byte == b',' || self.state == State::NextElement) {
+                                return Err(self.error("Expected JSON array element"));
+                            }
+                            if !is_empty {
+                                values.push(self.parse_element()?);
+                            }
+                            self.state = if byte == b',' { State::NextElement } else { State::End };
+                            continue;
+                        }
+                        b'"' => self.in_string = true,
+                        b'[' | b'{' => self.depth += 1,
+                        b']' | b'}' => self.depth = self.depth.saturating_sub(1),
+                        _ => {}
+                    }
+                    self.element.push(byte);
+                }
+            }
+        }
+        Ok(values)
+    }
+
+    pub fn finish(&self) -> PyResult<()> {
+        if self.state != State::End {
+            return Err(self.error("Unexpected end of JSON array"));
+        }
+        Ok(())
+    }
+
+    fn parse_element(&mut self) -> PyResult<serde_json::Value> {
+        let element = std::mem::take(&mut self.element);
+        serde_json::from_slice(&element).map_err(|e| self.error(&e.to_string()))
+    }
+
+    fn error(&self, message: &str) -> pyo3::PyErr {
+        let details = json!({"pos": self.pos.saturating_sub(1), "doc": "", "causes": serde_json::Value::Null});
+        JSONDecodeError::from_custom(message, details)
+    }
+}
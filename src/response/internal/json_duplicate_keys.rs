@@ -0,0 +1,117 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::Deserializer;
+use serde::de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Number, Value};
+use std::fmt;
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum DuplicateKeyPolicy {
+    Error,
+    First,
+    #[default]
+    Last,
+}
+impl<'py> FromPyObject<'py> for DuplicateKeyPolicy {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<&str>()? {
+            "error" => Ok(DuplicateKeyPolicy::Error),
+            "first" => Ok(DuplicateKeyPolicy::First),
+            "last" => Ok(DuplicateKeyPolicy::Last),
+            other => Err(PyValueError::new_err(format!(
+                "Invalid on_duplicate_key: {}, expected 'error', 'first' or 'last'",
+                other
+            ))),
+        }
+    }
+}
+
+// Parses JSON like serde_json::from_slice but duplicate object keys are handled by the policy
+pub fn json_from_slice(bytes: &[u8], policy: DuplicateKeyPolicy) -> serde_json::Result<Value> {
+    if policy == DuplicateKeyPolicy::Last {
+        return serde_json::from_slice(bytes); // serde_json default behavior
+    }
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let value = ValueSeed(policy).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+#[derive(Clone, Copy)]
+struct ValueSeed(DuplicateKeyPolicy);
+
+impl<'de> DeserializeSeed<'de> for ValueSeed {
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ValueSeed {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any valid JSON value")
+    }
+
+    fn visit_bool<E: Error>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E: Error>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::Number(value.into()))
+    }
+
+    fn visit_u64<E: Error>(self, value: u64) -> Result<Value, E> {
+        Ok(Value::Number(value.into()))
+    }
+
+    fn visit_f64<E: Error>(self, value: f64) -> Result<Value, E> {
+        Ok(Number::from_f64(value).map_or(Value::Null, Value::Number))
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Value, E> {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn visit_string<E: Error>(self, value: String) -> Result<Value, E> {
+        Ok(Value::String(value))
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(value) = seq.next_element_seed(self)? {
+            values.push(value);
+        }
+        Ok(Value::Array(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut values = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let value = map.next_value_seed(self)?;
+            if values.contains_key(&key) {
+                match self.0 {
+                    DuplicateKeyPolicy::Error => return Err(A::Error::custom(format!("duplicate key `{}`", key))),
+                    DuplicateKeyPolicy::First => continue,
+                    DuplicateKeyPolicy::Last => {}
+                }
+            }
+            values.insert(key, value);
+        }
+        Ok(Value::Object(values))
+    }
+}
@@ -0,0 +1,101 @@
+use crate::exceptions::JSONDecodeError;
+use crate::response::internal::DuplicateKeyPolicy;
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyBytes, PyDict, PyType};
+use serde_json::json;
+use std::ops::Range;
+
+// Parses JSON with the Python json module. With exact_numbers, numbers are kept exact: integers of any size as int,
+// others as Decimal (serde_json converts integers outside of i64/u64 range to f64). Python json module also accepts
+// NaN and Infinity, so the caller rejects them when not allowed. Duplicate key errors are checked by the caller.
+pub fn json_loads_python<'py>(
+    py: Python<'py>,
+    bytes: &[u8],
+    policy: DuplicateKeyPolicy,
+    exact_numbers: bool,
+) -> PyResult<Bound<'py, PyAny>> {
+    static JSON_LOADS: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+    static JSON_DECODE_ERROR: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+    static DECIMAL: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+
+    let kwargs = PyDict::new(py);
+    if exact_numbers {
+        kwargs.set_item("parse_float", DECIMAL.import(py, "decimal", "Decimal")?)?;
+    }
+    if policy == DuplicateKeyPolicy::First {
+        kwargs.set_item("object_pairs_hook", wrap_pyfunction!(keep_first_pairs, py)?)?;
+    }
+
+    let loads = JSON_LOADS.import(py, "json", "loads")?;
+    match loads.call((PyBytes::new(py, bytes),), Some(&kwargs)) {
+        Ok(value) => Ok(value),
+        Err(e) if e.is_instance(py, JSON_DECODE_ERROR.import(py, "json", "JSONDecodeError")?) => {
+            let err = e.value(py);
+            let doc = err.getattr("doc")?.extract::<String>()?;
+            let char_pos = err.getattr("pos")?.extract::<usize>()?;
+            // Byte offset like in the serde path
+            let pos = doc.char_indices().nth(char_pos).map_or(doc.len(), |(idx, _)| idx);
+            let details = json!({"pos": pos, "doc": doc, "causes": serde_json::Value::Null});
+            Err(JSONDecodeError::from_custom(&err.getattr("msg")?.extract::<String>()?, details))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+// Byte ranges of the non-standard NaN, Infinity and -Infinity tokens outside of strings
+pub fn json_constant_ranges(bytes: &[u8]) -> Vec<Range<usize>> {
+    const CONSTANTS: [&[u8]; 3] = [b"NaN", b"Infinity", b"-Infinity"];
+    let mut ranges = vec![];
+    let (mut in_string, mut escaped) = (false, false);
+    let mut pos = 0;
+    while let Some(&byte) = bytes.get(pos) {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+        } else if byte == b'"' {
+            in_string = true;
+        } else if let Some(constant) = CONSTANTS
+            .iter()
+            .find(|constant| bytes.get(pos..).is_some_and(|rest| rest.starts_with(constant)))
+        {
+            ranges.push(pos..pos + constant.len());
+            pos += constant.len();
+            continue;
+        }
+        pos += 1;
+    }
+    ranges
+}
+
+// Replaces the constants by zeros keeping the byte positions, so the document can be validated by serde_json
+pub fn replace_json_constants(bytes: &[u8], ranges: &[Range<usize>]) -> Vec<u8> {
+    let mut replaced = bytes.to_vec();
+    for range in ranges {
+        if let Some(token) = replaced.get_mut(range.clone()) {
+            token.fill(b' ');
+            if let Some(first) = token.first_mut() {
+                *first = b'0';
+            }
+        }
+    }
+    replaced
+}
+
+#[pyfunction]
+fn keep_first_pairs<'py>(
+    py: Python<'py>,
+    pairs: Vec<(Bound<'py, PyAny>, Bound<'py, PyAny>)>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for (key, value) in pairs {
+        if !dict.contains(&key)? {
+            dict.set_item(key, value)?;
+        }
+    }
+    Ok(dict)
+}
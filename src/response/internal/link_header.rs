@@ -0,0 +1,69 @@
+// Link header value (RFC 8288) parsed into links with their target and parameters.
+// Parameter names are lowercased, quoted values are unescaped.
+pub struct Link {
+    pub url: String,
+    pub params: Vec<(String, String)>,
+}
+
+impl Link {
+    // Relation types, the "rel" parameter may contain multiple space separated values
+    pub fn rels(&self) -> impl Iterator<Item = &str> {
+        self.params
+            .iter()
+            .filter(|(name, _)| name == "rel")
+            .take(1) // Occurrences after the first are ignored
+            .flat_map(|(_, val)| val.split_ascii_whitespace())
+    }
+}
+
+pub fn parse_link_header(value: &str) -> Vec<Link> {
+    let mut res = Vec::new();
+    let mut chars = value.chars().peekable();
+
+    loop {
+        // Skip to the start of the target
+        for c in chars.by_ref() {
+            if c == '<' {
+                break;
+            }
+        }
+        let url: String = chars.by_ref().take_while(|c| *c != '>').collect();
+        if chars.peek().is_none() && url.is_empty() {
+            return res;
+        }
+
+        let mut params = Vec::new();
+        loop {
+            while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+            match chars.next() {
+                Some(';') => {}
+                Some(',') | None => break,
+                Some(_) => continue, // Invalid syntax, skipped
+            }
+
+            let name: String = std::iter::from_fn(|| chars.next_if(|c| !matches!(c, '=' | ';' | ','))).collect();
+            let name = name.trim().to_ascii_lowercase();
+
+            let mut val = String::new();
+            if chars.next_if_eq(&'=').is_some() {
+                while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+                if chars.next_if_eq(&'"').is_some() {
+                    while let Some(c) = chars.next() {
+                        match c {
+                            '\\' => val.extend(chars.next()),
+                            '"' => break,
+                            c => val.push(c),
+                        }
+                    }
+                } else {
+                    val = std::iter::from_fn(|| chars.next_if(|c| !matches!(c, ';' | ','))).collect();
+                    val = val.trim().to_string();
+                }
+            }
+            if !name.is_empty() {
+                params.push((name, val));
+            }
+        }
+        res.push(Link { url, params });
+    }
+}
@@ -1,3 +1,19 @@
 mod body_reader;
+mod content_disposition;
+mod html_charset;
+mod json_array;
+mod json_duplicate_keys;
+mod json_exact;
+mod link_header;
+mod sse;
 
-pub use body_reader::{BodyConsumeConfig, BodyReader, DEFAULT_READ_BUFFER_LIMIT, StreamedReadConfig};
+pub use body_reader::{
+    BodyConsumeConfig, BodyReader, DEFAULT_PREFETCH_CHUNKS, DEFAULT_READ_BUFFER_LIMIT, StreamedReadConfig,
+};
+pub use content_disposition::content_disposition_filename;
+pub use html_charset::html_meta_charset;
+pub use json_array::JsonArrayParser;
+pub use json_duplicate_keys::{DuplicateKeyPolicy, json_from_slice};
+pub use json_exact::{json_constant_ranges, json_loads_python, replace_json_constants};
+pub use link_header::{Link, parse_link_header};
+pub use sse::{SseEvent, SseParser};
@@ -0,0 +1,93 @@
+// Incrementally parses the Server-Sent Events wire format while body chunks arrive
+#[derive(Default)]
+pub struct SseParser {
+    line: Vec<u8>,
+    skip_lf: bool,
+    started: bool,
+    event: String,
+    data: String,
+    last_id: Option<String>,
+    retry: Option<u64>,
+}
+
+pub struct SseEvent {
+    pub event: String,
+    pub data: String,
+    pub id: Option<String>,
+    pub retry: Option<u64>,
+}
+
+impl SseParser {
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = vec![];
+        for &byte in chunk {
+            if self.skip_lf {
+                self.skip_lf = false;
+                if byte == b'\n' {
+                    continue; // CRLF line ending
+                }
+            }
+            match byte {
+                b'\r' => {
+                    self.skip_lf = true;
+                    self.process_line(&mut events);
+                }
+                b'\n' => self.process_line(&mut events),
+                _ => self.line.push(byte),
+            }
+        }
+        events
+    }
+
+    fn process_line(&mut self, events: &mut Vec<SseEvent>) {
+        let mut line = std::mem::take(&mut self.line);
+        if !self.started {
+            self.started = true;
+            if line.starts_with(b"\xEF\xBB\xBF") {
+                line.drain(..3); // Strip leading BOM
+            }
+        }
+        let line = String::from_utf8_lossy(&line);
+
+        if line.is_empty() {
+            self.dispatch(events);
+            return;
+        }
+        if line.starts_with(':') {
+            return; // Comment
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line.as_ref(), ""),
+        };
+        match field {
+            "event" => self.event = value.to_string(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                self.retry = value.parse().ok()
+            }
+            _ => {} // Unknown fields are ignored
+        }
+    }
+
+    fn dispatch(&mut self, events: &mut Vec<SseEvent>) {
+        let retry = self.retry.take();
+        let event = std::mem::take(&mut self.event);
+        if self.data.is_empty() {
+            return; // Nothing to dispatch
+        }
+        let mut data = std::mem::take(&mut self.data);
+        data.pop(); // Remove the last newline
+        events.push(SseEvent {
+            event: if event.is_empty() { "message".to_string() } else { event },
+            data,
+            id: self.last_id.clone(),
+            retry,
+        });
+    }
+}
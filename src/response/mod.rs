@@ -1,8 +1,12 @@
 pub mod internal;
+mod proxy_response;
 mod response;
 mod response_body_reader;
 mod response_builder;
+mod sse;
 
+pub use proxy_response::ProxyResponse;
 pub use response::{BaseResponse, Response, SyncResponse};
 pub use response_body_reader::{BaseResponseBodyReader, ResponseBodyReader, SyncResponseBodyReader};
 pub use response_builder::ResponseBuilder;
+pub use sse::{ServerSentEvent, ServerSentEventIterator, SyncServerSentEventIterator};
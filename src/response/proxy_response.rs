@@ -0,0 +1,67 @@
+use crate::http::HeaderMap;
+use crate::internal::types::{StatusCode, Version};
+use crate::response::BaseResponseBodyReader;
+use http::header::CONNECTION;
+use pyo3::prelude::*;
+use pyo3::{PyTraverseError, PyVisit};
+
+// Connection specific headers that must not be forwarded by proxies (RFC 9110 7.6.1)
+const HOP_BY_HOP_HEADERS: [&str; 9] = [
+    "connection",
+    "keep-alive",
+    "proxy-connection",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+#[pyclass(frozen, get_all)]
+pub struct ProxyResponse {
+    status: StatusCode,
+    version: Version,
+    headers: Py<HeaderMap>,
+    body: Py<BaseResponseBodyReader>,
+}
+
+#[pymethods]
+impl ProxyResponse {
+    // :NOCOV_START
+    fn __traverse__(&self, visit: PyVisit<'_>) -> Result<(), PyTraverseError> {
+        visit.call(&self.headers)?;
+        visit.call(&self.body)
+    }
+    // :NOCOV_END
+}
+impl ProxyResponse {
+    pub fn new(status: StatusCode, version: Version, headers: Py<HeaderMap>, body: Py<BaseResponseBodyReader>) -> Self {
+        ProxyResponse {
+            status,
+            version,
+            headers,
+            body,
+        }
+    }
+}
+
+pub fn strip_hop_by_hop_headers(headers: &mut http::HeaderMap) {
+    // Headers listed in Connection are also connection specific
+    let listed: Vec<String> = headers
+        .get_all(CONNECTION)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|name| name.trim().to_ascii_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    for name in HOP_BY_HOP_HEADERS
+        .iter()
+        .copied()
+        .chain(listed.iter().map(String::as_str))
+    {
+        headers.remove(name);
+    }
+}
@@ -1,22 +1,33 @@
 use crate::allow_threads::AllowThreads;
 use crate::asyncio::{TaskLocal, py_coro_waiter};
 use crate::client::RuntimeHandle;
-use crate::exceptions::{JSONDecodeError, RequestError, StatusError};
+use crate::client::internal::Timings;
+use crate::exceptions::utils::RequestContext;
+use crate::exceptions::{ApiError, JSONDecodeError, RequestError, StatusError};
 use crate::http::{HeaderMap, Mime};
-use crate::internal::json::{JsonHandler, JsonLoadsContext};
+use crate::internal::json::{JsonHandler, JsonLoadsContext, apply_object_hook};
 use crate::internal::types::{Extensions, HeaderValue, JsonValue, StatusCode, Version};
 use crate::response::SyncResponseBodyReader;
-use crate::response::internal::{BodyConsumeConfig, BodyReader};
+use crate::response::internal::{
+    BodyConsumeConfig, BodyReader, DuplicateKeyPolicy, JsonArrayParser, Link, content_disposition_filename,
+    html_meta_charset, json_constant_ranges, json_from_slice, json_loads_python, parse_link_header,
+    replace_json_constants,
+};
+use crate::response::proxy_response::{ProxyResponse, strip_hop_by_hop_headers};
 use crate::response::response_body_reader::{BaseResponseBodyReader, ResponseBodyReader};
+use crate::response::sse::{ServerSentEventIterator, SyncServerSentEventIterator};
 use bytes::Bytes;
 use encoding_rs::{Encoding, UTF_8};
 use pyo3::coroutine::CancelHandle;
-use pyo3::exceptions::PyRuntimeError;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
-use pyo3::{PyTraverseError, PyVisit};
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{IntoPyDict, PyDict, PyMemoryView, PyType};
+use pyo3::{PyTraverseError, PyVisit, intern};
 use pyo3_bytes::PyBytes;
 use serde_json::json;
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
 use tokio::sync::OwnedSemaphorePermit;
 
 #[pyclass(subclass)]
@@ -30,6 +41,9 @@ pub struct Inner {
     runtime: RuntimeHandle,
     json_handler: Option<JsonHandler>,
     error_for_status: bool,
+    error_for_json: Option<Py<PyAny>>,
+    timings: Option<Timings>,
+    peer_certificate: Option<Bytes>,
 }
 
 #[pyclass(extends=BaseResponse)]
@@ -50,6 +64,21 @@ impl BaseResponse {
         Ok(())
     }
 
+    #[getter]
+    fn get_status_enum<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        static HTTP_STATUS: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+        let status = self.ref_inner()?.status.0.as_u16();
+        match HTTP_STATUS.import(py, "http", "HTTPStatus")?.call1((status,)) {
+            Ok(status) => Ok(Some(status)),
+            Err(e) if e.is_instance_of::<PyValueError>(py) => Ok(None), // Non-standard status code
+            Err(e) => Err(e),
+        }
+    }
+
+    fn __bool__(&self) -> PyResult<bool> {
+        Ok(self.ref_inner()?.status.0.is_success())
+    }
+
     #[getter]
     fn get_version(&self) -> PyResult<Version> {
         Ok(self.ref_inner()?.version.clone())
@@ -80,6 +109,13 @@ impl BaseResponse {
         Ok(())
     }
 
+    fn copy_headers(&self) -> PyResult<HeaderMap> {
+        match &self.ref_inner()?.headers {
+            RespHeaders::Headers(headers) => headers.try_clone(),
+            RespHeaders::PyHeaders(py_headers) => py_headers.get().try_clone(),
+        }
+    }
+
     #[getter]
     fn get_extensions(&mut self, py: Python) -> PyResult<Py<PyDict>> {
         let inner = self.mut_inner()?;
@@ -102,20 +138,59 @@ impl BaseResponse {
         Ok(())
     }
 
+    #[getter]
+    fn get_links<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let links = py.detach(|| self.links_inner())?;
+        let dict = PyDict::new(py);
+        for link in links {
+            for rel in link.rels() {
+                if dict.contains(rel)? {
+                    continue; // First link for the relation wins
+                }
+                let entry = PyDict::new(py);
+                entry.set_item(intern!(py, "url"), &link.url)?;
+                entry.set_item(intern!(py, "params"), link.params.clone().into_py_dict(py)?)?;
+                dict.set_item(rel, entry)?;
+            }
+        }
+        Ok(dict)
+    }
+
+    fn timings<'py>(&mut self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let Some(timings) = self.ref_inner()?.timings else {
+            return Ok(None);
+        };
+        let tag = self.get_extensions(py)?.bind(py).get_item(intern!(py, "tag"))?;
+        let dict = PyDict::new(py);
+        dict.set_item(intern!(py, "tag"), tag)?;
+        dict.set_item(intern!(py, "dns"), timings.dns)?;
+        dict.set_item(intern!(py, "connect"), timings.connect)?;
+        dict.set_item(intern!(py, "ttfb"), timings.ttfb)?;
+        dict.set_item(intern!(py, "total"), timings.total)?;
+        Ok(Some(dict))
+    }
+
+    fn peer_certificate(&self) -> PyResult<Option<PyBytes>> {
+        Ok(self.ref_inner()?.peer_certificate.clone().map(PyBytes::from))
+    }
+
     fn error_for_status(&self) -> PyResult<()> {
         let inner = self.ref_inner()?;
-        if inner.status.0.is_success() {
-            return Ok(());
-        }
         let msg = if inner.status.0.is_client_error() {
             "HTTP status client error"
-        } else {
-            debug_assert!(inner.status.0.is_server_error());
+        } else if inner.status.0.is_server_error() {
             "HTTP status server error"
+        } else {
+            return Ok(()); // Like in reqwest, only 4xx and 5xx are errors (e.g. not an unfollowed redirect)
         };
         Err(StatusError::from_custom(msg, json!({"status": inner.status.0.as_u16()})))
     }
 
+    fn raise_for_status(slf: Bound<Self>) -> PyResult<Bound<Self>> {
+        slf.try_borrow()?.error_for_status()?;
+        Ok(slf)
+    }
+
     fn get_header(&self, py: Python, name: &str) -> PyResult<Option<HeaderValue>> {
         py.detach(|| self.get_header_inner(name))
     }
@@ -128,35 +203,137 @@ impl BaseResponse {
         py.detach(|| self.content_type_mime_inner())
     }
 
+    fn content_disposition_filename(&self, py: Python) -> PyResult<Option<String>> {
+        py.detach(|| self.content_disposition_filename_inner())
+    }
+
     async fn bytes(&mut self, #[pyo3(cancel_handle)] mut cancel: CancelHandle) -> PyResult<PyBytes> {
         AllowThreads(async { self.bytes_inner(&mut cancel).await.map(PyBytes::new) }).await
     }
 
-    async fn json(&mut self, #[pyo3(cancel_handle)] cancel: CancelHandle) -> PyResult<Py<PyAny>> {
-        if self.ref_inner()?.json_handler.as_ref().is_some_and(|v| v.has_loads()) {
-            let coro = Python::attach(|py| {
-                let task_local = TaskLocal::current(py)?;
-                let ctx = JsonLoadsContext {
-                    body_reader: self.get_body_reader_inner(py, false)?,
-                    headers: self.get_headers(py)?,
-                    extensions: self.get_extensions(py)?,
-                };
-                let coro = self
-                    .ref_inner()?
-                    .json_handler
-                    .as_ref()
-                    .ok_or_else(|| PyRuntimeError::new_err("Expected json_handler"))?
-                    .call_loads(py, ctx)?;
-                py_coro_waiter(coro, &task_local, Some(cancel))
-            })?;
-            AllowThreads(coro).await
-        } else {
-            self.json_inner(cancel).await // AllowThreads is used inside
+    #[pyo3(signature = (*, object_hook=None, on_duplicate_key=DuplicateKeyPolicy::default(), exact_numbers=false, allow_nan=false))]
+    async fn json(
+        &mut self,
+        object_hook: Option<Py<PyAny>>,
+        on_duplicate_key: DuplicateKeyPolicy,
+        exact_numbers: bool,
+        allow_nan: bool,
+        #[pyo3(cancel_handle)] cancel: CancelHandle,
+    ) -> PyResult<Py<PyAny>> {
+        let res = self
+            .json_loads(on_duplicate_key, exact_numbers, allow_nan, cancel)
+            .await?;
+        match object_hook {
+            Some(hook) => Python::attach(|py| Ok(apply_object_hook(res.into_bound(py), hook.bind(py))?.unbind())),
+            None => Ok(res),
         }
     }
 
-    async fn text(&mut self, #[pyo3(cancel_handle)] mut cancel: CancelHandle) -> PyResult<String> {
-        self.text_inner(&mut cancel).await // AllowThreads is used inside
+    #[pyo3(signature = (*, strip_bom=true, sniff_html_charset=false))]
+    async fn text(
+        &mut self,
+        strip_bom: bool,
+        sniff_html_charset: bool,
+        #[pyo3(cancel_handle)] mut cancel: CancelHandle,
+    ) -> PyResult<String> {
+        self.text_inner(strip_bom, sniff_html_charset, &mut cancel).await // AllowThreads is used inside
+    }
+
+    async fn decode(&mut self, encoding: String, #[pyo3(cancel_handle)] mut cancel: CancelHandle) -> PyResult<String> {
+        let encoding = Encoding::for_label(encoding.as_bytes())
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown encoding: {}", encoding)))?;
+        AllowThreads(async {
+            let bytes = self.bytes_inner(&mut cancel).await?;
+            let (text, _, _) = encoding.decode(&bytes);
+            Ok(text.into_owned())
+        })
+        .await
+    }
+
+    async fn buffer_all(&mut self, #[pyo3(cancel_handle)] mut cancel: CancelHandle) -> PyResult<()> {
+        AllowThreads(async { self.bytes_inner(&mut cancel).await.map(|_| ()) }).await
+    }
+
+    #[pyo3(signature = (dir=None))]
+    async fn body_as_mmap(
+        &mut self,
+        dir: Option<PathBuf>,
+        #[pyo3(cancel_handle)] mut cancel: CancelHandle,
+    ) -> PyResult<Py<PyMemoryView>> {
+        let bytes = AllowThreads(async { self.body_as_mmap_inner(dir, &mut cancel).await }).await?;
+        Python::attach(|py| Ok(PyMemoryView::from(&PyBytes::new(bytes).into_pyobject(py)?.into_any())?.unbind()))
+    }
+
+    #[pyo3(signature = (path, chunk_callback=None))]
+    async fn write_to(
+        &mut self,
+        path: PathBuf,
+        chunk_callback: Option<Py<PyAny>>,
+        #[pyo3(cancel_handle)] mut cancel: CancelHandle,
+    ) -> PyResult<u64> {
+        // File IO needs the tokio runtime context so a writer task is fed with the body chunks
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Bytes>(1);
+        let writer = self.ref_inner()?.runtime.spawn(async move {
+            let mut file = tokio::fs::File::create(path).await?;
+            while let Some(chunk) = rx.recv().await {
+                file.write_all(&chunk).await?;
+            }
+            file.flush().await
+        })?;
+
+        let mut written = 0;
+        loop {
+            let sent = AllowThreads(async {
+                let Some(chunk) = self.next_chunk_inner(&mut cancel).await? else {
+                    return Ok(None);
+                };
+                let len = chunk.len() as u64;
+                Ok::<_, PyErr>(tx.send(chunk).await.ok().map(|_| len)) // Writer error is raised below
+            })
+            .await?;
+            let Some(len) = sent else {
+                break;
+            };
+            written += len;
+            if let Some(chunk_callback) = chunk_callback.as_ref() {
+                Python::attach(|py| chunk_callback.call1(py, (written,)))?;
+            }
+        }
+        drop(tx);
+        AllowThreads(writer)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(format!("File writer failed: {}", e)))??;
+        Ok(written)
+    }
+
+    async fn json_stream_to(
+        &mut self,
+        callback: Py<PyAny>,
+        #[pyo3(cancel_handle)] mut cancel: CancelHandle,
+    ) -> PyResult<usize> {
+        let mut parser = JsonArrayParser::default();
+        let mut count = 0;
+        loop {
+            let values = AllowThreads(async {
+                match self.next_chunk_inner(&mut cancel).await? {
+                    Some(chunk) => parser.feed(&chunk).map(Some),
+                    None => Ok(None),
+                }
+            })
+            .await?;
+            let Some(values) = values else {
+                break;
+            };
+            Python::attach(|py| {
+                for value in values {
+                    callback.call1(py, (JsonValue(value),))?;
+                    count += 1;
+                }
+                Ok::<_, PyErr>(())
+            })?;
+        }
+        parser.finish()?;
+        Ok(count)
     }
 
     // :NOCOV_START
@@ -170,20 +347,49 @@ impl BaseResponse {
         if let RespExtensions::PyExtensions(py_ext) = &inner.extensions {
             visit.call(py_ext)?;
         }
+        if let Some(error_for_json) = &inner.error_for_json {
+            visit.call(error_for_json)?;
+        }
         Ok(())
     } // :NOCOV_END
 }
 impl BaseResponse {
+    // Options of the built-in deserializer would be silently ignored by a custom json_handler loads
+    fn check_custom_loads_options(
+        on_duplicate_key: DuplicateKeyPolicy,
+        exact_numbers: bool,
+        allow_nan: bool,
+    ) -> PyResult<()> {
+        if on_duplicate_key != DuplicateKeyPolicy::default() || exact_numbers || allow_nan {
+            return Err(PyValueError::new_err(
+                "on_duplicate_key, exact_numbers and allow_nan are not supported with a custom json_handler loads",
+            ));
+        }
+        Ok(())
+    }
+
     pub async fn initialize(
-        response: reqwest::Response,
+        mut response: reqwest::Response,
         request_semaphore_permit: Option<OwnedSemaphorePermit>,
         consume_body: BodyConsumeConfig,
         runtime: RuntimeHandle,
         json_handler: Option<JsonHandler>,
         error_for_status: bool,
+        error_for_json: Option<Py<PyAny>>,
+        request_context: Option<RequestContext>,
     ) -> PyResult<Self> {
+        let mut timings = response.extensions_mut().remove::<Timings>();
+        let peer_certificate = response
+            .extensions()
+            .get::<reqwest::tls::TlsInfo>()
+            .and_then(|info| info.peer_certificate())
+            .map(Bytes::copy_from_slice);
         let (body_reader, head) =
-            BodyReader::initialize(response, request_semaphore_permit, consume_body, runtime.clone()).await?;
+            BodyReader::initialize(response, request_semaphore_permit, consume_body, runtime.clone(), request_context)
+                .await?;
+        if let Some(timings) = timings.as_mut() {
+            timings.finish(); // Includes the body when it was consumed fully
+        }
 
         let resp = BaseResponse(Some(Inner {
             status: StatusCode(head.status),
@@ -194,6 +400,9 @@ impl BaseResponse {
             runtime,
             json_handler,
             error_for_status,
+            error_for_json,
+            timings,
+            peer_certificate,
         }));
         Ok(resp)
     }
@@ -205,6 +414,37 @@ impl BaseResponse {
         Ok(self)
     }
 
+    pub fn has_error_for_json(&self) -> PyResult<bool> {
+        Ok(self.ref_inner()?.error_for_json.is_some())
+    }
+
+    // JSON is parsed like in json() so the predicate sees the same value, including the client json_handler
+    pub async fn check_error_for_json(mut self) -> PyResult<Self> {
+        let Some(predicate) = self.mut_inner()?.error_for_json.take() else {
+            return Ok(self);
+        };
+        let json = self
+            .json_loads(DuplicateKeyPolicy::default(), false, false, CancelHandle::new())
+            .await;
+        let status = self.ref_inner()?.status.0.as_u16();
+        Python::attach(|py| Self::raise_for_json_error(py, predicate, json, status))?;
+        Ok(self)
+    }
+
+    fn raise_for_json_error(py: Python, predicate: Py<PyAny>, json: PyResult<Py<PyAny>>, status: u16) -> PyResult<()> {
+        let json = match json {
+            Ok(json) => json,
+            Err(e) if e.is_instance_of::<PyValueError>(py) => return Ok(()), // Not a JSON body
+            Err(e) => return Err(e),
+        };
+        let error = predicate.call1(py, (json,))?.into_bound(py);
+        if error.is_truthy()? {
+            let details = json!({"status": status, "error": error.extract::<JsonValue>()?.0});
+            return Err(ApiError::from_custom("Response JSON contains an error", details));
+        }
+        Ok(())
+    }
+
     pub fn take_inner(&mut self) -> PyResult<BaseResponse> {
         let inner = self
             .0
@@ -213,11 +453,9 @@ impl BaseResponse {
         Ok(BaseResponse(Some(inner)))
     }
 
-    pub fn take_body_reader(&mut self) -> PyResult<RespReader> {
-        self.mut_inner()?
-            .body_reader
-            .take()
-            .ok_or_else(|| PyRuntimeError::new_err("Response body reader is closed"))
+    // None if the body reader was already closed
+    pub fn take_body_reader(&mut self) -> PyResult<Option<RespReader>> {
+        Ok(self.mut_inner()?.body_reader.take())
     }
 
     fn get_header_inner(&self, name: &str) -> PyResult<Option<HeaderValue>> {
@@ -247,6 +485,29 @@ impl BaseResponse {
         Ok(Some(Mime::new(mime)))
     }
 
+    fn content_disposition_filename_inner(&self) -> PyResult<Option<String>> {
+        let Some(content_disposition) = self.get_header_inner("content-disposition")? else {
+            return Ok(None);
+        };
+        let value = content_disposition
+            .0
+            .to_str()
+            .map_err(|e| RequestError::from_err("Invalid Content-Disposition header", &e))?;
+        Ok(content_disposition_filename(value))
+    }
+
+    fn links_inner(&self) -> PyResult<Vec<Link>> {
+        let mut links = Vec::new();
+        for value in self.get_header_all_inner("link")? {
+            let value = value
+                .0
+                .to_str()
+                .map_err(|e| RequestError::from_err("Invalid Link header", &e))?;
+            links.extend(parse_link_header(value));
+        }
+        Ok(links)
+    }
+
     async fn bytes_inner(&mut self, cancel: &mut CancelHandle) -> PyResult<Bytes> {
         match self.mut_inner()?.body_reader.as_mut() {
             Some(RespReader::Reader(reader)) => reader.bytes(cancel).await,
@@ -255,10 +516,116 @@ impl BaseResponse {
         }
     }
 
-    async fn json_inner(&mut self, mut cancel: CancelHandle) -> PyResult<Py<PyAny>> {
+    // Streams the remaining body into a private, unlinked tempfile and maps it into memory.
+    // The file is released when the last reference to the mapping is dropped.
+    async fn body_as_mmap_inner(&mut self, dir: Option<PathBuf>, cancel: &mut CancelHandle) -> PyResult<Bytes> {
+        // File IO needs the tokio runtime context so a writer task is fed with the body chunks
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Bytes>(1);
+        let writer = self.ref_inner()?.runtime.spawn(async move {
+            let file = tokio::task::spawn_blocking(move || match dir {
+                Some(dir) => tempfile::tempfile_in(dir),
+                None => tempfile::tempfile(),
+            })
+            .await??;
+            let mut file = tokio::fs::File::from_std(file);
+            let mut len = 0;
+            while let Some(chunk) = rx.recv().await {
+                file.write_all(&chunk).await?;
+                len += chunk.len();
+            }
+            file.flush().await?;
+            if len == 0 {
+                return Ok(Bytes::new()); // Empty files can not be mapped
+            }
+            let file = file.into_std().await;
+            // SAFETY: The file is a private, unlinked tempfile so it can not be modified while mapped.
+            let mmap = unsafe { memmap2::Mmap::map(&file)? };
+            Ok::<_, std::io::Error>(Bytes::from_owner(mmap))
+        })?;
+
+        while let Some(chunk) = self.next_chunk_inner(cancel).await? {
+            if tx.send(chunk).await.is_err() {
+                break; // Writer error is raised below
+            }
+        }
+        drop(tx);
+        Ok(writer
+            .await
+            .map_err(|e| PyRuntimeError::new_err(format!("File writer failed: {}", e)))??)
+    }
+
+    async fn next_chunk_inner(&mut self, cancel: &mut CancelHandle) -> PyResult<Option<Bytes>> {
+        match self.mut_inner()?.body_reader.as_mut() {
+            Some(RespReader::Reader(reader)) => reader.next_chunk(cancel).await,
+            Some(RespReader::PyReader(reader)) => reader.get().next_chunk_inner(cancel).await,
+            None => Err(PyRuntimeError::new_err("Response body reader is closed")),
+        }
+    }
+
+    async fn json_loads(
+        &mut self,
+        on_duplicate_key: DuplicateKeyPolicy,
+        exact_numbers: bool,
+        allow_nan: bool,
+        cancel: CancelHandle,
+    ) -> PyResult<Py<PyAny>> {
+        if self.ref_inner()?.json_handler.as_ref().is_some_and(|v| v.has_loads()) {
+            Self::check_custom_loads_options(on_duplicate_key, exact_numbers, allow_nan)?;
+            let coro = Python::attach(|py| {
+                let task_local = TaskLocal::current(py)?;
+                let ctx = JsonLoadsContext {
+                    body_reader: self.get_body_reader_inner(py, false)?,
+                    headers: self.get_headers(py)?,
+                    extensions: self.get_extensions(py)?,
+                };
+                let coro = self
+                    .ref_inner()?
+                    .json_handler
+                    .as_ref()
+                    .ok_or_else(|| PyRuntimeError::new_err("Expected json_handler"))?
+                    .call_loads(py, ctx)?;
+                py_coro_waiter(coro, &task_local, Some(cancel))
+            })?;
+            AllowThreads(coro).await
+        } else {
+            self.json_inner(on_duplicate_key, exact_numbers, allow_nan, cancel)
+                .await // AllowThreads is used inside
+        }
+    }
+
+    async fn json_inner(
+        &mut self,
+        on_duplicate_key: DuplicateKeyPolicy,
+        exact_numbers: bool,
+        allow_nan: bool,
+        mut cancel: CancelHandle,
+    ) -> PyResult<Py<PyAny>> {
+        if exact_numbers || allow_nan {
+            let bytes = AllowThreads(async {
+                let bytes = self.json_bytes_inner(&mut cancel).await?;
+                let constants = json_constant_ranges(&bytes);
+                if !allow_nan && let Some(constant) = constants.first() {
+                    return Err(self.json_constant_error(constant.start, &mut cancel).await?);
+                }
+                if on_duplicate_key == DuplicateKeyPolicy::Error {
+                    let res = if constants.is_empty() {
+                        json_from_slice(&bytes, on_duplicate_key)
+                    } else {
+                        json_from_slice(&replace_json_constants(&bytes, &constants), on_duplicate_key)
+                    };
+                    if let Err(e) = res {
+                        return Err(self.json_error(&e, &mut cancel).await?);
+                    }
+                }
+                Ok(bytes)
+            })
+            .await?;
+            return Python::attach(|py| Ok(json_loads_python(py, &bytes, on_duplicate_key, exact_numbers)?.unbind()));
+        }
+
         let serde_val = AllowThreads(async {
-            let bytes = self.bytes_inner(&mut cancel).await?;
-            match serde_json::from_slice::<serde_json::Value>(&bytes) {
+            let bytes = self.json_bytes_inner(&mut cancel).await?;
+            match json_from_slice(&bytes, on_duplicate_key) {
                 Ok(v) => Ok(v),
                 Err(e) => Err(self.json_error(&e, &mut cancel).await?),
             }
@@ -267,20 +634,68 @@ impl BaseResponse {
         Python::attach(|py| Ok(JsonValue(serde_val).into_pyobject(py)?.unbind()))
     }
 
-    async fn text_inner(&mut self, cancel: &mut CancelHandle) -> PyResult<String> {
+    // Body decoded into UTF-8 from the declared charset like in text
+    async fn json_bytes_inner(&mut self, cancel: &mut CancelHandle) -> PyResult<Bytes> {
+        let bytes = self.bytes_inner(cancel).await?;
+        let mime = self.content_type_mime_inner()?;
+        let charset = mime.as_ref().and_then(|mime| mime.get_param("charset"));
+        match charset.and_then(|charset| Encoding::for_label(charset.as_bytes())) {
+            Some(encoding) if encoding != UTF_8 => {
+                let (text, _, _) = encoding.decode(&bytes);
+                Ok(Bytes::from(text.into_owned()))
+            }
+            _ => Ok(bytes),
+        }
+    }
+
+    async fn text_inner(
+        &mut self,
+        strip_bom: bool,
+        sniff_html_charset: bool,
+        cancel: &mut CancelHandle,
+    ) -> PyResult<String> {
         AllowThreads(async {
             let bytes = self.bytes_inner(cancel).await?;
-            let encoding = self
-                .content_type_mime_inner()?
-                .and_then(|mime| mime.get_param("charset").map(String::from))
-                .and_then(|charset| Encoding::for_label(charset.as_bytes()))
-                .unwrap_or(UTF_8);
-            let (text, _, _) = encoding.decode(&bytes);
-            Ok(text.into_owned())
+            let mime = self.content_type_mime_inner()?;
+            let charset = mime.as_ref().and_then(|mime| mime.get_param("charset"));
+            let encoding = match charset {
+                Some(charset) => Encoding::for_label(charset.as_bytes()),
+                None if sniff_html_charset && mime.as_ref().is_none_or(Self::is_html) => html_meta_charset(&bytes),
+                None => None,
+            }
+            .unwrap_or(UTF_8);
+            if strip_bom {
+                let (text, _, _) = encoding.decode(&bytes); // BOM overrides the charset and is removed
+                Ok(text.into_owned())
+            } else {
+                let encoding = Encoding::for_bom(&bytes).map_or(encoding, |(bom_encoding, _)| bom_encoding);
+                let (text, _) = encoding.decode_without_bom_handling(&bytes);
+                Ok(text.into_owned())
+            }
         })
         .await
     }
 
+    fn is_html(mime: &Mime) -> bool {
+        matches!(mime.essence_str(), "text/html" | "application/xhtml+xml")
+    }
+
+    fn proxy_response_inner(
+        &mut self,
+        py: Python,
+        is_blocking: bool,
+        strip_hop_by_hop: bool,
+    ) -> PyResult<ProxyResponse> {
+        let mut headers = self.copy_headers()?.try_take_inner()?;
+        if strip_hop_by_hop {
+            strip_hop_by_hop_headers(&mut headers);
+        }
+        let inner = self.ref_inner()?;
+        let (status, version) = (inner.status.clone(), inner.version.clone());
+        let body = self.get_body_reader_inner(py, is_blocking)?;
+        Ok(ProxyResponse::new(status, version, Py::new(py, HeaderMap::from(headers))?, body))
+    }
+
     fn get_body_reader_inner(&mut self, py: Python, is_blocking: bool) -> PyResult<Py<BaseResponseBodyReader>> {
         let inner = self.mut_inner()?;
         if inner.body_reader.is_none() {
@@ -307,11 +722,17 @@ impl BaseResponse {
     }
 
     async fn json_error(&mut self, e: &serde_json::error::Error, cancel: &mut CancelHandle) -> PyResult<PyErr> {
-        let text = self.text_inner(cancel).await?;
+        let text = self.text_inner(true, false, cancel).await?;
         let details = json!({"pos": Self::json_error_pos(&text, e), "doc": text, "causes": serde_json::Value::Null});
         Ok(JSONDecodeError::from_custom(&e.to_string(), details))
     }
 
+    async fn json_constant_error(&mut self, pos: usize, cancel: &mut CancelHandle) -> PyResult<PyErr> {
+        let text = self.text_inner(true, false, cancel).await?;
+        let details = json!({"pos": pos, "doc": text, "causes": serde_json::Value::Null});
+        Ok(JSONDecodeError::from_custom("Expecting value", details))
+    }
+
     fn json_error_pos(content: &str, e: &serde_json::error::Error) -> usize {
         let (line, column) = (e.line(), e.column());
         // Use byte position to have error case efficient
@@ -357,6 +778,28 @@ impl Response {
             .cast_into::<ResponseBodyReader>()?
             .unbind())
     }
+
+    fn stream(slf: PyRefMut<'_, Self>, py: Python) -> PyResult<Py<ResponseBodyReader>> {
+        Self::get_body_reader(slf, py)
+    }
+
+    fn iter_sse(mut slf: PyRefMut<Self>, py: Python) -> PyResult<ServerSentEventIterator> {
+        let body_reader = slf.as_super().get_body_reader_inner(py, false)?;
+        Ok(ServerSentEventIterator::new(body_reader))
+    }
+
+    #[pyo3(signature = (*, strip_hop_by_hop=true))]
+    fn into_proxy_response(mut slf: PyRefMut<Self>, py: Python, strip_hop_by_hop: bool) -> PyResult<ProxyResponse> {
+        slf.as_super().proxy_response_inner(py, false, strip_hop_by_hop)
+    }
+
+    async fn aclose(slf: Py<Self>) -> PyResult<()> {
+        let body = Python::attach(|py| slf.bind(py).try_borrow_mut()?.into_super().take_body_reader())?;
+        if let Some(body) = body {
+            AllowThreads(body.close()).await;
+        }
+        Ok(())
+    }
 }
 impl Response {
     pub fn new_py(py: Python, inner: BaseResponse) -> PyResult<Py<Self>> {
@@ -382,11 +825,104 @@ impl SyncResponse {
         Self::runtime(slf.as_ref())?.blocking_spawn(slf.into_super().bytes(CancelHandle::new()))
     }
 
-    fn json(mut slf: PyRefMut<Self>, py: Python) -> PyResult<Py<PyAny>> {
+    #[pyo3(signature = (*, object_hook=None, on_duplicate_key=DuplicateKeyPolicy::default(), exact_numbers=false, allow_nan=false))]
+    fn json(
+        slf: PyRefMut<Self>,
+        py: Python,
+        object_hook: Option<Bound<PyAny>>,
+        on_duplicate_key: DuplicateKeyPolicy,
+        exact_numbers: bool,
+        allow_nan: bool,
+    ) -> PyResult<Py<PyAny>> {
+        let res = Self::json_loads(slf, py, on_duplicate_key, exact_numbers, allow_nan)?;
+        match object_hook {
+            Some(hook) => Ok(apply_object_hook(res.into_bound(py), &hook)?.unbind()),
+            None => Ok(res),
+        }
+    }
+
+    #[pyo3(signature = (*, strip_bom=true, sniff_html_charset=false))]
+    fn text(slf: PyRefMut<Self>, strip_bom: bool, sniff_html_charset: bool) -> PyResult<String> {
+        Self::runtime(slf.as_ref())?.blocking_spawn(slf.into_super().text(
+            strip_bom,
+            sniff_html_charset,
+            CancelHandle::new(),
+        ))
+    }
+
+    fn decode(slf: PyRefMut<Self>, encoding: String) -> PyResult<String> {
+        Self::runtime(slf.as_ref())?.blocking_spawn(slf.into_super().decode(encoding, CancelHandle::new()))
+    }
+
+    fn buffer_all(slf: PyRefMut<Self>) -> PyResult<()> {
+        Self::runtime(slf.as_ref())?.blocking_spawn(slf.into_super().buffer_all(CancelHandle::new()))
+    }
+
+    #[pyo3(signature = (dir=None))]
+    fn body_as_mmap(slf: PyRefMut<Self>, dir: Option<PathBuf>) -> PyResult<Py<PyMemoryView>> {
+        Self::runtime(slf.as_ref())?.blocking_spawn(slf.into_super().body_as_mmap(dir, CancelHandle::new()))
+    }
+
+    #[pyo3(signature = (path, chunk_callback=None))]
+    fn write_to(slf: PyRefMut<Self>, path: PathBuf, chunk_callback: Option<Py<PyAny>>) -> PyResult<u64> {
+        Self::runtime(slf.as_ref())?.blocking_spawn(slf.into_super().write_to(
+            path,
+            chunk_callback,
+            CancelHandle::new(),
+        ))
+    }
+
+    fn json_stream_to(slf: PyRefMut<Self>, callback: Py<PyAny>) -> PyResult<usize> {
+        Self::runtime(slf.as_ref())?.blocking_spawn(slf.into_super().json_stream_to(callback, CancelHandle::new()))
+    }
+
+    fn iter_bytes<'py>(slf: PyRefMut<'py, Self>, py: Python<'py>) -> PyResult<Bound<'py, SyncResponseBodyReader>> {
+        Self::get_body_reader(slf, py)
+    }
+
+    fn iter_sse(mut slf: PyRefMut<Self>, py: Python) -> PyResult<SyncServerSentEventIterator> {
+        let runtime = Self::runtime(slf.as_ref())?;
+        let body_reader = slf.as_super().get_body_reader_inner(py, true)?;
+        Ok(SyncServerSentEventIterator::new(body_reader, runtime))
+    }
+
+    #[pyo3(signature = (*, strip_hop_by_hop=true))]
+    fn into_proxy_response(mut slf: PyRefMut<Self>, py: Python, strip_hop_by_hop: bool) -> PyResult<ProxyResponse> {
+        slf.as_super().proxy_response_inner(py, true, strip_hop_by_hop)
+    }
+
+    fn close(slf: PyRefMut<Self>) -> PyResult<()> {
+        let runtime = Self::runtime(slf.as_ref())?;
+        if let Some(body) = slf.into_super().take_body_reader()? {
+            runtime.blocking_spawn(body.close());
+        }
+        Ok(())
+    }
+}
+impl SyncResponse {
+    pub fn new_py(py: Python, inner: BaseResponse) -> PyResult<Py<Self>> {
+        Py::new(py, PyClassInitializer::from(inner).add_subclass(Self))
+    }
+
+    fn json_loads(
+        mut slf: PyRefMut<Self>,
+        py: Python,
+        on_duplicate_key: DuplicateKeyPolicy,
+        exact_numbers: bool,
+        allow_nan: bool,
+    ) -> PyResult<Py<PyAny>> {
         let json_handler = match slf.as_super().ref_inner()?.json_handler.as_ref() {
-            Some(h) if h.has_loads() => h.clone_ref(py),
+            Some(h) if h.has_loads() => {
+                BaseResponse::check_custom_loads_options(on_duplicate_key, exact_numbers, allow_nan)?;
+                h.clone_ref(py)
+            }
             _ => {
-                return Self::runtime(slf.as_ref())?.blocking_spawn(slf.into_super().json_inner(CancelHandle::new()));
+                return Self::runtime(slf.as_ref())?.blocking_spawn(slf.into_super().json_inner(
+                    on_duplicate_key,
+                    exact_numbers,
+                    allow_nan,
+                    CancelHandle::new(),
+                ));
             }
         };
         let ctx = JsonLoadsContext {
@@ -399,18 +935,21 @@ impl SyncResponse {
         Ok(json_handler.call_loads(py, ctx)?.unbind())
     }
 
-    fn text(slf: PyRefMut<Self>) -> PyResult<String> {
-        Self::runtime(slf.as_ref())?.blocking_spawn(slf.into_super().text(CancelHandle::new()))
-    }
-}
-impl SyncResponse {
-    pub fn new_py(py: Python, inner: BaseResponse) -> PyResult<Py<Self>> {
-        Py::new(py, PyClassInitializer::from(inner).add_subclass(Self))
-    }
-
     pub fn runtime(slf: &BaseResponse) -> PyResult<RuntimeHandle> {
         Ok(slf.ref_inner()?.runtime.clone())
     }
+
+    // Sync json_handler reads the body through a SyncResponse so the check is done via one
+    pub fn check_error_for_json(py: Python, mut resp: BaseResponse) -> PyResult<BaseResponse> {
+        let Some(predicate) = resp.mut_inner()?.error_for_json.take() else {
+            return Ok(resp);
+        };
+        let status = resp.ref_inner()?.status.0.as_u16();
+        let resp = Self::new_py(py, resp)?;
+        let json = Self::json_loads(resp.bind(py).try_borrow_mut()?, py, DuplicateKeyPolicy::default(), false, false);
+        BaseResponse::raise_for_json_error(py, predicate, json, status)?;
+        resp.bind(py).try_borrow_mut()?.into_super().take_inner()
+    }
 }
 
 pub enum RespReader {
@@ -3,6 +3,8 @@ use crate::client::RuntimeHandle;
 use crate::response::internal::{BodyReader, DEFAULT_READ_BUFFER_LIMIT};
 use bytes::Bytes;
 use pyo3::coroutine::CancelHandle;
+use pyo3::exceptions::{PyStopAsyncIteration, PyStopIteration};
+use pyo3::intern;
 use pyo3::prelude::*;
 use pyo3_bytes::PyBytes;
 use tokio::sync::Mutex;
@@ -41,6 +43,14 @@ impl BaseResponseBodyReader {
     async fn read_chunk(&self, #[pyo3(cancel_handle)] mut cancel: CancelHandle) -> PyResult<Option<PyBytes>> {
         AllowThreads(async { Ok(self.inner.lock().await.next_chunk(&mut cancel).await?.map(PyBytes::new)) }).await
     }
+
+    #[pyo3(name = "_anext")]
+    async fn anext(&self, #[pyo3(cancel_handle)] mut cancel: CancelHandle) -> PyResult<PyBytes> {
+        AllowThreads(async { self.next_chunk_inner(&mut cancel).await })
+            .await?
+            .map(PyBytes::new)
+            .ok_or_else(|| PyStopAsyncIteration::new_err("Body stream exhausted"))
+    }
 }
 impl BaseResponseBodyReader {
     pub fn new(body_reader: BodyReader) -> Self {
@@ -54,11 +64,25 @@ impl BaseResponseBodyReader {
         self.inner.lock().await.bytes(cancel).await
     }
 
+    pub async fn next_chunk_inner(&self, cancel: &mut CancelHandle) -> PyResult<Option<Bytes>> {
+        self.inner.lock().await.next_chunk(cancel).await
+    }
+
     pub async fn close(&self) {
         self.inner.lock().await.close();
     }
 }
 
+#[pymethods]
+impl ResponseBodyReader {
+    fn __aiter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __anext__<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyAny>> {
+        slf.call_method0(intern!(slf.py(), "_anext")) // Async magic methods are not supported by pyo3
+    }
+}
 impl ResponseBodyReader {
     pub fn new_py(py: Python, inner: BodyReader) -> PyResult<Py<Self>> {
         let base = BaseResponseBodyReader::new(inner);
@@ -80,6 +104,14 @@ impl SyncResponseBodyReader {
     fn read_chunk(slf: PyRef<Self>) -> PyResult<Option<PyBytes>> {
         Self::runtime(slf.as_ref()).blocking_spawn(slf.as_super().read_chunk(CancelHandle::new()))
     }
+
+    fn __iter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __next__(slf: PyRef<Self>) -> PyResult<PyBytes> {
+        Self::read_chunk(slf)?.ok_or_else(|| PyStopIteration::new_err("Body stream exhausted"))
+    }
 }
 impl SyncResponseBodyReader {
     pub fn new_py(py: Python, inner: BodyReader) -> PyResult<Py<Self>> {
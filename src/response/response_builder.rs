@@ -26,15 +26,15 @@ impl ResponseBuilder {
         Self::new()
     }
 
-    fn status(mut slf: PyRefMut<Self>, status: StatusCode) -> PyResult<PyRefMut<Self>> {
+    fn status(mut slf: PyRefMut<Self>, value: StatusCode) -> PyResult<PyRefMut<Self>> {
         let head = slf.mut_head()?;
-        head.status = status.0;
+        head.status = value.0;
         Ok(slf)
     }
 
-    fn version(mut slf: PyRefMut<Self>, version: Version) -> PyResult<PyRefMut<Self>> {
+    fn version(mut slf: PyRefMut<Self>, value: Version) -> PyResult<PyRefMut<Self>> {
         let head = slf.mut_head()?;
-        head.version = version.0;
+        head.version = value.0;
         Ok(slf)
     }
 
@@ -52,8 +52,8 @@ impl ResponseBuilder {
         Ok(slf)
     }
 
-    fn extensions(mut slf: PyRefMut<Self>, value: Extensions) -> PyRefMut<Self> {
-        slf.extensions = Some(value);
+    fn extensions(mut slf: PyRefMut<Self>, extensions: Extensions) -> PyRefMut<Self> {
+        slf.extensions = Some(extensions);
         slf
     }
 
@@ -67,8 +67,8 @@ impl ResponseBuilder {
         Ok(slf)
     }
 
-    fn body_json<'py>(mut slf: PyRefMut<'py, Self>, data: JsonValue, py: Python<'py>) -> PyResult<PyRefMut<'py, Self>> {
-        let bytes = py.detach(|| serde_json::to_vec(&data).map_err(|e| PyValueError::new_err(e.to_string())))?;
+    fn body_json<'py>(mut slf: PyRefMut<'py, Self>, body: JsonValue, py: Python<'py>) -> PyResult<PyRefMut<'py, Self>> {
+        let bytes = py.detach(|| serde_json::to_vec(&body).map_err(|e| PyValueError::new_err(e.to_string())))?;
         slf.body = Some(RequestBody::from(Bytes::from(bytes)));
         slf.mut_head()?
             .headers
@@ -87,7 +87,8 @@ impl ResponseBuilder {
 
         let config = BodyConsumeConfig::Streamed(StreamedReadConfig::default());
         let runtime = RuntimeHandle::global_handle()?.clone();
-        let resp = AllowThreads(BaseResponse::initialize(inner, None, config, runtime, None, false)).await?;
+        let resp =
+            AllowThreads(BaseResponse::initialize(inner, None, config, runtime, None, false, None, None)).await?;
 
         Python::attach(|py| Response::new_py(py, resp))
     }
@@ -97,8 +98,16 @@ impl ResponseBuilder {
 
         let config = BodyConsumeConfig::Streamed(StreamedReadConfig::default());
         let runtime = RuntimeHandle::global_handle()?;
-        let resp =
-            runtime.blocking_spawn(BaseResponse::initialize(inner, None, config, runtime.clone(), None, false))?;
+        let resp = runtime.blocking_spawn(BaseResponse::initialize(
+            inner,
+            None,
+            config,
+            runtime.clone(),
+            None,
+            false,
+            None,
+            None,
+        ))?;
 
         Python::attach(|py| SyncResponse::new_py(py, resp))
     }
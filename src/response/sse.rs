@@ -0,0 +1,146 @@
+use crate::allow_threads::AllowThreads;
+use crate::client::RuntimeHandle;
+use crate::response::BaseResponseBodyReader;
+use crate::response::internal::{SseEvent, SseParser};
+use pyo3::coroutine::CancelHandle;
+use pyo3::exceptions::{PyStopAsyncIteration, PyStopIteration};
+use pyo3::prelude::*;
+use pyo3::{PyTraverseError, PyVisit, intern};
+use std::collections::VecDeque;
+use tokio::sync::Mutex;
+
+#[pyclass(frozen, get_all)]
+pub struct ServerSentEvent {
+    event: String,
+    data: String,
+    id: Option<String>,
+    retry: Option<u64>,
+}
+
+#[pyclass(frozen)]
+pub struct ServerSentEventIterator(SseStream);
+
+#[pyclass(frozen)]
+pub struct SyncServerSentEventIterator {
+    stream: SseStream,
+    runtime: RuntimeHandle,
+}
+
+struct SseStream {
+    body_reader: Py<BaseResponseBodyReader>,
+    state: Mutex<SseState>,
+}
+
+#[derive(Default)]
+struct SseState {
+    parser: SseParser,
+    events: VecDeque<SseEvent>,
+    done: bool,
+}
+
+#[pymethods]
+impl ServerSentEvent {
+    fn __repr__(&self, py: Python) -> PyResult<String> {
+        Ok(format!(
+            "ServerSentEvent(event={}, data={}, id={}, retry={})",
+            self.event.as_str().into_pyobject(py)?.repr()?,
+            self.data.as_str().into_pyobject(py)?.repr()?,
+            self.id.as_deref().into_pyobject(py)?.repr()?,
+            self.retry.into_pyobject(py)?.repr()?,
+        ))
+    }
+}
+impl From<SseEvent> for ServerSentEvent {
+    fn from(value: SseEvent) -> Self {
+        ServerSentEvent {
+            event: value.event,
+            data: value.data,
+            id: value.id,
+            retry: value.retry,
+        }
+    }
+}
+
+#[pymethods]
+impl ServerSentEventIterator {
+    fn __aiter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __anext__<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyAny>> {
+        slf.call_method0(intern!(slf.py(), "_anext")) // Async magic methods are not supported by pyo3
+    }
+
+    #[pyo3(name = "_anext")]
+    async fn anext(&self, #[pyo3(cancel_handle)] mut cancel: CancelHandle) -> PyResult<ServerSentEvent> {
+        AllowThreads(async { self.0.next_event(&mut cancel).await })
+            .await?
+            .ok_or_else(|| PyStopAsyncIteration::new_err("No more events"))
+    }
+
+    // :NOCOV_START
+    fn __traverse__(&self, visit: PyVisit<'_>) -> Result<(), PyTraverseError> {
+        visit.call(&self.0.body_reader)
+    }
+    // :NOCOV_END
+}
+impl ServerSentEventIterator {
+    pub fn new(body_reader: Py<BaseResponseBodyReader>) -> Self {
+        ServerSentEventIterator(SseStream::new(body_reader))
+    }
+}
+
+#[pymethods]
+impl SyncServerSentEventIterator {
+    fn __iter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __next__(&self) -> PyResult<ServerSentEvent> {
+        self.runtime
+            .blocking_spawn(self.stream.next_event(&mut CancelHandle::new()))?
+            .ok_or_else(|| PyStopIteration::new_err("No more events"))
+    }
+
+    // :NOCOV_START
+    fn __traverse__(&self, visit: PyVisit<'_>) -> Result<(), PyTraverseError> {
+        visit.call(&self.stream.body_reader)
+    }
+    // :NOCOV_END
+}
+impl SyncServerSentEventIterator {
+    pub fn new(body_reader: Py<BaseResponseBodyReader>, runtime: RuntimeHandle) -> Self {
+        SyncServerSentEventIterator {
+            stream: SseStream::new(body_reader),
+            runtime,
+        }
+    }
+}
+
+impl SseStream {
+    fn new(body_reader: Py<BaseResponseBodyReader>) -> Self {
+        SseStream {
+            body_reader,
+            state: Mutex::new(SseState::default()),
+        }
+    }
+
+    async fn next_event(&self, cancel: &mut CancelHandle) -> PyResult<Option<ServerSentEvent>> {
+        let mut state = self.state.lock().await;
+        loop {
+            if let Some(event) = state.events.pop_front() {
+                return Ok(Some(event.into()));
+            }
+            if state.done {
+                return Ok(None);
+            }
+            match self.body_reader.get().next_chunk_inner(cancel).await? {
+                Some(chunk) => {
+                    let events = state.parser.feed(&chunk);
+                    state.events.extend(events);
+                }
+                None => state.done = true, // Incomplete event at the end is discarded
+            }
+        }
+    }
+}
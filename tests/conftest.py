@@ -1,13 +1,18 @@
+import asyncio
+import ssl
 from collections.abc import AsyncGenerator, Generator
+from contextlib import AsyncExitStack
 from pathlib import Path
 
 import pytest
 import trustme
+from pyreqwest.http import Url
 
 from tests.utils import temp_file
 
 from .servers.echo_body_parts_server import EchoBodyPartsServer
 from .servers.echo_server import EchoServer
+from .servers.raw_server import RawServer
 from .servers.server import ServerConfig
 from .servers.server_pool import ServerPool
 from .servers.server_subprocess import SubprocessServer
@@ -33,6 +38,12 @@ async def echo_body_parts_server(server_pool: ServerPool) -> AsyncGenerator[Subp
         yield server
 
 
+@pytest.fixture
+async def raw_server() -> AsyncGenerator[RawServer]:
+    async with AsyncExitStack() as exit_stack:
+        yield RawServer(exit_stack)
+
+
 @pytest.fixture(scope="session")
 def cert_authority() -> trustme.CA:
     return trustme.CA()
@@ -73,3 +84,20 @@ async def https_echo_server(
     async with server_pool.use_server(EchoServer, config) as server:
         assert str(server.url).startswith("https://")
         yield server
+
+
+@pytest.fixture
+async def mtls_server(raw_server: RawServer, cert_authority: trustme.CA, localhost_cert: trustme.LeafCert) -> Url:
+    """HTTPS server requiring a client certificate issued by `cert_authority`."""
+    ssl_ctx = ssl.create_default_context(ssl.Purpose.CLIENT_AUTH)
+    ssl_ctx.verify_mode = ssl.CERT_REQUIRED
+    localhost_cert.configure_cert(ssl_ctx)
+    cert_authority.configure_trust(ssl_ctx)
+
+    async def handler(reader: asyncio.StreamReader, writer: asyncio.StreamWriter) -> None:
+        await reader.readuntil(b"\r\n\r\n")
+        writer.write(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+        await writer.drain()
+        writer.close()
+
+    return await raw_server.start(handler, ssl_ctx=ssl_ctx)
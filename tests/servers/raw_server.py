@@ -0,0 +1,59 @@
+import asyncio
+import ssl
+import struct
+from collections.abc import Awaitable, Callable
+from contextlib import AsyncExitStack
+from dataclasses import dataclass
+
+from pyreqwest.http import Url
+
+RawHandler = Callable[[asyncio.StreamReader, asyncio.StreamWriter], Awaitable[None]]
+
+
+@dataclass(frozen=True)
+class H2Frame:
+    frame_type: int
+    flags: int
+    stream_id: int
+    payload: bytes = b""
+
+    def encode(self) -> bytes:
+        head = len(self.payload).to_bytes(3, "big") + bytes([self.frame_type, self.flags])
+        return head + struct.pack(">I", self.stream_id) + self.payload
+
+
+H2FrameHandler = Callable[[H2Frame, asyncio.StreamWriter], None]
+
+
+class RawServer:
+    """In-process TCP servers for tests that need full control over the bytes on the wire."""
+
+    def __init__(self, exit_stack: AsyncExitStack) -> None:
+        self._exit_stack = exit_stack
+
+    async def start(self, handler: RawHandler, host: str = "127.0.0.1", ssl_ctx: ssl.SSLContext | None = None) -> Url:
+        server = await asyncio.start_server(handler, host, 0, ssl=ssl_ctx)
+        await self._exit_stack.enter_async_context(server)
+        scheme = "https" if ssl_ctx else "http"
+        url_host = f"[{host}]" if ":" in host else host
+        return Url(f"{scheme}://{url_host}:{server.sockets[0].getsockname()[1]}/")
+
+    async def start_h2(self, on_frame: H2FrameHandler) -> Url:
+        """HTTP/2 prior knowledge server. SETTINGS are acked here, every received frame is passed to on_frame."""
+
+        async def handler(reader: asyncio.StreamReader, writer: asyncio.StreamWriter) -> None:
+            await reader.readexactly(24)  # Client connection preface
+            writer.write(H2Frame(0x4, 0, 0).encode())  # SETTINGS
+            try:
+                while True:
+                    head = await reader.readexactly(9)
+                    payload = await reader.readexactly(int.from_bytes(head[:3], "big"))
+                    frame = H2Frame(head[3], head[4], int.from_bytes(head[5:], "big") & 0x7FFFFFFF, payload)
+                    if frame.frame_type == 0x4 and not frame.flags & 0x1:
+                        writer.write(H2Frame(0x4, 0x1, 0).encode())  # SETTINGS ack
+                    on_frame(frame, writer)
+                    await writer.drain()
+            except asyncio.IncompleteReadError:
+                writer.close()
+
+        return await self.start(handler)
@@ -4,6 +4,7 @@ from datetime import UTC, datetime, timedelta
 import pytest
 from pyreqwest.client import ClientBuilder
 from pyreqwest.cookie import Cookie, CookieStore
+from pyreqwest.http import Url
 
 from tests.servers.server_subprocess import SubprocessServer
 
@@ -48,6 +49,103 @@ async def test_cookie_provider(echo_server: SubprocessServer):
     assert store.get_all_any() == []
 
 
+async def test_cookie_provider_shared(echo_server: SubprocessServer):
+    store = CookieStore()
+    url = echo_server.url.with_query({"header_Set_Cookie": "name=val"})
+    async with client_builder().cookie_provider(store).build() as client:
+        await client.get(url).build().send()
+
+    store.insert("other=val2", echo_server.url)
+    async with client_builder().cookie_provider(store).build() as client:
+        resp = await client.get(echo_server.url).build().send()
+        assert ["cookie", "name=val; other=val2"] in (await resp.json())["headers"]
+
+
+@pytest.mark.parametrize("provider_first", [False, True])
+def test_cookie_provider_with_default_store(provider_first: bool):
+    builder = ClientBuilder()
+    if provider_first:
+        builder = builder.cookie_provider(CookieStore()).default_cookie_store(True)
+    else:
+        builder = builder.default_cookie_store(True).cookies(["a=b"], "http://example.com")
+    with pytest.raises(ValueError, match="can not be combined with cookie_provider"):
+        builder.build()
+
+    ClientBuilder().default_cookie_store(False).cookie_provider(CookieStore()).build()
+
+
+async def test_cookies_initial(echo_server: SubprocessServer):
+    initial = [Cookie("session", "abc"), "other=val; Path=/other"]
+    async with client_builder().cookies(initial, echo_server.url).build() as client:
+        resp = await client.get(echo_server.url).build().send()
+        assert ["cookie", "session=abc"] in (await resp.json())["headers"]
+
+        resp = await client.get(echo_server.url / "other").build().send()
+        assert ["cookie", "session=abc; other=val"] in (await resp.json())["headers"]
+
+    with pytest.raises(ValueError):
+        client_builder().cookies(["name=val; Domain=other.example"], echo_server.url)
+
+
+def test_cookie_header():
+    store = CookieStore()
+    assert store.cookie_header("https://example.com/") is None
+
+    store.insert("a=1", "https://example.com/")
+    store.insert("b=2; Path=/sub", "https://example.com/sub")
+    store.insert("c=3; Secure", "https://example.com/")
+    store.insert("d=4", "https://other.example/")
+
+    header = store.cookie_header("https://example.com/")
+    assert header and sorted(header.split("; ")) == ["a=1", "c=3"]
+    header = store.cookie_header(Url("https://example.com/sub/x"))
+    assert header and sorted(header.split("; ")) == ["a=1", "b=2", "c=3"]
+    assert store.cookie_header("http://example.com/") == "a=1"
+    assert store.cookie_header("https://unknown.example/") is None
+
+
+def test_get_request_cookies():
+    store = CookieStore()
+    assert store.get_request_cookies("https://example.com/") == []
+
+    store.insert("a=1; HttpOnly", "https://example.com/")
+    store.insert("b=2; Path=/sub", "https://example.com/sub")
+    store.insert("c=3; Secure", "https://example.com/")
+    store.insert("d=4; Domain=example.com", "https://www.example.com/")
+    store.insert("e=5", "https://other.example/")
+    store.insert("f=6; Max-Age=60", "https://example.com/")
+    store.insert("f=6; Max-Age=0", "https://example.com/")  # Expires the existing one
+
+    cookies = store.get_request_cookies("https://example.com/sub/x")
+    assert sorted(map(str, cookies)) == ["a=1", "b=2", "c=3", "d=4"]
+    assert "; ".join(map(str, cookies)) == store.cookie_header("https://example.com/sub/x")
+    assert sorted(map(str, store.get_request_cookies(Url("http://www.example.com/")))) == ["d=4"]
+    assert sorted(map(str, store.get_request_cookies("http://example.com/"))) == ["a=1", "d=4"]
+    assert all(c.path is None and not c.secure for c in cookies)
+
+
+def test_cookie_store_merge():
+    store1 = CookieStore()
+    store1.insert("a=1", "https://example.com/")
+    store1.insert("b=1", "https://example.com/")
+    store2 = CookieStore()
+    store2.insert("b=2", "https://example.com/")
+    store2.insert("c=2; Path=/sub", "https://example.com/sub")
+    store2.insert("d=2", "https://example.com/")
+    store2.insert("d=2; Max-Age=0", "https://example.com/")  # Expires the existing one
+    assert store2.contains_any("example.com", "/", "d")
+    store2.insert("e=2", "https://other.example/")
+
+    store1.merge(store2)
+    assert sorted(map(str, store1.matches("https://example.com/sub/x"))) == ["a=1", "b=2", "c=2; Path=/sub"]
+    assert store1.matches("https://other.example/") == ["e=2"]
+    assert not store1.contains_any("example.com", "/", "d")
+    assert sorted(map(str, store2.matches("https://example.com/sub/x"))) == ["b=2", "c=2; Path=/sub"]  # Not modified
+
+    store1.merge(store1)
+    assert sorted(map(str, store1.matches("https://example.com/"))) == ["a=1", "b=2"]
+
+
 def test_cookie_create():
     assert str(Cookie("key", "val")) == "key=val"
     assert str(Cookie.parse("key=val")) == "key=val"
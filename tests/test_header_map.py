@@ -1,3 +1,4 @@
+import random
 import re
 from collections.abc import Callable, ItemsView, KeysView, Mapping, MutableMapping, ValuesView
 from copy import copy
@@ -357,6 +358,18 @@ def test_pop():
     assert headers.pop("d", 1) == 1
 
 
+def test_pop__case_insensitive():
+    headers = HeaderMap([("content-type", "text/plain"), ("X-Foo", "v1"), ("x-foo", "v2")])
+    assert headers.pop("Content-Type") == "text/plain"
+    assert "content-type" not in headers
+    assert headers.popall("X-FOO") == ["v1", "v2"]
+    assert len(headers) == 0
+
+    headers = HeaderMap([("content-type", "text/plain")])
+    assert headers.pop("CONTENT-TYPE", None) == "text/plain"
+    assert headers.pop("Content-Type", None) is None
+
+
 def test_popitem():
     headers = HeaderMap([("a", "v1"), ("b", "v2"), ("a", "v3")])
     assert len(headers) == 3
@@ -371,6 +384,11 @@ def test_popitem():
     assert [kv for kv in popped if kv[0] == "a"] == [("a", "v1"), ("a", "v3")]
     assert sorted(popped) == [("a", "v1"), ("a", "v3"), ("b", "v2")]  # sorted as "b" is allowed to change order
 
+    headers = HeaderMap([("a", "v1"), ("b", "v2"), ("a", "v3"), ("c", "v4")])
+    while headers:
+        first = next(iter(headers.items()))
+        assert headers.popitem() == first  # Deterministic, pops the first iterated entry
+
     with pytest.raises(KeyError, match="HeaderMap is empty"):
         headers.popitem()
     with pytest.raises(KeyError, match="HeaderMap is empty"):
@@ -437,6 +455,22 @@ def test_setdefault():
         headers.setdefault("a", "v5\n")
 
 
+def test_setdefault_append():
+    headers = HeaderMap([("a", "v1"), ("b", "v2"), ("a", "v3")])
+
+    assert headers.setdefault_append("a", "v4") == ["v1", "v3"]
+    assert headers.getall("a") == ["v1", "v3"] and len(headers) == 3
+    assert headers.setdefault_append("c", "v4", is_sensitive=True) == ["v4"]
+    assert headers.getall("c") == ["v4"] and len(headers) == 4
+    assert headers.setdefault_append("c", "v5") == ["v4"]
+    assert headers.getall("c") == ["v4"]
+
+    with pytest.raises(ValueError, match="invalid HTTP header name"):
+        headers.setdefault_append("a\n", "v5")
+    with pytest.raises(ValueError, match="failed to parse header value"):
+        headers.setdefault_append("d", "v5\n")
+
+
 def test_getall():
     headers = HeaderMap([("a", "v1"), ("b", "v2"), ("a", "v3")])
     assert headers.getall("a") == ["v1", "v3"]
@@ -445,6 +479,23 @@ def test_getall():
     assert headers.getall("a\n") == []
 
 
+def test_get_all_str():
+    headers = HeaderMap([("a", "v1"), ("b", "bäd"), ("a", "v3")])
+    assert headers.get_all_str("a") == ["v1", "v3"]
+    assert headers.get_all_str("b") == ["bäd"]
+    assert headers.get_all_str("c") == []
+    with pytest.raises(ValueError, match="failed to convert header to a str"):
+        headers.getall("b")
+
+
+def test_get_str():
+    headers = HeaderMap([("a", "v1"), ("b", "bäd"), ("a", "v3")])
+    assert headers.get_str("a") == "v1"
+    assert headers.get_str("b") == "bäd"
+    assert headers.get_str("c") is None
+    assert headers.get_str("c", "default") == "default"
+
+
 def test_insert():
     headers = HeaderMap([("a", "v1"), ("b", "v2"), ("a", "v3")])
     assert len(headers) == 3
@@ -515,6 +566,27 @@ def test_append__sensitive(sensitive_arg: dict[str, bool]):
         assert repr(headers) == "HeaderMap({'a': ['v1', 'v2']})"
 
 
+def test_replace_all():
+    headers = HeaderMap([("a", "v1"), ("b", "v2"), ("a", "v3")])
+
+    assert headers.replace_all("a", ["v4", "v5", "v6"]) == ["v1", "v3"]
+    assert headers.getall("a") == ["v4", "v5", "v6"]
+    assert headers.getall("b") == ["v2"]
+    assert len(headers) == 4
+
+    assert headers.replace_all("c", ["v7"]) == []
+    assert headers.getall("c") == ["v7"]
+
+    assert headers.replace_all("A", []) == ["v4", "v5", "v6"]
+    assert "a" not in headers
+
+    with pytest.raises(ValueError, match="invalid HTTP header name"):
+        headers.replace_all("a\n", ["v8"])
+    with pytest.raises(ValueError, match="failed to parse header value"):
+        headers.replace_all("b", ["v8", "v9\n"])
+    assert headers.getall("b") == ["v2"]
+
+
 @pytest.mark.parametrize("kind", [list, tuple, dict, CIMultiDict, HeaderMap])
 def test_extend(kind: Callable[[list[Any]], Any]):
     headers = HeaderMap([("a", "v1"), ("b", "v2")])
@@ -534,6 +606,18 @@ def test_extend(kind: Callable[[list[Any]], Any]):
     assert sorted(headers.items()) == [("a", "v1"), ("a", "v4"), ("b", "v2"), ("c", "v3")]
 
 
+@pytest.mark.parametrize("kind", [list, tuple, dict, CIMultiDict, HeaderMap])
+def test_update_if_absent(kind: Callable[[list[Any]], Any]):
+    headers = HeaderMap({"Accept": "text/plain"})
+    headers.update_if_absent(kind([("Accept", "x"), ("User-Agent", "y")]))
+    assert sorted(headers.items()) == [("accept", "text/plain"), ("user-agent", "y")]
+
+    if kind is not dict:
+        headers.update_if_absent(kind([("accept", "z"), ("x-a", "1"), ("x-a", "2")]))
+        assert headers.getall("accept") == ["text/plain"]
+        assert headers.getall("x-a") == ["1", "2"]
+
+
 def test_popall():
     headers = HeaderMap([("a", "v1"), ("b", "v2"), ("a", "v3")])
     assert len(headers) == 3
@@ -569,6 +653,53 @@ def test_dict_multi_value():
     assert headers.dict_multi_value() == {"a": ["v1", "v3", "v4"], "b": "v2"}
     headers.append("b", "v5", is_sensitive=True)
     assert headers.dict_multi_value() == {"a": ["v1", "v3", "v4"], "b": ["v2", "v5"]}
+    assert headers.dict_multi_value(hide_sensitive=True) == {"a": ["v1", "v3", "v4"], "b": ["v2", "Sensitive"]}
+
+
+def test_is_sensitive():
+    headers = HeaderMap([("a", "v1")])
+    assert headers.is_sensitive("a") is False
+    assert headers.is_sensitive("missing") is False
+
+    headers.append("A", "v2", is_sensitive=True)
+    assert headers.is_sensitive("a") is True
+    headers.insert("a", "v3")
+    assert headers.is_sensitive("A") is False
+    headers.insert("a", "v4", is_sensitive=True)
+    assert headers.is_sensitive("a") is True
+
+
+def test_sorted_items():
+    assert HeaderMap().sorted_items() == []
+
+    items = [("X-B", "2"), ("a", "z"), ("x-b", "1"), ("C", "v"), ("A", "b"), ("x-b", "1")]
+    expected = [("a", "b"), ("a", "z"), ("c", "v"), ("x-b", "1"), ("x-b", "1"), ("x-b", "2")]
+    for seed in range(5):
+        shuffled = items.copy()
+        random.Random(seed).shuffle(shuffled)
+        assert HeaderMap(shuffled).sorted_items() == expected
+
+
+def test_filter():
+    headers = HeaderMap([("X-Internal-A", "1"), ("accept", "a"), ("x-b", "2"), ("Accept", "b"), ("x-internal-a", "3")])
+    headers.insert("authorization", "secret", is_sensitive=True)
+
+    filtered = headers.filter(lambda name, _: not name.startswith("x-"))
+    assert list(filtered.items()) == [("accept", "a"), ("accept", "b"), ("authorization", "secret")]
+    assert filtered.is_sensitive("authorization")
+    assert len(headers) == 6  # Original is unchanged
+
+    assert list(headers.filter(lambda _, value: value in ("1", "3")).items()) == [
+        ("x-internal-a", "1"),
+        ("x-internal-a", "3"),
+    ]
+    assert headers.filter(lambda *_: headers.get("accept") == "x") == HeaderMap()
+
+    def fail(*_: str) -> bool:
+        raise ValueError("fail")
+
+    with pytest.raises(ValueError, match="fail"):
+        headers.filter(fail)
 
 
 @pytest.mark.parametrize("std_copy", [False, True])
@@ -5,16 +5,21 @@ import weakref
 from asyncio import Task
 from collections.abc import AsyncGenerator, AsyncIterable
 from contextvars import ContextVar
+from datetime import timedelta
 from typing import Any
 
 import pytest
 from pyreqwest.client import Client, ClientBuilder
-from pyreqwest.exceptions import ConnectError
+from pyreqwest.client.types import RecordEntry
+from pyreqwest.exceptions import ConnectError, StatusError
+from pyreqwest.http import Url
 from pyreqwest.middleware import Next
+from pyreqwest.middleware.retry import RetryPolicy
 from pyreqwest.middleware.types import Middleware
 from pyreqwest.request import Request, RequestBody
 from pyreqwest.response import Response, ResponseBuilder
 
+from tests.servers.raw_server import RawServer
 from tests.servers.server_subprocess import SubprocessServer
 
 
@@ -386,6 +391,162 @@ async def test_proxy_modify_request(echo_server: SubprocessServer) -> None:
     assert dict((await res.json())["headers"])["host"].startswith("localhost:")
 
 
+async def test_record(echo_server: SubprocessServer) -> None:
+    records: list[RecordEntry] = []
+
+    async def middleware(request: Request, next_handler: Next) -> Response:
+        request.headers["x-test"] = "Val1"
+        return await next_handler.run(request)
+
+    async with ClientBuilder().with_middleware(middleware).record(records.append).build() as client:
+        await client.get(echo_server.url).build().send()
+        await client.post(echo_server.url.with_query({"status": 404})).build().send()
+        with pytest.raises(ConnectError):
+            await client.get("http://localhost:1").build().send()
+
+    assert [(r["method"], r["url"], r["status"]) for r in records] == [
+        ("GET", echo_server.url, 200),
+        ("POST", echo_server.url.with_query({"status": 404}), 404),
+        ("GET", Url("http://localhost:1"), None),
+    ]
+    assert records[0]["request_headers"]["x-test"] == "Val1"
+    assert records[0]["response_headers"] and records[0]["response_headers"]["content-type"] == "application/json"
+    assert records[0]["elapsed"] > timedelta(0) and records[0]["error"] is None
+    assert isinstance(records[2]["error"], ConnectError) and records[2]["response_headers"] is None
+
+
+async def test_tag(echo_server: SubprocessServer) -> None:
+    records: list[RecordEntry] = []
+    seen_tags: list[str | None] = []
+
+    async def middleware(request: Request, next_handler: Next) -> Response:
+        seen_tags.append(request.extensions.get("tag"))
+        return await next_handler.run(request)
+
+    async with ClientBuilder().with_middleware(middleware).record(records.append).build() as client:
+        resp = await client.get(echo_server.url / "users/1").extensions({"a": 1}).tag("get_user").build().send()
+        assert resp.extensions == {"a": 1, "tag": "get_user"}
+        resp = await client.get(echo_server.url / "users/2").tag("get_user").extensions({"b": 2}).build().send()
+        assert resp.extensions == {"b": 2, "tag": "get_user"}
+        await client.get(echo_server.url).build().send()
+
+    assert seen_tags == ["get_user", "get_user", None]
+    assert [r["tag"] for r in records] == ["get_user", "get_user", None]
+
+
+@pytest.mark.parametrize("error_for_status", [False, True])
+async def test_retries_middleware(echo_server: SubprocessServer, error_for_status: bool) -> None:
+    attempts = 0
+
+    async def fail_twice(request: Request, next_handler: Next) -> Response:
+        nonlocal attempts
+        attempts += 1
+        if attempts < 3:
+            request.url = request.url.with_query({"status": 503})
+        return await next_handler.run(request)
+
+    policy = RetryPolicy(max_attempts=3, backoff=timedelta(milliseconds=10))
+    builder = ClientBuilder().with_retries_middleware(policy).error_for_status(error_for_status)
+
+    async with builder.build() as client:
+        resp = await client.get(echo_server.url).with_middleware(fail_twice).build().send()
+        assert resp.status == 200 and (await resp.json())["method"] == "GET"
+        assert attempts == 3
+
+        attempts = 0
+        req = client.post(echo_server.url).with_middleware(fail_twice).build()
+        if error_for_status:
+            with pytest.raises(StatusError):
+                await req.send()  # Not idempotent
+        else:
+            assert (await req.send()).status == 503
+        assert attempts == 1
+
+
+async def test_retries_middleware__attempts(echo_server: SubprocessServer) -> None:
+    records: list[RecordEntry] = []
+    policy = RetryPolicy(max_attempts=3, backoff=timedelta(milliseconds=10), backoff_multiplier=1)
+    builder = ClientBuilder().with_retries_middleware(policy).record(records.append)
+
+    async with builder.build() as client:
+        resp = await client.get(echo_server.url.with_query({"status": 503})).build().send()
+        assert resp.status == 503
+        assert [r["status"] for r in records] == [503, 503, 503]
+
+        records.clear()
+        with pytest.raises(ConnectError):
+            await client.get("http://localhost:1").build().send()
+        assert len(records) == 3
+
+        records.clear()
+        assert (await client.get(echo_server.url.with_query({"status": 404})).build().send()).status == 404
+        assert len(records) == 1
+
+    with pytest.raises(TypeError, match="Expected a RetryPolicy"):
+        ClientBuilder().with_retries_middleware(3)  # type: ignore[arg-type]
+    with pytest.raises(ValueError, match="max_attempts must be at least 1"):
+        RetryPolicy(max_attempts=0)
+
+
+async def test_retries_middleware__closes_response(raw_server: RawServer) -> None:
+    readers: list[asyncio.StreamReader] = []
+    writers: list[asyncio.StreamWriter] = []
+    previous_closed: list[bool] = []
+
+    async def handler(reader: asyncio.StreamReader, writer: asyncio.StreamWriter) -> None:
+        await reader.readuntil(b"\r\n\r\n")
+        previous_closed.append(all(r.at_eof() for r in readers))
+        readers.append(reader)
+        writers.append(writer)
+        writer.write(b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 1000000\r\n\r\n" + b"a" * 100_000)
+        await writer.drain()
+
+    url = await raw_server.start(handler)
+    policy = RetryPolicy(max_attempts=3, backoff=timedelta(milliseconds=50))
+
+    async with ClientBuilder().with_retries_middleware(policy).build() as client:
+        async with client.get(url).build_streamed() as resp:
+            assert resp.status == 503
+
+    assert previous_closed == [True, True, True]  # Retried responses were released before the next attempt
+    for writer in writers:
+        writer.close()
+
+
+async def test_request_retry(raw_server: RawServer) -> None:
+    requests = 0
+
+    async def handler(reader: asyncio.StreamReader, writer: asyncio.StreamWriter) -> None:
+        nonlocal requests
+        await reader.readuntil(b"\r\n\r\n")
+        requests += 1
+        writer.write(b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+        await writer.drain()
+        writer.close()
+
+    url = await raw_server.start(handler)
+    backoff = timedelta(milliseconds=10)
+
+    async with ClientBuilder().build() as client:
+        assert (await client.post(url).body_text("a").retry(2, backoff).build().send()).status == 503
+        assert requests == 1  # Statuses are not retried by default
+
+        requests = 0
+        resp = await client.post(url).body_text("a").retry(2, backoff, retry_statuses=[503]).build().send()
+        assert resp.status == 503 and requests == 3
+
+        start = time.monotonic()
+        with pytest.raises(ConnectError):
+            await client.get("http://localhost:1").retry(2, backoff).build().send()
+        assert time.monotonic() - start >= 2 * backoff.total_seconds()
+
+        async def stream() -> AsyncGenerator[bytes]:
+            yield b"a"
+
+        with pytest.raises(TypeError, match="Request body can not be copied for retrying"):
+            await client.post(url).body_stream(stream()).retry(2, backoff).build().send()
+
+
 async def test_mocking_via_middleware(monkeypatch: pytest.MonkeyPatch) -> None:
     mocked_ids: set[int] = set()
     orig_build = ClientBuilder.build
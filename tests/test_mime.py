@@ -37,6 +37,26 @@ def test_mime():
     assert mime.essence_str == "image/svg+xml"
 
 
+def test_matches():
+    mime = Mime.parse("application/json; charset=utf-8")
+    assert mime.essence_str == "application/json"
+    assert mime.matches("application/json")
+    assert mime.matches("APPLICATION/JSON; charset=latin1")
+    assert mime.matches(Mime.parse("application/json"))
+    assert mime.matches("application/*")
+    assert mime.matches("*/*")
+    assert not mime.matches("application/xml")
+    assert not mime.matches("text/*")
+
+    mime = Mime.parse("image/svg+xml")
+    assert mime.matches("image/svg+xml")
+    assert mime.matches("image/*")
+    assert not mime.matches("image/svg")
+
+    with pytest.raises(ValueError, match="mime parse error"):
+        mime.matches("invalid")
+
+
 def test_eq():
     mime = Mime.parse("text/plain")
     assert mime == Mime.parse("text/plain")
@@ -89,6 +109,21 @@ def test_hash():
     assert d.get(Mime.parse("application/json; charset=utf-8")) is None
 
 
+@pytest.mark.parametrize(
+    ("mime1", "mime2"),
+    [
+        ("application/json", "application/json"),
+        ("Application/JSON", "application/json"),
+        ("text/plain;charset=utf-8", "text/plain; charset=utf-8"),
+        ("text/plain; charset=UTF-8", "text/plain;  charset=utf-8"),
+    ],
+)
+def test_hash_eq_consistent(mime1: str, mime2: str):
+    assert Mime.parse(mime1) == Mime.parse(mime2)
+    assert hash(Mime.parse(mime1)) == hash(Mime.parse(mime2))
+    assert {Mime.parse(mime1): 1}[Mime.parse(mime2)] == 1
+
+
 @pytest.mark.parametrize(
     "mime_str",
     ["application/json", "application/json; charset=utf-8", "application/json;charset=utf-8"],
@@ -132,6 +132,26 @@ async def test_multipart_with_part_file(echo_server: SubprocessServer, file: str
     assert file_part.headers[b"content-type"] == b"text/plain; charset=utf-8"
 
 
+@pytest.mark.parametrize("file", ["async", "sync"])
+async def test_multipart_part_file_overrides(client: Client, echo_server: SubprocessServer, file: str):
+    with temp_file(b"file content", suffix=".txt") as tmp:
+        if file == "async":
+            file_part = await PartBuilder.from_file(tmp, file_name="custom.bin", mime="application/octet-stream")
+        else:
+            assert file == "sync"
+            file_part = PartBuilder.from_sync_file(tmp, file_name="custom.bin", mime=Mime.parse("application/json"))
+
+    form = FormBuilder().part("attachment", file_part)
+    resp = await client.post(echo_server.url).multipart(form).build().send()
+    decoder = decode_multipart(await resp.json())
+
+    assert len(decoder.parts) == 1
+    part = decoder.parts[0]
+    assert part.content == b"file content"
+    assert part.headers[b"content-disposition"] == b'form-data; name="attachment"; filename="custom.bin"'
+    assert part.headers[b"content-type"] == (b"application/octet-stream" if file == "async" else b"application/json")
+
+
 @pytest.mark.parametrize("sync", [False, True])
 @pytest.mark.parametrize("with_length", [False, True])
 async def test_multipart_with_stream_part(client: Client, echo_server: SubprocessServer, sync: bool, with_length: bool):
@@ -185,6 +205,25 @@ async def test_multipart_with_async_stream_async_part_in_sync_request(echo_serve
         req_builder.multipart(form)
 
 
+async def test_multipart_require_known_length(client: Client, echo_server: SubprocessServer):
+    async def data_stream() -> AsyncGenerator[bytes, None]:
+        yield b"First chunk"
+        yield b" Second chunk"
+
+    form = FormBuilder().require_known_length(True).text("type", "streaming")
+    form = form.part("data", PartBuilder.from_stream(data_stream()))
+    with pytest.raises(BuilderError, match="Multipart form has a part with unknown length"):
+        client.post(echo_server.url).multipart(form)
+
+    form = FormBuilder().require_known_length(True).text("type", "streaming")
+    form = form.part("data", PartBuilder.from_stream_with_length(data_stream(), length=24))
+    resp = await client.post(echo_server.url).multipart(form).build().send()
+    data = await resp.json()
+    assert any(name == "content-length" for name, _ in data["headers"])
+    data_part = next(p for p in decode_multipart(data).parts if b'name="data"' in p.headers[b"content-disposition"])
+    assert data_part.content == b"First chunk Second chunk"
+
+
 async def test_multipart_with_bytes_part(client: Client, echo_server: SubprocessServer):
     binary_data = b"Binary content \x00\x01\x02"
     part = PartBuilder.from_bytes(binary_data).mime("application/octet-stream")
@@ -1,12 +1,15 @@
+import asyncio
+import struct
 from collections.abc import Callable
 
 import pytest
 import trustme
-from pyreqwest.client import ClientBuilder
+from pyreqwest.client import Client, ClientBuilder
 from pyreqwest.exceptions import ConnectError, RequestPanicError
 from pyreqwest.http import Url
 from pyreqwest.proxy import ProxyBuilder
 
+from tests.servers.raw_server import RawHandler, RawServer
 from tests.servers.server_subprocess import SubprocessServer
 
 
@@ -58,6 +61,54 @@ async def test_proxy_custom(echo_server: SubprocessServer):
             await client.get("http://foo2.invalid/").build().send()  # not captured
 
 
+async def test_proxy_multiple__connections_not_shared(echo_server: SubprocessServer):
+    def proxy(host: str) -> ProxyBuilder:
+        return ProxyBuilder.custom(lambda url: echo_server.url if url.host_str == host else None).headers(
+            {"x-proxy": host}
+        )
+
+    builder = ClientBuilder().proxy(proxy("one.invalid")).proxy(proxy("two.invalid")).pool_max_idle_per_host(1)
+    async with builder.error_for_status(True).build() as client:
+        for host in ["one.invalid", "two.invalid", "one.invalid", "two.invalid"]:
+            resp = await client.get(f"http://{host}/").build().send()
+            headers = (await resp.json())["headers"]
+            assert ["x-proxy", host] in headers
+            assert ["host", host] in headers
+
+
+async def test_proxy_same_host__connections_not_shared(echo_server: SubprocessServer, raw_server: RawServer):
+    requests: dict[str, list[int]] = {"one": [], "two": []}  # Request count per connection
+
+    def start_proxy(name: str) -> RawHandler:
+        async def pipe(reader: asyncio.StreamReader, writer: asyncio.StreamWriter, conn: int | None) -> None:
+            while data := await reader.read(65536):
+                if conn is not None:
+                    requests[name][conn] += data.count(b" HTTP/1.1\r\n")
+                writer.write(data)
+                await writer.drain()
+            writer.close()
+
+        async def handler(reader: asyncio.StreamReader, writer: asyncio.StreamWriter) -> None:
+            requests[name].append(0)
+            conn = len(requests[name]) - 1
+            target_reader, target_writer = await asyncio.open_connection(echo_server.url.host_str, echo_server.url.port)
+            await asyncio.gather(pipe(reader, target_writer, conn), pipe(target_reader, writer, None))
+
+        return handler
+
+    def client(proxy_url: Url) -> Client:
+        return ClientBuilder().proxy(ProxyBuilder.all(proxy_url)).max_connections(1).error_for_status(True).build()
+
+    proxy1 = await raw_server.start(start_proxy("one"))
+    proxy2 = await raw_server.start(start_proxy("two"))
+    async with client(proxy1) as client1, client(proxy2) as client2:
+        for c in [client1, client2, client1, client2]:
+            resp = await c.get("http://same.invalid/").build().send()
+            assert ["host", "same.invalid"] in (await resp.json())["headers"]
+
+    assert requests == {"one": [2], "two": [2]}
+
+
 @pytest.mark.parametrize("case", ["raises", "bad_return"])
 async def test_proxy_custom__fail(case: str):
     def proxy_func_raises(_url: Url) -> str | None:
@@ -121,3 +172,38 @@ async def test_no_proxy(echo_server: SubprocessServer):
 
         resp = await client.get("http://doproxy.invalid/").build().send()
         assert ["host", "doproxy.invalid"] in (await resp.json())["headers"]
+
+
+async def test_socks5_proxy(echo_server: SubprocessServer, raw_server: RawServer):
+    requested: list[tuple[str, int]] = []
+
+    async def pipe(reader: asyncio.StreamReader, writer: asyncio.StreamWriter) -> None:
+        while data := await reader.read(65536):
+            writer.write(data)
+            await writer.drain()
+        writer.close()
+
+    async def handler(reader: asyncio.StreamReader, writer: asyncio.StreamWriter) -> None:
+        _, n_methods = await reader.readexactly(2)
+        await reader.readexactly(n_methods)
+        writer.write(b"\x05\x00")  # No authentication
+        _, _, _, addr_type = await reader.readexactly(4)
+        assert addr_type == 3  # Domain name, resolved by the proxy (socks5h)
+        host = (await reader.readexactly((await reader.readexactly(1))[0])).decode()
+        requested.append((host, struct.unpack(">H", await reader.readexactly(2))[0]))
+        target_reader, target_writer = await asyncio.open_connection(echo_server.url.host_str, echo_server.url.port)
+        writer.write(b"\x05\x00\x00\x01" + bytes(6))  # Succeeded
+        await asyncio.gather(pipe(reader, target_writer), pipe(target_reader, writer))
+
+    proxy = ProxyBuilder.all(f"socks5h://127.0.0.1:{(await raw_server.start(handler)).port}")
+
+    async with ClientBuilder().proxy(proxy).error_for_status(True).build() as client:
+        resp = await client.get("http://socks.invalid:1234/test").build().send()
+        assert (await resp.json())["path"] == "/test"
+        assert requested == [("socks.invalid", 1234)]
+
+
+@pytest.mark.parametrize("proxy_type", ["http", "https", "all"])
+def test_unsupported_scheme(proxy_type: str):
+    with pytest.raises(ValueError, match="Invalid proxy: unsupported scheme 'ftp', expected http, https, socks4"):
+        getattr(ProxyBuilder, proxy_type)("ftp://127.0.0.1:1080")
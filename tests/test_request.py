@@ -182,6 +182,31 @@ async def test_body__stream_class(client: Client, echo_server: SubprocessServer)
     assert (await resp.json())["body_parts"] == ["test1", "test2"]
 
 
+async def test_body__chunks(client: Client, echo_server: SubprocessServer) -> None:
+    req = client.post(echo_server.url).build()
+    req.body = RequestBody.from_chunks([b"test1", bytearray(b"test2"), memoryview(b"test3")])
+    assert req.body is not None and req.body.copy_bytes() == b"test1test2test3"
+    assert req.body.get_stream() == [b"test1", b"test2", b"test3"]
+    assert repr(req.body) == "RequestBody(chunks=3, len=15)"
+
+    req_copy = req.copy()
+    resp = await req.send()
+    assert "".join((await resp.json())["body_parts"]) == "test1test2test3"
+    resp = await req_copy.send()
+    assert "".join((await resp.json())["body_parts"]) == "test1test2test3"
+
+
+@pytest.mark.parametrize("status", [307, 308])
+async def test_body__chunks_redirect(client: Client, echo_server: SubprocessServer, status: int) -> None:
+    req = client.post(echo_server.url.with_query({"status": status, "header_location": "/redirect"})).build()
+    req.body = RequestBody.from_chunks([b"test1", b"test2", b"test3"])
+    resp = await req.send()
+    assert resp.status == 200
+    body = await resp.json()
+    assert body["path"] == "/redirect" and body["method"] == "POST"
+    assert "".join(body["body_parts"]) == "test1test2test3"
+
+
 async def test_body__stream_error(client: Client, echo_server: SubprocessServer) -> None:
     class StreamGen:
         def __aiter__(self) -> AsyncGenerator[bytes]:
@@ -322,6 +347,15 @@ async def test_copy(echo_server: SubprocessServer, call: str, build_streamed: bo
         assert (await resp1.json()) == (await resp2.json())
 
 
+async def test_copy__stream_without_copy(client: Client, echo_server: SubprocessServer) -> None:
+    async def stream() -> AsyncGenerator[bytes]:
+        yield b"test1"
+
+    req = client.post(echo_server.url).body_stream(stream()).build()
+    with pytest.raises(TypeError, match="Stream body can not be copied, it must implement __copy__"):
+        req.copy()
+
+
 async def test_duplicate_send_fails(client: Client, echo_server: SubprocessServer) -> None:
     req = client.get(echo_server.url).build()
     await req.send()
@@ -1,14 +1,20 @@
-from collections.abc import AsyncGenerator, Mapping, Sequence
+import asyncio
+import base64
+import hashlib
+import json
+from collections.abc import AsyncGenerator, Generator, Mapping, Sequence
 from datetime import timedelta
-from typing import Any
+from typing import Any, Literal
 
 import pytest
 import trustme
 from pyreqwest.client import Client, ClientBuilder
-from pyreqwest.exceptions import BuilderError, ConnectTimeoutError, StatusError
-from pyreqwest.http import HeaderMap
-from pyreqwest.request import RequestBuilder
+from pyreqwest.client.types import JsonLoadsContext
+from pyreqwest.exceptions import ApiError, BuilderError, ConnectTimeoutError, RequestError, StatusError
+from pyreqwest.http import HeaderMap, Url
+from pyreqwest.request import ConsumedRequest, RequestBody, RequestBuilder, RequestTemplate
 
+from tests.servers.raw_server import RawServer
 from tests.servers.server_subprocess import SubprocessServer
 from tests.utils import IS_CI
 
@@ -32,6 +38,78 @@ async def test_build_streamed(client: Client, echo_body_parts_server: Subprocess
         assert (await resp.text()) == sent
 
 
+async def test_send_head_only(echo_server: SubprocessServer):
+    url = echo_server.url.with_query({"sleep_body": 10, "header_X_Test": "val"})
+    builder = ClientBuilder().max_connections(1).pool_timeout(timedelta(seconds=1)).error_for_status(True)
+    async with builder.build() as client:
+        resp = await client.get(url).send_head_only()
+        assert resp.status == 200 and resp.headers["x-test"] == "val"
+        # Connection was released without waiting for the body
+        assert (await client.get(url).send_head_only()).status == 200
+        with pytest.raises(RuntimeError, match="Response body reader is closed"):
+            await resp.bytes()
+
+
+async def test_send_head_only__large_body(raw_server: RawServer):
+    body_size = 10 * 1024 * 1024
+    body_sent: list[bool] = []
+
+    async def handler(reader: asyncio.StreamReader, writer: asyncio.StreamWriter) -> None:
+        await reader.readuntil(b"\r\n\r\n")
+        writer.write(f"HTTP/1.1 200 OK\r\nContent-Length: {body_size}\r\n\r\n".encode())
+        await writer.drain()
+        try:
+            await asyncio.wait_for(reader.read(), 1)  # Client closes the connection without waiting for the body
+            body_sent.append(False)
+        except TimeoutError:
+            writer.write(b"a" * body_size)
+            body_sent.append(True)
+        writer.close()
+
+    url = await raw_server.start(handler)
+    async with ClientBuilder().error_for_status(True).build() as client:
+        resp = await client.get(url).send_head_only()
+        assert resp.status == 200 and resp.headers["content-length"] == str(body_size)
+        with pytest.raises(RuntimeError, match="Response body reader is closed"):
+            await resp.bytes()
+        while not body_sent:
+            await asyncio.sleep(0.01)
+        assert body_sent == [False]  # No body bytes were read
+
+
+async def test_freeze(client: Client, echo_server: SubprocessServer):
+    template = client.post(echo_server.url).header("X-Test", "Val").body_text("test1").freeze()
+    assert type(template) is RequestTemplate
+    assert repr(template).startswith("RequestTemplate(")
+
+    requests = [template.clone() for _ in range(3)]
+    assert len({id(req) for req in requests}) == 3
+    for req in requests:
+        assert type(req) is ConsumedRequest
+        resp = await req.send()
+        data = await resp.json()
+        assert ["x-test", "Val"] in data["headers"]
+        assert data["body_parts"] == ["test1"]
+
+    req = template.clone()
+    req.headers["X-Test"] = "Changed"  # Does not affect the template
+    assert template.clone().headers["X-Test"] == "Val"
+
+
+@pytest.mark.parametrize("prefetch", [1, 4, 16])
+async def test_prefetch_chunks(client: Client, echo_body_parts_server: SubprocessServer, prefetch: int):
+    assert RequestBuilder.default_prefetch_chunks() == 1
+    sent = b"a" * (RequestBuilder.default_streamed_read_buffer_limit() * 20)
+
+    req = client.post(echo_body_parts_server.url).body_bytes(sent).prefetch_chunks(prefetch).build_streamed()
+    assert req.prefetch_chunks == prefetch
+    async with req as resp:
+        assert (await resp.bytes()) == sent
+
+    with pytest.raises(ValueError, match="prefetch_chunks must be at least 1"):
+        client.get(echo_body_parts_server.url).prefetch_chunks(0)
+
+
 @pytest.mark.parametrize("value", [True, False])
 async def test_error_for_status(echo_server: SubprocessServer, value: bool):
     url = echo_server.url.with_query({"status": 400})
@@ -46,6 +124,36 @@ async def test_error_for_status(echo_server: SubprocessServer, value: bool):
             assert (await req.send()).status == 400
 
 
+async def test_error_for_json(client: Client, echo_body_parts_server: SubprocessServer):
+    def predicate(obj: Any) -> Any:
+        return obj.get("error") if isinstance(obj, dict) else None
+
+    req = client.post(echo_body_parts_server.url).body_json({"error": "bad"}).error_for_json(predicate).build()
+    with pytest.raises(ApiError, match="Response JSON contains an error") as e:
+        await req.send()
+    assert e.value.details == {"status": 200, "error": "bad"}
+
+    resp = await client.post(echo_body_parts_server.url).body_json({"ok": 1}).error_for_json(predicate).build().send()
+    assert (await resp.json()) == {"ok": 1}
+
+    resp = await client.post(echo_body_parts_server.url).body_text("not json").error_for_json(predicate).build().send()
+    assert (await resp.text()) == "not json"
+
+    with pytest.raises(BuilderError, match="Can not use error_for_json with a streamed request"):
+        client.post(echo_body_parts_server.url).error_for_json(predicate).build_streamed()
+
+
+async def test_error_for_json__json_handler(echo_body_parts_server: SubprocessServer):
+    async def custom_loads(ctx: JsonLoadsContext) -> Any:
+        return {"wrapped": json.loads((await ctx.body_reader.bytes()).to_bytes())}
+
+    async with ClientBuilder().json_handler(loads=custom_loads).build() as client:
+        req = client.post(echo_body_parts_server.url).body_json({"error": "bad"})
+        with pytest.raises(ApiError) as e:
+            await req.error_for_json(lambda obj: obj["wrapped"].get("error")).build().send()
+        assert e.value.details == {"status": 200, "error": "bad"}
+
+
 async def test_header(client: Client, echo_server: SubprocessServer):
     resp = await client.get(echo_server.url).header("X-Test", "Val").build().send()
     assert ["x-test", "Val"] in (await resp.json())["headers"]
@@ -109,6 +217,84 @@ async def test_body_stream(client: Client, echo_body_parts_server: SubprocessSer
     assert (await resp.body_reader.read_chunk()) is None
 
 
+async def test_body_json_url(client: Client, echo_server: SubprocessServer):
+    url = Url("https://example.com/a?b=1")
+    body = {"url": url, "urls": [url, (url, 1)], url: "key"}
+    resp = await client.post(echo_server.url).body_json(body).build().send()
+    assert json.loads("".join((await resp.json())["body_parts"])) == {
+        "url": "https://example.com/a?b=1",
+        "urls": ["https://example.com/a?b=1", ["https://example.com/a?b=1", 1]],
+        "https://example.com/a?b=1": "key",
+    }
+    assert url.to_json() == "https://example.com/a?b=1"
+
+    with pytest.raises(TypeError, match="unsupported type"):
+        client.post(echo_server.url).body_json({"url": url, "bad": object()})
+
+
+@pytest.mark.parametrize("kind", ["list", "tuple", "generator", "async_generator"])
+async def test_body_json_lines(client: Client, echo_server: SubprocessServer, kind: str):
+    items = [{"a": 1}, {"b": [1, 2]}, {"c": "d"}]
+
+    def gen() -> Generator[Any, None, None]:
+        yield from items
+
+    async def async_gen() -> AsyncGenerator[Any, None]:
+        for item in items:
+            yield item
+
+    arg: Any = {"list": items, "tuple": tuple(items), "generator": gen(), "async_generator": async_gen()}[kind]
+    resp = await client.post(echo_server.url).body_json_lines(arg).build().send()
+    resp_json = await resp.json()
+    assert ["content-type", "application/x-ndjson"] in resp_json["headers"]
+    lines = "".join(resp_json["body_parts"]).split("\n")
+    assert lines.pop() == ""
+    assert [json.loads(line) for line in lines] == items
+
+
+@pytest.mark.parametrize("count", [0, 1, 3])
+async def test_body_json_stream(client: Client, echo_server: SubprocessServer, count: int):
+    items = [{"a": 1}, {"b": [1, 2]}, {"c": "d"}][:count]
+
+    async def async_gen() -> AsyncGenerator[Any, None]:
+        for item in items:
+            yield item
+
+    resp = await client.post(echo_server.url).body_json_stream(async_gen()).build().send()
+    resp_json = await resp.json()
+    assert ["content-type", "application/json"] in resp_json["headers"]
+    assert json.loads("".join(resp_json["body_parts"])) == items
+
+    req = client.post(echo_server.url).build()
+    req.body = RequestBody.from_json_stream(iter(items))
+    resp_json = await (await req.send()).json()
+    assert json.loads("".join(resp_json["body_parts"])) == items
+
+
+@pytest.mark.parametrize("algorithm", ["sha-256", "sha-512"])
+async def test_add_digest(client: Client, echo_server: SubprocessServer, algorithm: Literal["sha-256", "sha-512"]):
+    def digest(body: bytes) -> str:
+        value = hashlib.new(algorithm.replace("-", ""), body).digest()
+        return f"{algorithm}={base64.b64encode(value).decode()}"
+
+    resp = await client.post(echo_server.url).body_bytes(b"test body").add_digest(algorithm).build().send()
+    assert ["digest", digest(b"test body")] in (await resp.json())["headers"]
+
+    resp = await client.post(echo_server.url).form({"a": "b"}).add_digest(algorithm).build().send()
+    assert ["digest", digest(b"a=b")] in (await resp.json())["headers"]
+
+    resp = await client.get(echo_server.url).add_digest(algorithm).add_digest(algorithm).build().send()
+    assert [h for h in (await resp.json())["headers"] if h[0] == "digest"] == [["digest", digest(b"")]]
+
+    async def stream_gen() -> AsyncGenerator[bytes, None]:
+        yield b"test"
+
+    with pytest.raises(ValueError, match="Digest requires a buffered body"):
+        client.post(echo_server.url).body_stream(stream_gen()).add_digest(algorithm)
+    with pytest.raises(ValueError, match="Unsupported digest algorithm: md5"):
+        client.post(echo_server.url).add_digest("md5")  # type: ignore[arg-type]
+
+
 @pytest.mark.parametrize("server_sleep", [0.1, 0.01, None])
 async def test_timeout(client: Client, echo_server: SubprocessServer, server_sleep: float | None):
     timeout = 0.5 if IS_CI else 0.05
@@ -129,6 +315,18 @@ async def test_timeout(client: Client, echo_server: SubprocessServer, server_sle
         client.get(echo_server.url).timeout(1.0)  # type: ignore[arg-type]
 
 
+async def test_timeout__none(echo_server: SubprocessServer):
+    timeout = 0.5 if IS_CI else 0.05
+    url = echo_server.url.with_query({"sleep_start": timeout * 4})
+
+    async with ClientBuilder().timeout(timedelta(seconds=timeout)).error_for_status(True).build() as client:
+        with pytest.raises(ConnectTimeoutError):
+            await client.get(url).build().send()
+
+        resp = await client.get(url).timeout(None).build().send()
+        assert resp.status == 200
+
+
 async def test_query(client: Client, echo_server: SubprocessServer):
     async def send(arg: Sequence[tuple[str, str]] | Mapping[str, str]) -> list[list[str]]:
         resp = await client.get(echo_server.url).query(arg).build().send()
@@ -147,6 +345,47 @@ async def test_query(client: Client, echo_server: SubprocessServer):
         assert (await resp.json())["query"] == [["foo", "bar"], ["foo", "baz"]]
 
 
+async def test_prefix_path(client: Client, echo_server: SubprocessServer):
+    async with ClientBuilder().base_url(echo_server.url).error_for_status(True).build() as base_client:
+        resp = await base_client.get("/users").prefix_path("v2").build().send()
+        assert (await resp.json())["path"] == "/v2/users"
+
+    builder = client.get(echo_server.url.with_path("/users/1").with_query({"a": "b"})).prefix_path("/api/v 2/")
+    assert builder.url == echo_server.url.with_path("/api/v%202/users/1").with_query({"a": "b"})
+    assert client.get(echo_server.url).prefix_path("v2").prefix_path("api").url.path == "/api/v2/"
+
+    resp = await client.get(echo_server.url / "users").prefix_path("v2").build().send()
+    assert (await resp.json())["path"] == "/v2/users"
+
+
+async def test_url(client: Client, echo_server: SubprocessServer):
+    builder = client.get(echo_server.url).query({"foo": "bar"})
+    assert builder.url == echo_server.url.with_query({"foo": "bar"})
+
+    builder.url = builder.url.with_path("/sub/path")
+    assert builder.url == echo_server.url.with_path("/sub/path").with_query({"foo": "bar"})
+
+    req = builder.query({"a": "b"}).build()
+    assert req.url == echo_server.url.with_path("/sub/path").with_query({"foo": "bar", "a": "b"})
+    resp = await req.send()
+    assert (await resp.json())["path"] == "/sub/path"
+
+    builder = client.get(echo_server.url)
+    builder.url = "http://localhost:1/other"
+    assert builder.build().url == "http://localhost:1/other"
+
+
+async def test_query_replace(client: Client, echo_server: SubprocessServer):
+    builder = client.get(echo_server.url.with_query({"a": "1"})).query({"b": "2"})
+    assert builder.url.query_pairs == [("a", "1"), ("b", "2")]
+
+    resp = await builder.query_replace([("c", "3"), ("c", "4")]).build().send()
+    assert (await resp.json())["query"] == [["c", "3"], ["c", "4"]]
+
+    req = client.get(echo_server.url.with_query({"a": "1"})).query_replace({}).build()
+    assert req.url.query_string is None
+
+
 async def test_version(client: Client, echo_server: SubprocessServer, https_echo_server: SubprocessServer):
     resp = await client.get(echo_server.url).build().send()
     assert (await resp.json())["http_version"] == "1.1"
@@ -154,6 +393,22 @@ async def test_version(client: Client, echo_server: SubprocessServer, https_echo
     assert (await resp.json())["http_version"] == "2"
 
 
+async def test_version__forced(client: Client, echo_server: SubprocessServer, https_echo_server: SubprocessServer):
+    resp = await client.get(echo_server.url).version("HTTP/1.0").build().send()
+    assert (await resp.json())["http_version"] == "1.0"
+    resp = await client.get(echo_server.url).version("HTTP/1.1").build().send()
+    assert (await resp.json())["http_version"] == "1.1"
+    resp = await client.get(https_echo_server.url).version("HTTP/2").build().send()
+    assert (await resp.json())["http_version"] == "2"
+
+    req = client.get(echo_server.url).version("HTTP/2").build()
+    with pytest.raises(RequestError, match="error sending request"):
+        await req.send()  # Connection is HTTP/1
+
+    with pytest.raises(ValueError, match="Invalid HTTP version"):
+        client.get(echo_server.url).version("HTTP/3")
+
+
 async def test_form(client: Client, echo_server: SubprocessServer):
     async def send(arg: Sequence[tuple[str, str]] | Mapping[str, str]) -> str:
         resp = await client.get(echo_server.url).form(arg).build().send()
@@ -193,6 +448,24 @@ async def test_form_query_invalid(client: Client, echo_server: SubprocessServer,
     assert e.value.details and {"message": "unsupported value"} in e.value.details["causes"]
 
 
+async def test_invalid_request__failed_call(client: Client, echo_server: SubprocessServer):
+    builder = client.get(echo_server.url).query([("foo", {"a": "b"})])
+
+    for _ in range(2):  # Builder keeps failing with the original error
+        with pytest.raises(BuilderError, match="Failed to build request") as e:
+            _ = builder.url
+        assert e.value.details and {"message": "unsupported value"} in e.value.details["causes"]
+
+    with pytest.raises(BuilderError, match="Failed to build request"):
+        builder.timeout(None)
+    with pytest.raises(BuilderError, match="Failed to build request"):
+        builder.query_replace({"foo": "bar"})
+    with pytest.raises(BuilderError, match="Failed to build request"):
+        builder.header("x-foo", "bar")
+    with pytest.raises(BuilderError, match="Failed to build request"):
+        builder.build()
+
+
 async def test_form_fails_with_body_set(client: Client, echo_server: SubprocessServer):
     with pytest.raises(BuilderError, match="Can not set body when multipart or form is used"):
         client.post(echo_server.url).form({"a": "b"}).body_text("fail").build()
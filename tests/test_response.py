@@ -1,12 +1,18 @@
 import asyncio
 import gc
 import json
+import math
+import random
 import string
 import sys
 import time
 import weakref
 from asyncio import Task
+from collections import namedtuple
 from collections.abc import AsyncGenerator, AsyncIterator, Iterator, MutableMapping
+from decimal import Decimal
+from http import HTTPStatus
+from pathlib import Path
 from typing import Any
 
 import pytest
@@ -17,6 +23,7 @@ from pyreqwest.exceptions import BodyDecodeError, JSONDecodeError, StatusError
 from pyreqwest.http import HeaderMap
 from pyreqwest.response import ResponseBuilder
 
+from tests.servers.raw_server import RawServer
 from tests.servers.server_subprocess import SubprocessServer
 
 
@@ -43,6 +50,30 @@ async def test_status(client: Client, echo_server: SubprocessServer) -> None:
         resp.status = 9999
 
 
+async def test_status_enum(client: Client, echo_server: SubprocessServer) -> None:
+    resp = await client.get(echo_server.url).build().send()
+    assert resp.status_enum is HTTPStatus.OK
+
+    resp.status = 404
+    status = resp.status_enum
+    assert status is HTTPStatus.NOT_FOUND and status.name == "NOT_FOUND"
+    assert status == resp.status == 404
+
+    resp.status = 599
+    assert resp.status_enum is None
+
+
+@pytest.mark.parametrize(("status", "expected"), [(200, True), (302, False), (404, False), (500, False)])
+async def test_bool(client: Client, echo_server: SubprocessServer, status: int, expected: bool) -> None:
+    url = echo_server.url.with_query({"status": status})
+    resp = await client.get(url).error_for_status(False).build().send()
+    assert resp.status == status
+    assert bool(resp) is expected
+
+    resp.status = 200
+    assert bool(resp) is True
+
+
 async def test_headers(client: Client, echo_server: SubprocessServer) -> None:
     req = (
         client.get(echo_server.url)
@@ -72,6 +103,21 @@ async def test_headers(client: Client, echo_server: SubprocessServer) -> None:
     assert type(resp.headers) is HeaderMap and isinstance(resp.headers, MutableMapping)
 
 
+async def test_copy_headers(client: Client, echo_server: SubprocessServer) -> None:
+    resp = await client.get(echo_server.url).query({"header_x_test": "Value1"}).build().send()
+
+    headers = resp.copy_headers()  # Before headers are accessed
+    headers["X-Test"] = "Changed"
+    headers["X-New"] = "New"
+    assert resp.get_header("x-test") == "Value1" and resp.get_header("x-new") is None
+
+    assert resp.headers["x-test"] == "Value1"
+    headers = resp.copy_headers()  # After headers are accessed
+    assert headers == resp.headers
+    headers.pop("X-Test")
+    assert resp.headers["x-test"] == "Value1"
+
+
 @pytest.mark.parametrize("proto", ["http", "https"])
 async def test_version(
     client: Client, echo_server: SubprocessServer, https_echo_server: SubprocessServer, proto: str
@@ -138,6 +184,267 @@ async def test_body(client: Client, echo_body_parts_server: SubprocessServer, ki
         assert (await resp.body_reader.read_chunk()) is None
 
 
+async def test_json_object_hook(client: Client, echo_body_parts_server: SubprocessServer) -> None:
+    Point = namedtuple("Point", ["x", "y"])  # noqa: PYI024
+    body = b'{"points": [{"x": 1, "y": 2}, {"x": 3, "y": 4}], "origin": {"x": 0, "y": 0}, "n": [1, "a"]}'
+
+    def hook(obj: dict[str, Any]) -> Any:
+        return Point(**obj) if obj.keys() == {"x", "y"} else obj
+
+    resp = await client.post(echo_body_parts_server.url).body_bytes(body).build().send()
+    res = await resp.json(object_hook=hook)
+    assert res == {"points": [Point(1, 2), Point(3, 4)], "origin": Point(0, 0), "n": [1, "a"]}
+    assert type(res["origin"]) is Point
+    assert res == json.loads(body, object_hook=hook)
+    assert (await resp.json()) == json.loads(body)  # Hook is not applied to the cached body
+
+    called: list[Any] = []
+    assert (await resp.json(object_hook=lambda o: called.append(o) or len(o))) == 3
+    assert len(called) == 4
+
+
+async def test_json_on_duplicate_key(client: Client, echo_body_parts_server: SubprocessServer) -> None:
+    body = b'{"a": 1, "a": 2, "b": [{"c": 3, "c": 4}, 1.5, null, true, "s"]}'
+    resp = await client.post(echo_body_parts_server.url).body_bytes(body).build().send()
+
+    assert (await resp.json()) == {"a": 2, "b": [{"c": 4}, 1.5, None, True, "s"]}
+    assert (await resp.json(on_duplicate_key="last")) == {"a": 2, "b": [{"c": 4}, 1.5, None, True, "s"]}
+    assert (await resp.json(on_duplicate_key="first")) == {"a": 1, "b": [{"c": 3}, 1.5, None, True, "s"]}
+    with pytest.raises(JSONDecodeError, match="duplicate key `a`") as e:
+        await resp.json(on_duplicate_key="error")
+    assert e.value.pos == 14
+    with pytest.raises(ValueError, match="Invalid on_duplicate_key: foo"):
+        await resp.json(on_duplicate_key="foo")  # type: ignore[arg-type]
+
+
+async def test_json_exact_numbers(client: Client, echo_body_parts_server: SubprocessServer) -> None:
+    big = 123456789012345678901234567890
+    body = f'{{"big": {big}, "neg": -{big}, "f": 0.1, "a": 1, "a": 2, "l": [1.10, "s", null]}}'.encode()
+    resp = await client.post(echo_body_parts_server.url).body_bytes(body).build().send()
+
+    assert (await resp.json())["big"] != big  # Precision is lost
+    res = await resp.json(exact_numbers=True)
+    assert res == {"big": big, "neg": -big, "f": Decimal("0.1"), "a": 2, "l": [Decimal("1.10"), "s", None]}
+    assert type(res["big"]) is int
+    assert res == json.loads(body, parse_float=Decimal)
+    assert (await resp.json(exact_numbers=True, on_duplicate_key="first"))["a"] == 1
+    with pytest.raises(JSONDecodeError, match="duplicate key `a`"):
+        await resp.json(exact_numbers=True, on_duplicate_key="error")
+
+    resp = await client.post(echo_body_parts_server.url).body_bytes(b'{"a": 1,}').build().send()
+    with pytest.raises(JSONDecodeError, match="Expecting property name") as e:
+        await resp.json(exact_numbers=True)
+    assert e.value.pos == 8 and e.value.doc == '{"a": 1,}'
+
+
+async def test_json_allow_nan(client: Client, echo_body_parts_server: SubprocessServer) -> None:
+    body = b'{"x": NaN, "y": -Infinity, "s": "NaN", "a": 1, "a": 2}'
+    resp = await client.post(echo_body_parts_server.url).body_bytes(body).build().send()
+
+    res = await resp.json(allow_nan=True)
+    assert math.isnan(res["x"]) and res["y"] == -math.inf and res["s"] == "NaN" and res["a"] == 2
+    assert (await resp.json(allow_nan=True, on_duplicate_key="first"))["a"] == 1
+    with pytest.raises(JSONDecodeError, match="duplicate key `a`"):
+        await resp.json(allow_nan=True, on_duplicate_key="error")
+
+    with pytest.raises(JSONDecodeError, match="expected value"):
+        await resp.json()
+    with pytest.raises(JSONDecodeError, match="Expecting value") as e:
+        await resp.json(exact_numbers=True)
+    assert e.value.pos == 6 and e.value.doc == body.decode()
+
+
+@pytest.mark.parametrize("charset", ["iso-8859-1", "utf-16", "utf-8"])
+@pytest.mark.parametrize("exact_numbers", [False, True])
+async def test_json_charset(
+    client: Client, echo_body_parts_server: SubprocessServer, charset: str, exact_numbers: bool
+) -> None:
+    body = '{"a": "bäd", "b": 1.5}'.encode(charset)
+    req = client.post(echo_body_parts_server.url).body_bytes(body)
+    req = req.query({"content_type": f"application/json; charset={charset}"})
+    resp = await req.build().send()
+
+    assert await resp.json(exact_numbers=exact_numbers) == {"a": "bäd", "b": Decimal("1.5") if exact_numbers else 1.5}
+
+
+async def test_json_stream_to(client: Client, echo_body_parts_server: SubprocessServer) -> None:
+    items = [{"id": i, "tags": ["a,]", '"b"'], "nested": {"v": [i]}} for i in range(1000)]
+    body = json.dumps(items).encode()
+
+    async def stream_gen() -> AsyncGenerator[bytes, None]:
+        for i in range(0, len(body), 100):
+            yield body[i : i + 100]
+
+    received: list[Any] = []
+    req = client.post(echo_body_parts_server.url).body_stream(stream_gen()).streamed_read_buffer_limit(100)
+    async with req.build_streamed() as resp:
+        assert (await resp.json_stream_to(received.append)) == 1000
+    assert received == items
+
+    resp = await client.post(echo_body_parts_server.url).body_text(" [ ] ").build().send()
+    assert (await resp.json_stream_to(received.append)) == 0
+
+
+@pytest.mark.parametrize(
+    ("body", "error"),
+    [
+        ("{}", "Expected a single JSON array"),
+        ("[1] 2", "Expected a single JSON array"),
+        ("[1,]", "Expected JSON array element"),
+        ("[1", "Unexpected end of JSON array"),
+        ("[1, nope]", "expected ident"),
+    ],
+)
+async def test_json_stream_to__invalid(
+    client: Client, echo_body_parts_server: SubprocessServer, body: str, error: str
+) -> None:
+    resp = await client.post(echo_body_parts_server.url).body_text(body).build().send()
+    with pytest.raises(JSONDecodeError, match=error):
+        await resp.json_stream_to(lambda _: None)
+
+
+async def test_into_proxy_response(client: Client, raw_server: RawServer) -> None:
+    async def handler(reader: asyncio.StreamReader, writer: asyncio.StreamWriter) -> None:
+        await reader.readuntil(b"\r\n\r\n")
+        writer.write(
+            b"HTTP/1.1 201 Created\r\nTransfer-Encoding: chunked\r\nConnection: keep-alive, X-Hop\r\n"
+            b"Keep-Alive: timeout=5\r\nX-Hop: 1\r\nX-Custom: 2\r\n\r\n"
+            b"5\r\nHello\r\n6\r\n World\r\n0\r\n\r\n"
+        )
+        await writer.drain()
+        writer.close()
+
+    url = await raw_server.start(handler)
+    proxy = (await client.get(url).build().send()).into_proxy_response()
+    assert proxy.status == 201 and proxy.version == "HTTP/1.1"
+    assert proxy.headers == {"x-custom": "2"}
+    forwarded = await ResponseBuilder().status(proxy.status).headers(proxy.headers).body_stream(proxy.body).build()
+    assert forwarded.status == 201 and forwarded.headers == {"x-custom": "2"}
+    assert await forwarded.text() == "Hello World"
+
+    resp = await client.get(url).build().send()
+    proxy = resp.into_proxy_response(strip_hop_by_hop=False)
+    assert proxy.headers["transfer-encoding"] == "chunked" and proxy.headers["x-hop"] == "1"
+    assert b"".join([bytes(chunk) async for chunk in proxy.body]) == b"Hello World"
+    assert resp.headers["transfer-encoding"] == "chunked"  # Response headers are not modified
+
+
+async def test_iter_sse(client: Client, echo_body_parts_server: SubprocessServer) -> None:
+    async def stream_gen() -> AsyncGenerator[bytes, None]:
+        yield b": comment\r\nevent: greet\r\ndata: hello\r"
+        yield b"\ndata:  world\r\nid: 1\r\n\r\n"
+        yield b"data: second\nretry: 300\n\n"
+        yield b"event: empty\n\ndata: third\n\ndata: incomplete"
+
+    req = client.post(echo_body_parts_server.url).body_stream(stream_gen()).streamed_read_buffer_limit(0)
+    async with req.build_streamed() as resp:
+        events = [e async for e in resp.iter_sse()]
+
+    assert [(e.event, e.data, e.id, e.retry) for e in events] == [
+        ("greet", "hello\n world", "1", None),
+        ("message", "second", "1", 300),
+        ("message", "third", "1", None),
+    ]
+    assert repr(events[0]) == "ServerSentEvent(event='greet', data='hello\\n world', id='1', retry=None)"
+
+
+async def test_buffer_all(client: Client, echo_body_parts_server: SubprocessServer) -> None:
+    async def stream_gen() -> AsyncGenerator[bytes, None]:
+        yield b'{"foo": "bar", '
+        yield b'"baz": 123}'
+
+    req = client.post(echo_body_parts_server.url).body_stream(stream_gen()).streamed_read_buffer_limit(1)
+    async with req.build_streamed() as resp:
+        await resp.buffer_all()
+        assert (await resp.json()) == {"foo": "bar", "baz": 123}
+        assert (await resp.json()) == {"foo": "bar", "baz": 123}
+        assert (await resp.text()) == '{"foo": "bar", "baz": 123}'
+        await resp.buffer_all()
+        assert (await resp.body_reader.read_chunk()) is None
+
+    async with client.post(echo_body_parts_server.url).body_stream(stream_gen()).build_streamed() as resp:
+        assert (await resp.body_reader.read_chunk()) == b'{"foo": "bar", '
+        with pytest.raises(RuntimeError, match="Response body already consumed"):
+            await resp.buffer_all()
+
+
+async def test_stream(client: Client, echo_body_parts_server: SubprocessServer) -> None:
+    async def stream_gen() -> AsyncGenerator[bytes, None]:
+        yield b"chunk1"
+        yield b"chunk2"
+
+    resp = await client.post(echo_body_parts_server.url).body_stream(stream_gen()).build().send()
+    assert [bytes(chunk) async for chunk in resp.stream()] == [b"chunk1", b"chunk2"]
+    with pytest.raises(RuntimeError, match="Response body already consumed"):
+        await resp.bytes()
+
+
+async def test_aclose(client: Client, echo_body_parts_server: SubprocessServer) -> None:
+    async def stream_gen() -> AsyncGenerator[bytes, None]:
+        yield b"chunk1"
+        await asyncio.sleep(0.05)
+        yield b"chunk2"
+
+    async with client.post(echo_body_parts_server.url).body_stream(stream_gen()).build_streamed() as resp:
+        assert (await resp.body_reader.read_chunk()) == b"chunk1"
+        await resp.aclose()
+        with pytest.raises(RuntimeError, match="Response body reader is closed"):
+            await resp.body_reader.read_chunk()
+        with pytest.raises(RuntimeError, match="Response body reader is closed"):
+            await resp.bytes()
+        await resp.aclose()  # Closing again is a no-op
+        assert resp.status == 200
+
+    resp = await client.post(echo_body_parts_server.url).body_bytes(b"test").build().send()
+    await resp.aclose()
+    with pytest.raises(RuntimeError, match="Response body reader is closed"):
+        await resp.text()
+
+
+async def test_body_as_mmap(client: Client, echo_body_parts_server: SubprocessServer, tmp_path: Path) -> None:
+    body = random.Random(0).randbytes(4 * 1024 * 1024)
+
+    async def stream_gen() -> AsyncGenerator[bytes, None]:
+        for i in range(0, len(body), 65536):
+            yield body[i : i + 65536]
+
+    async with client.post(echo_body_parts_server.url).body_stream(stream_gen()).build_streamed() as resp:
+        view = await resp.body_as_mmap(tmp_path)
+    assert view.readonly
+    assert len(view) == len(body)
+    rnd = random.Random(1)
+    for _ in range(100):
+        start = rnd.randrange(len(body))
+        end = start + rnd.randrange(1, 4096)
+        assert view[start:end] == body[start:end]
+    assert list(tmp_path.iterdir()) == []  # Temporary file is not visible
+
+    async with client.post(echo_body_parts_server.url).body_bytes(b"").build_streamed() as resp:
+        assert len(await resp.body_as_mmap()) == 0
+
+
+async def test_write_to(client: Client, echo_body_parts_server: SubprocessServer, tmp_path: Path) -> None:
+    body = random.Random(0).randbytes(1024 * 1024)
+
+    async def stream_gen() -> AsyncGenerator[bytes, None]:
+        for i in range(0, len(body), 65536):
+            yield body[i : i + 65536]
+
+    progress: list[int] = []
+    async with client.post(echo_body_parts_server.url).body_stream(stream_gen()).build_streamed() as resp:
+        assert await resp.write_to(tmp_path / "out.bin", progress.append) == len(body)
+    assert (tmp_path / "out.bin").read_bytes() == body
+    assert progress == sorted(progress) and progress[-1] == len(body)
+
+    resp = await client.post(echo_body_parts_server.url).body_bytes(b"abc").build().send()
+    assert await resp.write_to(str(tmp_path / "out.bin")) == 3
+    assert (tmp_path / "out.bin").read_bytes() == b"abc"
+
+    async with client.post(echo_body_parts_server.url).body_bytes(b"abc").build_streamed() as resp:
+        with pytest.raises(FileNotFoundError):
+            await resp.write_to(tmp_path / "missing" / "out.bin")
+
+
 async def test_body_reader_read(client: Client, echo_body_parts_server: SubprocessServer) -> None:
     chars = string.ascii_letters + string.digits
     body = b"".join(chars[v % len(chars)].encode() for v in range(131072))
@@ -242,6 +549,73 @@ async def test_text(
     assert await resp.text() == expect
 
 
+@pytest.mark.parametrize(
+    ("body", "charset"),
+    [
+        pytest.param(b"\xff\xfe" + "bäd 😊".encode("utf-16-le"), None, id="utf16le"),
+        pytest.param(b"\xfe\xff" + "bäd 😊".encode("utf-16-be"), "utf-16le", id="utf16be_overrides_charset"),
+        pytest.param(b"\xef\xbb\xbf" + "bäd 😊".encode(), "iso-8859-1", id="utf8_overrides_charset"),
+    ],
+)
+async def test_text_bom(
+    client: Client, echo_body_parts_server: SubprocessServer, body: bytes, charset: str | None
+) -> None:
+    async def resp_body() -> AsyncGenerator[bytes]:
+        yield body
+
+    content_type = f"text/plain; charset={charset}" if charset else "text/plain"
+    resp = (
+        await client.post(echo_body_parts_server.url)
+        .body_stream(resp_body())
+        .query({"content_type": content_type})
+        .build()
+        .send()
+    )
+    assert await resp.text() == "bäd 😊"
+    assert await resp.text(strip_bom=False) == "\ufeffbäd 😊"
+
+
+@pytest.mark.parametrize(
+    ("content_type", "meta"),
+    [
+        ("text/html", '<meta charset="iso-8859-1">'),
+        ("application/xhtml+xml", "<META CHARSET=latin1>"),
+        ("text/html", '<meta http-equiv="Content-Type" content="text/html; charset=ISO-8859-1" />'),
+        ("text/html; charset=utf-8", '<meta charset="iso-8859-1">'),
+        ("text/plain", '<meta charset="iso-8859-1">'),
+    ],
+)
+async def test_text_sniff_html_charset(
+    client: Client, echo_body_parts_server: SubprocessServer, content_type: str, meta: str
+) -> None:
+    body = f"<html><head>{meta}</head><body>bäd</body></html>".encode("iso-8859-1")
+
+    async def resp_body() -> AsyncGenerator[bytes]:
+        yield body
+
+    req = client.post(echo_body_parts_server.url).body_stream(resp_body()).query({"content_type": content_type})
+    resp = await req.build().send()
+
+    assert await resp.text() == body.decode("utf-8", errors="replace")
+    sniffed = await resp.text(sniff_html_charset=True)
+    if content_type in ("text/html", "application/xhtml+xml"):
+        assert sniffed == body.decode("iso-8859-1")
+    else:
+        assert sniffed == body.decode("utf-8", errors="replace")
+
+
+async def test_decode(client: Client, echo_body_parts_server: SubprocessServer) -> None:
+    async def resp_body() -> AsyncGenerator[bytes]:
+        yield "bäd".encode()
+
+    resp = await client.post(echo_body_parts_server.url).body_stream(resp_body()).build().send()
+    assert await resp.bytes() == "bäd".encode()
+    assert await resp.decode("utf-8") == "bäd"
+    assert await resp.decode("iso-8859-1") == "bÃ¤d"
+    with pytest.raises(ValueError, match="Unknown encoding: foo"):
+        await resp.decode("foo")
+
+
 async def test_mime(client: Client, echo_body_parts_server: SubprocessServer) -> None:
     async def resp_body() -> AsyncGenerator[bytes]:
         yield b"test"
@@ -268,6 +642,48 @@ async def test_mime(client: Client, echo_body_parts_server: SubprocessServer) ->
     assert resp.content_type_mime() is None
 
 
+async def test_content_disposition_filename(client: Client, echo_server: SubprocessServer) -> None:
+    url = echo_server.url.with_query({"header_content_disposition": "attachment; filename*=UTF-8''%e2%82%ac.txt"})
+    resp = await client.get(url).build().send()
+    assert resp.content_disposition_filename() == "€.txt"
+
+    resp.headers["content-disposition"] = 'attachment; filename="foo \\"bar\\".txt"'
+    assert resp.content_disposition_filename() == 'foo "bar".txt'
+    resp.headers["content-disposition"] = "attachment; filename=plain.txt"
+    assert resp.content_disposition_filename() == "plain.txt"
+    resp.headers["content-disposition"] = "attachment; filename*=iso-8859-1'en'%A3%20rates.txt; filename=rates.txt"
+    assert resp.content_disposition_filename() == "£ rates.txt"
+    resp.headers["content-disposition"] = "attachment; filename*=unknown''foo.txt; filename=fallback.txt"
+    assert resp.content_disposition_filename() == "fallback.txt"
+    resp.headers["content-disposition"] = "inline"
+    assert resp.content_disposition_filename() is None
+    assert resp.headers.pop("content-disposition") == "inline"
+    assert resp.content_disposition_filename() is None
+
+
+async def test_links(client: Client, echo_server: SubprocessServer) -> None:
+    url = echo_server.url.with_query({"header_link": '<https://example.com/2>; rel="next", </1>; rel="prev"'})
+    resp = await client.get(url).build().send()
+    assert resp.links["next"]["url"] == "https://example.com/2"
+    assert resp.links == {
+        "next": {"url": "https://example.com/2", "params": {"rel": "next"}},
+        "prev": {"url": "/1", "params": {"rel": "prev"}},
+    }
+
+    resp.headers.append("link", '<https://example.com/9>; REL="last first"; title="a, \\"b\\""; crossorigin')
+    resp.headers.append("link", "<https://example.com/0>; rel=next, <https://example.com/3>")
+    params = {"rel": "last first", "title": 'a, "b"', "crossorigin": ""}
+    assert resp.links == {
+        "next": {"url": "https://example.com/2", "params": {"rel": "next"}},
+        "prev": {"url": "/1", "params": {"rel": "prev"}},
+        "last": {"url": "https://example.com/9", "params": params},
+        "first": {"url": "https://example.com/9", "params": params},
+    }
+
+    assert resp.headers.popall("link")
+    assert resp.links == {}
+
+
 async def test_error_for_status(echo_server: SubprocessServer) -> None:
     async with ClientBuilder().build() as client:
         resp = await client.get(echo_server.url).query([("status", 201)]).build().send()
@@ -284,6 +700,18 @@ async def test_error_for_status(echo_server: SubprocessServer) -> None:
         assert e.value.details and e.value.details["status"] == 500
 
 
+async def test_raise_for_status(echo_server: SubprocessServer) -> None:
+    async with ClientBuilder().build() as client:
+        resp = await client.get(echo_server.url).query([("status", 201)]).build().send()
+        assert resp.raise_for_status() is resp
+        assert (await resp.raise_for_status().json())["method"] == "GET"
+
+        resp = await client.get(echo_server.url).query([("status", 404)]).build().send()
+        with pytest.raises(StatusError, match="HTTP status client error") as e:
+            resp.raise_for_status()
+        assert e.value.details and e.value.details["status"] == 404
+
+
 @pytest.mark.parametrize("read", ["bytes", "text", "json", "reader_bytes", "read", "read_chunk"])
 async def test_response_read_cancel(client: Client, echo_body_parts_server: SubprocessServer, read: str) -> None:
     buf_size = 1024
@@ -6,16 +6,24 @@ from concurrent.futures import ThreadPoolExecutor
 from contextlib import contextmanager
 from contextvars import ContextVar
 from datetime import timedelta
+from pathlib import Path
 from typing import Any, TypeVar
 
 import pytest
 from pyreqwest.client import BaseClient, BaseClientBuilder, SyncClient, SyncClientBuilder
-from pyreqwest.client.types import SyncJsonLoadsContext
-from pyreqwest.exceptions import ClientClosedError, PoolTimeoutError
+from pyreqwest.client.types import RecordEntry, SyncJsonLoadsContext
+from pyreqwest.exceptions import ApiError, ClientClosedError, PoolTimeoutError
 from pyreqwest.http import HeaderMap
 from pyreqwest.middleware import SyncNext
 from pyreqwest.middleware.types import SyncMiddleware
-from pyreqwest.request import BaseRequestBuilder, Request, SyncConsumedRequest, SyncRequestBuilder, SyncStreamRequest
+from pyreqwest.request import (
+    BaseRequestBuilder,
+    Request,
+    SyncConsumedRequest,
+    SyncRequestBuilder,
+    SyncRequestTemplate,
+    SyncStreamRequest,
+)
 from pyreqwest.response import BaseResponse, SyncResponse, SyncResponseBodyReader
 
 from tests.servers.server_subprocess import SubprocessServer
@@ -41,6 +49,8 @@ def client() -> Generator[SyncClient, None, None]:
 
 def test_send(client: SyncClient, echo_server: SubprocessServer) -> None:
     assert client.get(echo_server.url).build().send().json()["method"] == "GET"
+    assert client.get(echo_server.url, params={"a": "1"}).build().send().json()["query"] == [["a", "1"]]
+    assert client.get(echo_server.url).build().send().raise_for_status().json()["method"] == "GET"
 
 
 @pytest.mark.parametrize("str_url", [False, True])
@@ -64,6 +74,36 @@ def test_http_methods(echo_server: SubprocessServer, str_url: bool):
             assert response.json()["method"] == "QUERY"
 
 
+def test_stream_request(client: SyncClient, echo_server: SubprocessServer) -> None:
+    with client.stream("GET", echo_server.url) as response:
+        assert response.status == 200
+        chunks = []
+        while (chunk := response.body_reader.read_chunk()) is not None:
+            chunks.append(bytes(chunk))
+        assert json.loads(b"".join(chunks))["method"] == "GET"
+
+    with client.stream("POST", str(echo_server.url)) as response:
+        assert response.json()["method"] == "POST"
+
+
+def test_iter_sse(client: SyncClient, echo_body_parts_server: SubprocessServer) -> None:
+    body = b"event: greet\ndata: hello\ndata: world\nid: 1\n\n: comment\ndata: second\n\n"
+    resp = client.post(echo_body_parts_server.url).body_bytes(body).build().send()
+    events = [(e.event, e.data, e.id, e.retry) for e in resp.iter_sse()]
+    assert events == [("greet", "hello\nworld", "1", None), ("message", "second", "1", None)]
+
+
+def test_iter_bytes(client: SyncClient, echo_body_parts_server: SubprocessServer) -> None:
+    def stream_gen() -> Iterator[bytes]:
+        yield b"chunk1"
+        yield b"chunk2"
+
+    resp = client.post(echo_body_parts_server.url).body_stream(stream_gen()).build().send()
+    assert [bytes(chunk) for chunk in resp.iter_bytes()] == [b"chunk1", b"chunk2"]
+    with pytest.raises(RuntimeError, match="Response body already consumed"):
+        resp.bytes()
+
+
 def test_read(client: SyncClient, echo_body_parts_server: SubprocessServer) -> None:
     chars = string.ascii_letters + string.digits
     body = b"".join(chars[v % len(chars)].encode() for v in range(131072))
@@ -97,6 +137,15 @@ def test_middleware(echo_server: SubprocessServer) -> None:
         assert resp.headers["x-test2"] == "bar"
 
 
+def test_record(echo_server: SubprocessServer) -> None:
+    records: list[RecordEntry] = []
+    with client_builder().error_for_status(False).record(records.append).build() as client:
+        client.get(echo_server.url).build().send()
+        client.get(echo_server.url.with_query({"status": 500})).build().send()
+
+    assert [(r["method"], r["status"], r["error"]) for r in records] == [("GET", 200, None), ("GET", 500, None)]
+
+
 def test_middleware__bad() -> None:
     async def bad_middleware(_request: Request, _next_handler: SyncNext) -> SyncResponse:
         raise RuntimeError("bad middleware")
@@ -156,6 +205,64 @@ def test_stream(client: SyncClient, echo_body_parts_server: SubprocessServer) ->
         assert resp.body_reader.read_chunk() is None
 
 
+def test_buffer_all(client: SyncClient, echo_body_parts_server: SubprocessServer) -> None:
+    with client.post(echo_body_parts_server.url).body_bytes(b'{"foo": "bar"}').build_streamed() as resp:
+        resp.buffer_all()
+        assert resp.json() == {"foo": "bar"}
+        assert resp.json() == {"foo": "bar"}
+
+
+def test_close(client: SyncClient, echo_body_parts_server: SubprocessServer) -> None:
+    with client.post(echo_body_parts_server.url).body_text("test").build_streamed() as resp:
+        resp.close()
+        with pytest.raises(RuntimeError, match="Response body reader is closed"):
+            resp.bytes()
+        resp.close()  # Closing again is a no-op
+        assert resp.status == 200
+
+
+def test_body_as_mmap(client: SyncClient, echo_body_parts_server: SubprocessServer) -> None:
+    body = b"".join(i.to_bytes(4, "big") for i in range(262144))
+    with client.post(echo_body_parts_server.url).body_bytes(body).build_streamed() as resp:
+        view = resp.body_as_mmap()
+    assert len(view) == len(body)
+    assert view[4000:4008] == body[4000:4008]
+    assert view[-4:] == body[-4:]
+
+
+def test_error_for_json(client: SyncClient, echo_body_parts_server: SubprocessServer) -> None:
+    def predicate(obj: Any) -> Any:
+        return obj.get("error") if isinstance(obj, dict) else None
+
+    req = client.post(echo_body_parts_server.url).body_json({"error": "bad"}).error_for_json(predicate).build()
+    with pytest.raises(ApiError, match="Response JSON contains an error") as e:
+        req.send()
+    assert e.value.details == {"status": 200, "error": "bad"}
+
+    resp = client.post(echo_body_parts_server.url).body_text("not json").error_for_json(predicate).build().send()
+    assert resp.text() == "not json"
+
+    def custom_loads(ctx: SyncJsonLoadsContext) -> Any:
+        return {"wrapped": json.loads(ctx.body_reader.bytes().to_bytes())}
+
+    with client_builder().json_handler(loads=custom_loads).build() as custom_client:
+        req = custom_client.post(echo_body_parts_server.url).body_json({"error": "bad"})
+        with pytest.raises(ApiError) as e:
+            req.error_for_json(lambda obj: obj["wrapped"].get("error")).build().send()
+        assert e.value.details == {"status": 200, "error": "bad"}
+        req = custom_client.post(echo_body_parts_server.url).body_json({"ok": 1})
+        assert req.error_for_json(predicate).build().send().json() == {"wrapped": {"ok": 1}}
+
+
+def test_write_to(client: SyncClient, echo_body_parts_server: SubprocessServer, tmp_path: Path) -> None:
+    body = b"".join(i.to_bytes(4, "big") for i in range(262144))
+    progress: list[int] = []
+    with client.post(echo_body_parts_server.url).body_bytes(body).build_streamed() as resp:
+        assert resp.write_to(tmp_path / "out.bin", progress.append) == len(body)
+    assert (tmp_path / "out.bin").read_bytes() == body
+    assert progress[-1] == len(body)
+
+
 @pytest.mark.parametrize("call", ["copy", "__copy__"])
 @pytest.mark.parametrize("build_streamed", [False, True])
 @pytest.mark.parametrize("body_streamed", [False, True])
@@ -219,6 +326,15 @@ def test_request_copy(echo_server: SubprocessServer, call: str, build_streamed:
         assert req1.send().json() == req2.send().json()
 
 
+def test_freeze(client: SyncClient, echo_server: SubprocessServer) -> None:
+    template = client.get(echo_server.url).header("X-Test", "Val").freeze()
+    assert type(template) is SyncRequestTemplate
+    for _ in range(3):
+        req = template.clone()
+        assert type(req) is SyncConsumedRequest
+        assert ["x-test", "Val"] in req.send().json()["headers"]
+
+
 @pytest.mark.parametrize("concurrency", [1, 2, 10])
 @pytest.mark.parametrize("limit", [None, 1, 2, 10])
 def test_concurrent_requests(echo_server: SubprocessServer, concurrency: int, limit: int | None) -> None:
@@ -249,6 +365,16 @@ def test_max_connections_pool_timeout(echo_server: SubprocessServer, max_conn: i
             assert all(fut.result()["method"] == "GET" for fut in futures)
 
 
+def test_send_head_only(echo_server: SubprocessServer):
+    url = echo_server.url.with_query({"sleep_body": 10, "header_X_Test": "val"})
+    with client_builder().max_connections(1).pool_timeout(timedelta(seconds=1)).build() as client:
+        resp = client.get(url).send_head_only()
+        assert resp.status == 200 and resp.headers["x-test"] == "val"
+        assert client.get(url).send_head_only().status == 200  # Connection was released without waiting for the body
+        with pytest.raises(RuntimeError, match="Response body reader is closed"):
+            resp.text()
+
+
 def test_json_loads_callback(echo_server: SubprocessServer):
     called = 0
 
@@ -275,6 +401,11 @@ def test_json_loads_callback(echo_server: SubprocessServer):
         assert resp.json() == {**res, "test": "bar"}
         assert called == 2
 
+        for kwargs in ({"on_duplicate_key": "error"}, {"exact_numbers": True}, {"allow_nan": True}):
+            with pytest.raises(ValueError, match="not supported with a custom json_handler loads"):
+                resp.json(**kwargs)  # type: ignore[arg-type]
+        assert called == 2
+
     async def bad_loads(_ctx: SyncJsonLoadsContext) -> Any:
         raise RuntimeError("should not be called")
 
@@ -288,15 +419,17 @@ def test_json_loads_callback(echo_server: SubprocessServer):
 def test_use_after_close(echo_server: SubprocessServer):
     with client_builder().build() as client:
         assert client.get(echo_server.url).build().send().status == 200
-    req = client.get(echo_server.url).build()
+        req = client.get(echo_server.url).build()
     with pytest.raises(ClientClosedError, match="Client was closed"):
         req.send()
+    with pytest.raises(ClientClosedError, match="Client was closed"):
+        client.get(echo_server.url)
 
     client = client_builder().build()
     client.close()
-    req = client.get(echo_server.url).build()
+    client.close()  # Idempotent
     with pytest.raises(ClientClosedError, match="Client was closed"):
-        req.send()
+        client.get(echo_server.url).build().send()
 
 
 def test_stream_use_after_close(client: SyncClient, echo_body_parts_server: SubprocessServer):
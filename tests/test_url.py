@@ -189,6 +189,25 @@ def test_fragment():
     assert Url("http://example.com").fragment is None
 
 
+def test_data_uri():
+    url = Url("data:text/plain;base64,aGk=")
+    assert url.is_data_uri is True
+    assert url.data_uri_decode() == ("text/plain", b"hi")
+
+    assert Url("data:,Hello%2C%20World%21").data_uri_decode() == ("text/plain", b"Hello, World!")
+    assert Url("data:;charset=utf-8,abc").data_uri_decode() == ("text/plain;charset=utf-8", b"abc")
+    assert Url("data:application/json,%7B%7D#frag").data_uri_decode() == ("application/json", b"{}")
+    assert Url("data:image/png;BASE64,aG k").data_uri_decode() == ("image/png", b"hi")
+
+    assert Url("http://example.com").is_data_uri is False
+    with pytest.raises(ValueError, match="Not a data URI"):
+        Url("http://example.com").data_uri_decode()
+    with pytest.raises(ValueError, match="missing ','"):
+        Url("data:text/plain").data_uri_decode()
+    with pytest.raises(ValueError, match="Invalid symbol"):
+        Url("data:;base64,@@@").data_uri_decode()
+
+
 def test_with_fragment():
     url = Url("http://example.com")
     assert str(url.with_fragment("section1")) == "http://example.com/#section1"
@@ -220,6 +239,38 @@ def test_extend_query():
     assert str(url) == "http://example.com/"
 
 
+def test_with_query_merged():
+    url = Url("http://example.com/?a=old&b=2")
+    assert str(url.with_query_merged({"a": "new"})) == "http://example.com/?a=new&b=2"
+    assert str(url.with_query_merged({"c": "3", "a": "new"})) == "http://example.com/?a=new&b=2&c=3"
+    assert str(url.with_query_merged({})) == "http://example.com/?a=old&b=2"
+    assert str(url) == "http://example.com/?a=old&b=2"
+
+    url = Url("http://example.com/?a=1&b=2&a=3")
+    assert str(url.with_query_merged([("a", "x"), ("a", "y")])) == "http://example.com/?a=x&a=y&b=2"
+    assert str(url.with_query_merged({"a": ["x", "y"], "b": 5})) == "http://example.com/?a=x&a=y&b=5"
+
+    url = Url("http://example.com")
+    assert str(url.with_query_merged({"a": "1"})) == "http://example.com/?a=1"
+    assert str(url.with_query_merged({})) == "http://example.com/"
+
+
+def test_with_query_removed():
+    url = Url("http://example.com/?a=1&utm=x&b=2&utm=y&c=a%20b")
+    assert str(url.with_query_removed("utm")) == "http://example.com/?a=1&b=2&c=a+b"
+    assert str(url.with_query_removed("missing")) == "http://example.com/?a=1&utm=x&b=2&utm=y&c=a+b"
+    assert str(url) == "http://example.com/?a=1&utm=x&b=2&utm=y&c=a%20b"
+    assert str(Url("http://example.com/?a=1&a=2").with_query_removed("a")) == "http://example.com/"
+
+
+def test_with_query_set():
+    url = Url("http://example.com/?a=1&b=2&a=3&c=4")
+    assert str(url.with_query_set("a", "x")) == "http://example.com/?b=2&c=4&a=x"
+    assert str(url.with_query_set("d", "y z")) == "http://example.com/?a=1&b=2&a=3&c=4&d=y+z"
+    assert str(url) == "http://example.com/?a=1&b=2&a=3&c=4"
+    assert str(Url("http://example.com").with_query_set("a", "1")) == "http://example.com/?a=1"
+
+
 def test_with_query_string():
     url = Url("http://example.com?key=value")
     assert str(url.with_query_string("key2=value2")) == "http://example.com/?key2=value2"
@@ -244,6 +295,37 @@ def test_with_path_segments():
     assert str(url) == "http://example.com/old"
 
 
+def test_insert_path_segment():
+    url = Url("http://example.com/a/b?q=1")
+    assert str(url.insert_path_segment(1, "new")) == "http://example.com/a/new/b?q=1"
+    assert str(url.insert_path_segment(0, "new")) == "http://example.com/new/a/b?q=1"
+    assert str(url.insert_path_segment(100, "new")) == "http://example.com/a/b/new?q=1"
+    assert str(url.insert_path_segment(1, "a b/c%")) == "http://example.com/a/a%20b%2Fc%25/b?q=1"
+    assert str(url) == "http://example.com/a/b?q=1"
+
+    url = Url("http://example.com/a%2Fb/c/")
+    assert str(url.insert_path_segment(1, "new")) == "http://example.com/a%2Fb/new/c/"
+    assert str(url.insert_path_segment(2, "new")) == "http://example.com/a%2Fb/c/new/"
+    assert str(url.insert_path_segment(5, "new")) == "http://example.com/a%2Fb/c/new/"
+
+    assert str(Url("http://example.com").insert_path_segment(0, "new")) == "http://example.com/new"
+    assert str(Url("http://example.com/").insert_path_segment(1, "new")) == "http://example.com/new"
+
+    with pytest.raises(ValueError, match="cannot be base"):
+        Url("mailto:foo@example.com").insert_path_segment(0, "new")
+
+
+def test_pop_path_segment():
+    url = Url("http://example.com/a%2Fb/c?q=1")
+    assert str(url.pop_path_segment()) == "http://example.com/a%2Fb?q=1"
+    assert str(url.pop_path_segment().pop_path_segment()) == "http://example.com/?q=1"
+    assert str(url.pop_path_segment().pop_path_segment().pop_path_segment()) == "http://example.com/?q=1"
+    assert str(Url("http://example.com/a/b/").pop_path_segment()) == "http://example.com/a"
+
+    with pytest.raises(ValueError, match="cannot be base"):
+        Url("mailto:foo@example.com").pop_path_segment()
+
+
 def test_with_port():
     url = Url("http://example.com:1234")
     assert str(url.with_port(8080)) == "http://example.com:8080/"
@@ -288,6 +370,23 @@ def test_with_scheme():
     assert str(url) == "http://example.com/"
 
 
+def test_upgrade_to_https():
+    assert str(Url("http://h:80/").upgrade_to_https()) == "https://h/"
+    assert str(Url("http://h/path?q=1").upgrade_to_https()) == "https://h/path?q=1"
+    assert str(Url("http://h:8080/").upgrade_to_https()) == "https://h:8080/"
+    assert str(Url("https://h:8443/").upgrade_to_https()) == "https://h:8443/"
+    with pytest.raises(ValueError, match="Expected http or https URL"):
+        Url("ftp://h/").upgrade_to_https()
+
+
+def test_downgrade_to_http():
+    assert str(Url("https://h:443/").downgrade_to_http()) == "http://h/"
+    assert str(Url("https://h:8443/path").downgrade_to_http()) == "http://h:8443/path"
+    assert str(Url("http://h:8080/").downgrade_to_http()) == "http://h:8080/"
+    with pytest.raises(ValueError, match="Expected https or http URL"):
+        Url("ws://h/").downgrade_to_http()
+
+
 def test_copy():
     url = Url("http://example.com")
     url2 = copy(url)